@@ -79,16 +79,75 @@ load_order_string_info_paste = <p>Select the mode you want, and paste the String
     <ul>
         <li><b>Runcher mode:</b> select this if you're importing a Load Order String generated on another Runcher instance</li>
         <li><b>Modlist mode:</b> select this if you're importing a Load Order from a "used_mods.txt" or similar file. This is for importing load orders from other mod managers.</li>
+        <li><b>Official Launcher mode:</b> select this if you're importing the mods enabled in the official launcher, from its "moddata.dat" file.</li>
+        <li><b>Mod Manager JSON mode:</b> select this if you're importing a JSON export from another mod manager.</li>
     </ul>
 
-load_order_string_info_copy = Copy this String, and paste it in another instance of Runcher to replicate this load order.
+load_order_string_info_copy = Copy this String, and paste it in another instance of Runcher to replicate this load order. It includes the mods, movie packs and categories, and the current launch options.
 load_order_string_title_copy = Copy Load Order
 load_order_string_title_paste = Paste Load Order
+export_to_launcher = Export to Official Launcher
+export_to_launcher_success = Mod data of the official launcher written to {"{"}{"}"}. The official launcher will enable these mods, in this order, the next time it starts.
+export_modpack = Export Modpack
+export_modpack_success = Modpack exported, with {"{"}{"}"} packs bundled and {"{"}{"}"} Workshop mods listed.
+import_modpack = Import Modpack
+import_workshop_collection = Import Workshop Collection
+import_workshop_collection_label = Id of the collection, or link to its Workshop page. Mods not yet downloaded will be subscribed to and downloaded.
+modpack_file_type = Runcher Modpack
+import_launch_options_title = Import Launch Options
+import_launch_options_question = The imported load order includes launch options different from the current ones. Do you want to use them?
 
 enable_logging = Enable Logging
 enable_skip_intro = Skip Intro Videos
 pack_type = Pack Type
 steam_id = Steam ID
+file_conflicts = File Conflicts
+file_conflicts_summary = Overwrites {"{"}{"}"}, overwritten in {"{"}{"}"}
+file_conflicts_won = Overwrites these files from (lower priority packs):
+file_conflicts_lost = These files are overwritten by:
+file_conflicts_more = ...and {"{"}{"}"} more.
+data_conflicts_tooltip = Check which DB rows and loc keys are defined by more than one pack of the load order.
+undo_mod_list_tooltip = Undo the last change to the enabled mods, categories or load order (Ctrl+Z).
+redo_mod_list_tooltip = Redo the last undone change to the enabled mods, categories or load order (Ctrl+Shift+Z).
+launch_snapshots_tooltip = Restore the enabled mods and load order you had when you launched the game.
+launch_snapshots_empty = No launches yet.
+launch_snapshot = Launched on {"{"}{"}"} ({"{"}{"}"} mods enabled)
+data_conflicts_title = Data Conflicts
+data_conflicts_explanation_title = Explanation
+data_conflicts_explanation = These are the DB rows and loc keys defined with different values by more than one pack of the load order. Each row shows the values the game ends up using, and expanding it shows all the packs defining it, from higher to lower priority.
+
+    The game merges table files by their file name, not by the position of their pack in the load order, so a pack lower in the load order may still win a row. Tables that cannot be decoded with the current schema are not checked.
+data_conflicts_none = No DB or loc conflicts found between the packs of the load order.
+data_conflicts_row = Row
+data_conflicts_values = Values
+translation_coverage_tooltip = Check how much of each pack of the load order is translated to the language selected in the launch options.
+translation_coverage_title = Translation Coverage ({"{"}{"}"})
+translation_coverage_explanation_title = Explanation
+translation_coverage_explanation = {"{"}{"}"} of the {"{"}{"}"} loc keys of the load order are translated to {"{"}{"}"}. Each row shows the translation status of a pack, and expanding it shows the keys that will be shown in english ingame.
+
+    Keys are untranslated if the translation doesn't include them or its text is empty, and need retranslation if their translation is marked as outdated. Local translations have priority over the ones from the translation hub.
+translation_coverage_none = No packs with loc files found in the load order.
+translation_coverage_no_language = There is no translation language selected. Select one in the launch options first.
+translation_coverage_coverage = Coverage
+translation_coverage_translated = Translated
+translation_coverage_untranslated = Untranslated
+translation_coverage_needs_retranslation = Needs Retranslation
+translation_coverage_no_translation = No translation
+translation_coverage_export = Export
+translation_coverage_file_type = JSON Files
+edit_local_translation = Edit Local Translation
+translation_editor_title = Local Translation: {"{"}{"}"} ({"{"}{"}"})
+translation_editor_explanation = These are the loc keys of the pack, with their english text, their translation from the translation hub, and their local translation. Write in the last column the translations you want to use instead of the ones from the hub, and leave it empty to keep the one from the hub.
+
+    Local translations have priority over the ones from the hub, so once saved, the next launch will use them. Keys without local translation keep the one from the hub at the time of saving. Hub translations in italics are marked as outdated, and are not used ingame.
+translation_editor_filter = Filter by key or text...
+translation_editor_key = Key
+translation_editor_original = Original (English)
+translation_editor_hub = Translation Hub
+translation_editor_local = Local Translation
+translation_editor_hub_outdated = This translation is marked as outdated, so it is not used ingame.
+translation_editor_nothing_to_translate = This pack doesn't have any loc keys to translate.
+translation_editor_saved = Local translation of {"{"}{"}"} saved. It will be used the next time you launch the game.
 game_paths = Game Paths
 flags = Flags
 
@@ -197,9 +256,12 @@ profile_details_title = Profile Details
 profile_rename = Rename Profile
 profile_delete = Delete Profile
 profile_shortcut_new = Create Shortcut
+profile_publish_collection = Publish as Workshop Collection
+profile_publish_collection_success = <p>Mods published as the Workshop collection <a href="https://steamcommunity.com/sharedfiles/filedetails/?id={"{"}{"}"}">{"{"}{"}"}</a>.</p>
 profile_manager_title = Profile Manager
 
 are_you_sure_delete_profile = Are you sure you want to delete the currently selected profile?
+are_you_sure_unsubscribe = Are you sure you want to unsubscribe from the selected mods? Steam will delete them once the game is closed.
 
 profile_shortcut = New Shortcut
 profile_shortcut_name = Shortcut Name
@@ -231,6 +293,8 @@ open_workshop_link_in_steam = Open Workshop Links on Steam
 
 import_string_modlist_mode = Modlist Mode
 import_string_runcher_mode = Runcher Mode
+import_string_ca_launcher_mode = Official Launcher Mode
+import_string_json_mode = Mod Manager JSON Mode
 
 settings_secondary_mods_folder = Secondary Mods Folder
 settings_secondary_mods_folder_ph = This is an alternative folder to place mods, so /data doesn't get too crowded.
@@ -240,7 +304,13 @@ upload_to_workshop = Upload/Update To Workshop
 upload_workshop_title = Title
 upload_workshop_description = Description
 upload_workshop_changelog = Changelog
-upload_workshop_tag = Tag
+upload_workshop_tag = Tags
+upload_workshop_preview = Preview
+upload_workshop_preview_select = Select Preview Image
+upload_workshop_preview_file_type = PNG Images
+upload_workshop_preview_invalid = The selected file is not an image, or cannot be read.
+upload_workshop_preview_info = {"{"}{"}"}x{"{"}{"}"} px, {"{"}{"}"} KB.
+upload_workshop_preview_not_square = The image is not square, so it'll be cropped in the Workshop listings.
 upload_workshop_visibility = Visibility
 
 upload_workshop_visibility_public = Public
@@ -248,6 +318,19 @@ upload_workshop_visibility_friends_only = Friends Only
 upload_workshop_visibility_private = Private
 upload_workshop_visibility_unlisted = Unlisted
 
+upload_workshop_preflight = Pre-flight Check
+upload_workshop_preflight_ok = <p>No problems found.</p>
+upload_workshop_preflight_blocking = <p>The following problems must be fixed before uploading the mod:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+upload_workshop_preflight_warnings = <p>The following problems were found. You can still upload the mod, but you should check them:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+upload_workshop_preflight_error = Error
+upload_workshop_preflight_warning = Warning
+
 copy_to_secondary = Copy to Secondary folder
 move_to_secondary = Move to Secondary folder
 
@@ -271,6 +354,40 @@ mods_downloaded = Mods downloaded. Please, MAKE SURE TO CHECK THE LOG.
     The log contains the links to them.
 
 download_from_workshop = Download Selected Mods
+unsubscribe_from_workshop = Unsubscribe from Selected Mods
+resubscribe_to_workshop = Re-subscribe to Selected Mods (Fix Broken Downloads)
+remove_orphaned_content = Remove Orphaned Workshop Folders
+remove_orphaned_content_none = No orphaned Workshop folders found.
+remove_orphaned_content_question = <p>The following Workshop folders belong to mods you're no longer subscribed to:</p><ul>{"{"}{"}"}</ul><p>Do you want to delete them?</p>
+
+missing_dependencies_title = Missing Dependencies
+missing_dependencies_fixable = <p>The following mods have dependencies that are not in the load order:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+    <p>Do you want to enable the installed ones and download the ones from the Workshop? Press "No" to launch the game without them.</p>
+missing_dependencies_not_fixable = <p>The following mods have dependencies that are not installed:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+    <p>The game may crash or misbehave without them. Do you want to launch it anyway?</p>
+missing_dependency_disabled = Installed, but disabled.
+missing_dependency_not_downloaded = Not downloaded.
+missing_dependency_not_found = Not installed.
+
+save_mods = Mods used by this save:
+save_load_order_mismatch_title = Save Made With Different Mods
+save_mods_missing = <p>The save selected uses these mods, but they're not in the load order:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+save_mods_extra = <p>These mods are in the load order, but the save selected doesn't use them:</p>
+    <ul>
+    {"{"}{"}"}
+    </ul>
+save_mods_unreadable = <p>{"{"}{"}"}</p>
+    <p>Runcher cannot check if the load order matches the mods of the save. Do you want to launch the game anyway?</p>
+save_restore_load_order = <p>Do you want to restore the load order the save was made with before launching the game? Press "No" to launch it with the current load order.</p>
 
 log_anaylis_title = Log Analysis
 log_anaylis_explanation_title = Explanation
@@ -285,3 +402,7 @@ enable_debug_terminal = Enable Debug Terminal
 remove_trait_limit = Remove Trait Limit
 updates_locked = Updates Locked
 universal_rebalancer = Universal Rebalancer
+linux_launch_command = Linux Launcher Command
+linux_launch_command_ph = Command used to launch the game on Linux. {"{"}app_id{"}"}, {"{"}exe{"}"} and {"{"}game_path{"}"} are replaced before launching. Example: steam -applaunch {"{"}app_id{"}"}
+linux_launch_command_tt = Command used to launch the game on Linux. {"{"}app_id{"}"}, {"{"}exe{"}"} and {"{"}game_path{"}"} are replaced before launching.
+    When Runcher needs to wait for the game to close (like when checking the logs after playing), it waits for this command to finish. Proton or wine commands finish when the game closes, but "steam -applaunch {"{"}app_id{"}"}" finishes as soon as Steam starts the game.
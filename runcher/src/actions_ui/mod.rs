@@ -34,6 +34,8 @@ use std::rc::Rc;
 use rpfm_ui_common::locale::qtr;
 use rpfm_ui_common::utils::*;
 
+use crate::games::launch_options::LaunchOptions;
+
 const VIEW_DEBUG: &str = "ui_templates/actions_groupbox.ui";
 const VIEW_RELEASE: &str = "ui/actions_groupbox.ui";
 
@@ -64,7 +66,11 @@ pub struct ActionsUI {
     open_runcher_error_folder: QPtr<QAction>,
 
     copy_load_order_button: QPtr<QToolButton>,
+    export_to_launcher: QPtr<QAction>,
+    export_modpack: QPtr<QAction>,
     paste_load_order_button: QPtr<QToolButton>,
+    import_modpack: QPtr<QAction>,
+    import_workshop_collection: QPtr<QAction>,
     reload_button: QPtr<QToolButton>,
     download_subscribed_mods_button: QPtr<QToolButton>,
 
@@ -204,6 +210,18 @@ impl ActionsUI {
         let download_subscribed_mods_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "download_subscribed_mods_button")?;
         copy_load_order_button.set_tool_tip(&qtr("copy_load_order"));
         paste_load_order_button.set_tool_tip(&qtr("paste_load_order"));
+
+        let copy_load_order_menu = QMenu::from_q_widget(&copy_load_order_button);
+        let export_to_launcher = copy_load_order_menu.add_action_q_icon_q_string(&QIcon::from_theme_1a(&QString::from_std_str("document-export")), &qtr("export_to_launcher"));
+        let export_modpack = copy_load_order_menu.add_action_q_icon_q_string(&QIcon::from_theme_1a(&QString::from_std_str("archive-insert")), &qtr("export_modpack"));
+        copy_load_order_button.set_menu(copy_load_order_menu.into_raw_ptr());
+        copy_load_order_button.set_popup_mode(ToolButtonPopupMode::MenuButtonPopup);
+
+        let paste_load_order_menu = QMenu::from_q_widget(&paste_load_order_button);
+        let import_modpack = paste_load_order_menu.add_action_q_icon_q_string(&QIcon::from_theme_1a(&QString::from_std_str("archive-extract")), &qtr("import_modpack"));
+        let import_workshop_collection = paste_load_order_menu.add_action_q_icon_q_string(&QIcon::from_theme_1a(&QString::from_std_str("folder-download")), &qtr("import_workshop_collection"));
+        paste_load_order_button.set_menu(paste_load_order_menu.into_raw_ptr());
+        paste_load_order_button.set_popup_mode(ToolButtonPopupMode::MenuButtonPopup);
         reload_button.set_tool_tip(&qtr("reload"));
         download_subscribed_mods_button.set_tool_tip(&qtr("download_subscribed_mods"));
 
@@ -246,7 +264,11 @@ impl ActionsUI {
             open_runcher_error_folder,
 
             copy_load_order_button,
+            export_to_launcher,
+            export_modpack,
             paste_load_order_button,
+            import_modpack,
+            import_workshop_collection,
             reload_button,
             download_subscribed_mods_button,
 
@@ -262,4 +284,52 @@ impl ActionsUI {
 
         Ok(ui)
     }
+
+    /// This function returns the launch options currently set in the UI.
+    ///
+    /// Options disabled for the current game are ignored.
+    pub unsafe fn launch_options(&self) -> LaunchOptions {
+        let mut launch_options = LaunchOptions::default();
+        launch_options.set_enable_logging(self.enable_logging_checkbox().is_enabled() && self.enable_logging_checkbox().is_checked());
+        launch_options.set_enable_skip_intros(self.enable_skip_intro_checkbox().is_enabled() && self.enable_skip_intro_checkbox().is_checked());
+        launch_options.set_remove_trait_limit(self.remove_trait_limit_checkbox().is_enabled() && self.remove_trait_limit_checkbox().is_checked());
+        launch_options.set_merge_all_mods(self.merge_all_mods_checkbox().is_enabled() && self.merge_all_mods_checkbox().is_checked());
+
+        if self.enable_translations_combobox().is_enabled() && self.enable_translations_combobox().current_index() != 0 {
+            launch_options.set_translations_language(Some(self.enable_translations_combobox().current_text().to_std_string()));
+        }
+
+        if self.unit_multiplier_spinbox().is_enabled() {
+            launch_options.set_unit_multiplier(self.unit_multiplier_spinbox().value());
+        }
+
+        if self.universal_rebalancer_combobox().is_enabled() && self.universal_rebalancer_combobox().current_index() != 0 {
+            launch_options.set_universal_rebalancer_base_mod(Some(self.universal_rebalancer_combobox().current_text().to_std_string()));
+        }
+
+        launch_options
+    }
+
+    /// This function sets the provided launch options in the UI.
+    ///
+    /// Values not available in the comboboxes are reset to none.
+    pub unsafe fn set_launch_options(&self, launch_options: &LaunchOptions) {
+        self.enable_logging_checkbox().set_checked(*launch_options.enable_logging());
+        self.enable_skip_intro_checkbox().set_checked(*launch_options.enable_skip_intros());
+        self.remove_trait_limit_checkbox().set_checked(*launch_options.remove_trait_limit());
+        self.merge_all_mods_checkbox().set_checked(*launch_options.merge_all_mods());
+        self.unit_multiplier_spinbox().set_value(*launch_options.unit_multiplier());
+
+        let translations_index = match launch_options.translations_language() {
+            Some(language) => self.enable_translations_combobox().find_text_1a(&QString::from_std_str(language)),
+            None => 0,
+        };
+        self.enable_translations_combobox().set_current_index(translations_index.max(0));
+
+        let universal_rebalancer_index = match launch_options.universal_rebalancer_base_mod() {
+            Some(base_mod) => self.universal_rebalancer_combobox().find_text_1a(&QString::from_std_str(base_mod)),
+            None => 0,
+        };
+        self.universal_rebalancer_combobox().set_current_index(universal_rebalancer_index.max(0));
+    }
 }
@@ -14,12 +14,16 @@ use qt_widgets::QApplication;
 use qt_widgets::QButtonGroup;
 use qt_widgets::QComboBox;
 use qt_widgets::QGroupBox;
+use qt_widgets::QInputDialog;
+use qt_widgets::q_header_view::ResizeMode;
 use qt_widgets::QLineEdit;
 use qt_widgets::QRadioButton;
 use qt_widgets::QTabWidget;
 use qt_widgets::QToolBar;
 use qt_widgets::{QDialog, QDialogButtonBox, q_dialog_button_box::StandardButton};
+use qt_widgets::{QFileDialog, q_file_dialog::FileMode};
 use qt_widgets::QLabel;
+use qt_widgets::QListWidget;
 use qt_widgets::QMainWindow;
 use qt_widgets::QMessageBox;
 use qt_widgets::q_message_box;
@@ -27,15 +31,19 @@ use qt_widgets::QPushButton;
 use qt_widgets::QSplitter;
 use qt_widgets::QTableView;
 use qt_widgets::QTextEdit;
+use qt_widgets::QToolButton;
 use qt_widgets::QTreeView;
 use qt_widgets::QWidget;
 
 use qt_gui::QFont;
 use qt_gui::QIcon;
 use qt_gui::QListOfQStandardItem;
+use qt_gui::QPixmap;
 use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
 
+use qt_core::AspectRatioMode;
+use qt_core::CaseSensitivity;
 use qt_core::CheckState;
 use qt_core::Orientation;
 use qt_core::QBox;
@@ -46,36 +54,36 @@ use qt_core::QPtr;
 use qt_core::QSize;
 use qt_core::QSortFilterProxyModel;
 use qt_core::QString;
+use qt_core::QTimer;
 use qt_core::QVariant;
 use qt_core::SlotNoArgs;
+use qt_core::TransformationMode;
 
 use cpp_core::CppBox;
 use cpp_core::Ref;
 
 use anyhow::{anyhow, Result};
-use base64::prelude::*;
 use crossbeam::channel::Receiver;
 use flate2::read::ZlibDecoder;
 use getset::Getters;
 use itertools::Itertools;
 use rayon::prelude::*;
-use sha256::try_digest;
+use time::OffsetDateTime;
 
 use std::collections::HashMap;
-use std::fs::{DirBuilder, File};
-use std::io::{BufReader, BufWriter, Cursor, Read, Write};
+use std::fs::File;
+use std::io::{BufWriter, Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
 
-use rpfm_lib::binary::{ReadBytes, WriteBytes};
+use rpfm_lib::binary::ReadBytes;
 use rpfm_lib::files::{Container, db::DB, EncodeableExtraData, FileType, loc::Loc, pack::Pack, RFile, RFileDecoded, table::DecodedData};
 use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::*};
-use rpfm_lib::integrations::log::*;
+use rpfm_lib::integrations::{git::GitResponse, log::*};
 use rpfm_lib::schema::Schema;
-use rpfm_lib::utils::files_from_subdir;
 
 use rpfm_ui_common::ASSETS_PATH;
 use rpfm_ui_common::clone;
@@ -93,7 +101,7 @@ use crate::data_ui::DataListUI;
 use crate::data_ui::pack_tree::PackTree;
 use crate::ffi::*;
 use crate::games::*;
-use crate::mod_manager::{*, game_config::{GameConfig, DEFAULT_CATEGORY}, integrations::*, load_order::{ImportedLoadOrderMode, LoadOrder}, mods::{Mod, ShareableMod}, profiles::Profile, saves::Save};
+use crate::mod_manager::{*, conflicts::DataConflictReport, game_config::{GameConfig, DEFAULT_CATEGORY}, history::{LaunchSnapshot, ModListHistory, ModListState}, integrations::*, load_order::{ImportedLoadOrderMode, LoadOrder, MissingDependencyStatus}, log_analysis, modpack::{ModpackManifest, MODPACK_EXTENSION}, mods::Mod, pack_watcher::{PackChanges, PackWatcher}, preflight::default_preview_path, profiles::Profile, saves::Save, shared_load_order::SharedLoadOrder, translations::{TranslationCoverageReport, TranslationOverride}};
 use crate::LIGHT_PALETTE;
 use crate::LIGHT_STYLE_SHEET;
 use crate::mod_list_ui::*;
@@ -125,12 +133,19 @@ const WORKSHOP_UPLOAD_VIEW_RELEASE: &str = "ui/workshop_upload_dialog.ui";
 const LOG_ANALYSIS_VIEW_DEBUG: &str = "ui_templates/log_analysis_dialog.ui";
 const LOG_ANALYSIS_VIEW_RELEASE: &str = "ui/log_analysis_dialog.ui";
 
+const DATA_CONFLICTS_VIEW_DEBUG: &str = "ui_templates/data_conflicts_dialog.ui";
+const DATA_CONFLICTS_VIEW_RELEASE: &str = "ui/data_conflicts_dialog.ui";
+
+const TRANSLATION_COVERAGE_VIEW_DEBUG: &str = "ui_templates/translation_coverage_dialog.ui";
+const TRANSLATION_COVERAGE_VIEW_RELEASE: &str = "ui/translation_coverage_dialog.ui";
+
+const TRANSLATION_EDITOR_VIEW_DEBUG: &str = "ui_templates/translation_editor_dialog.ui";
+const TRANSLATION_EDITOR_VIEW_RELEASE: &str = "ui/translation_editor_dialog.ui";
+
 const MERGE_ALL_PACKS_PACK_NAME: &str = "merge_me_sideways_honey";
 
-#[allow(dead_code)] const VANILLA_MOD_LIST_FILE_NAME: &str = "used_mods.txt";
-#[allow(dead_code)] const CUSTOM_MOD_LIST_FILE_NAME: &str = "mod_list.txt";
-#[allow(dead_code)] const USER_SCRIPT_FILE_NAME: &str = "user.script.txt";
-#[allow(dead_code)] const USER_SCRIPT_EMPIRE_FILE_NAME: &str = "user.empire_script.txt";
+/// Time between checks for changes reported by the pack watcher, in milliseconds.
+const PACK_WATCHER_CHECK_INTERVAL: i32 = 500;
 
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
@@ -208,15 +223,13 @@ pub struct AppUI {
 
     // Game selected. Unlike RPFM, here it's not a global.
     game_selected: Rc<RwLock<GameInfo>>,
-}
 
-#[derive(Debug, Default, Getters)]
-#[getset(get = "pub")]
-pub struct ScriptBreak {
-    posible_pack: String,
-    posible_pack_mod: String,
-    posible_pack_link: Option<String>,
-    full_log: String,
+    // Watcher over the mod folders of the game selected, and the timer to check what it found.
+    pack_watcher: Rc<RwLock<Option<PackWatcher>>>,
+    pack_watcher_timer: QBox<QTimer>,
+
+    // Undo/redo history of the mod list of the game selected.
+    mod_list_history: Rc<RwLock<ModListHistory>>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -363,6 +376,9 @@ impl AppUI {
         //-------------------------------------------------------------------------------//
         let pack_list_ui = PackListUI::new(&right_tabbar)?;
 
+        let pack_watcher_timer = QTimer::new_1a(&main_window);
+        pack_watcher_timer.set_interval(PACK_WATCHER_CHECK_INTERVAL);
+
         let app_ui = Rc::new(Self {
 
             //-------------------------------------------------------------------------------//
@@ -430,6 +446,11 @@ impl AppUI {
 
             // NOTE: This loads arena on purpose, so ANY game selected triggers a game change properly.
             game_selected: Rc::new(RwLock::new(SUPPORTED_GAMES.game("arena").unwrap().clone())),
+
+            pack_watcher: Rc::new(RwLock::new(None)),
+            pack_watcher_timer,
+
+            mod_list_history: Rc::new(RwLock::new(ModListHistory::default())),
         });
 
         let slots = AppUISlots::new(&app_ui);
@@ -552,6 +573,7 @@ impl AppUI {
         self.actions_ui().remove_trait_limit_checkbox().toggled().connect(slots.toggle_remove_trait_limit());
         self.actions_ui().merge_all_mods_checkbox().toggled().connect(slots.toggle_merge_all_mods());
         self.actions_ui().enable_translations_combobox().current_text_changed().connect(slots.toggle_enable_translations());
+        self.actions_ui().save_combobox().current_index_changed().connect(slots.read_selected_save());
         self.actions_ui().unit_multiplier_spinbox().value_changed().connect(slots.change_unit_multiplier());
         self.actions_ui().settings_button().released().connect(slots.open_settings());
         self.actions_ui().universal_rebalancer_combobox().current_text_changed().connect(slots.toggle_universal_rebalancer());
@@ -565,6 +587,10 @@ impl AppUI {
         self.actions_ui().open_runcher_error_folder().triggered().connect(slots.open_runcher_error_folder());
         self.actions_ui().copy_load_order_button().released().connect(slots.copy_load_order());
         self.actions_ui().paste_load_order_button().released().connect(slots.paste_load_order());
+        self.actions_ui().export_to_launcher().triggered().connect(slots.export_to_launcher());
+        self.actions_ui().export_modpack().triggered().connect(slots.export_modpack());
+        self.actions_ui().import_modpack().triggered().connect(slots.import_modpack());
+        self.actions_ui().import_workshop_collection().triggered().connect(slots.import_workshop_collection());
         self.actions_ui().reload_button().released().connect(slots.reload());
         self.actions_ui().download_subscribed_mods_button().released().connect(slots.download_subscribed_mods());
         self.actions_ui().profile_load_button().released().connect(slots.load_profile());
@@ -595,6 +621,9 @@ impl AppUI {
         self.mod_list_ui().model().item_changed().connect(slots.update_pack_list());
         self.mod_list_ui().upload_to_workshop().triggered().connect(slots.upload_to_workshop());
         self.mod_list_ui().download_from_workshop().triggered().connect(slots.download_from_workshop());
+        self.mod_list_ui().unsubscribe_from_workshop().triggered().connect(slots.unsubscribe_from_workshop());
+        self.mod_list_ui().resubscribe_to_workshop().triggered().connect(slots.resubscribe_to_workshop());
+        self.mod_list_ui().remove_orphaned_content().triggered().connect(slots.remove_orphaned_content());
         self.mod_list_ui().context_menu().about_to_show().connect(slots.mod_list_context_menu_open());
         self.mod_list_ui().enable_selected().triggered().connect(slots.enable_selected());
         self.mod_list_ui().disable_selected().triggered().connect(slots.disable_selected());
@@ -604,14 +633,22 @@ impl AppUI {
         self.mod_list_ui().category_sort().triggered().connect(slots.category_sort());
         draggable_tree_view_drop_signal(self.mod_list_ui().tree_view().static_upcast()).connect(slots.category_move());
 
+        self.mod_list_ui().edit_local_translation().triggered().connect(slots.edit_local_translation());
         self.mod_list_ui().copy_to_secondary().triggered().connect(slots.copy_to_secondary());
         self.mod_list_ui().move_to_secondary().triggered().connect(slots.move_to_secondary());
 
         self.pack_list_ui().automatic_order_button().toggled().connect(slots.pack_toggle_auto_sorting());
+        self.pack_list_ui().data_conflicts_button().released().connect(slots.pack_data_conflicts());
+        self.pack_list_ui().translation_coverage_button().released().connect(slots.pack_translation_coverage());
+        self.pack_list_ui().undo_button().released().connect(slots.mod_list_undo());
+        self.pack_list_ui().redo_button().released().connect(slots.mod_list_redo());
+        self.pack_list_ui().snapshots_menu().about_to_show().connect(slots.launch_snapshots_menu_open());
         draggable_tree_view_drop_signal(self.pack_list_ui().tree_view().static_upcast()).connect(slots.pack_move());
 
         self.data_list_ui().reload_button().released().connect(slots.data_view_reload());
         self.data_list_ui().tree_view().double_clicked().connect(slots.open_file_with_rpfm());
+
+        self.pack_watcher_timer().timeout().connect(slots.check_pack_changes());
     }
 
     /// Function to toggle the main window on and off, while keeping the stupid focus from breaking.
@@ -685,6 +722,13 @@ impl AppUI {
                 // Schemas are optional, so don't interrupt loading due to they not being present.
                 let schema_path = schemas_path().unwrap().join(game.schema_file_name());
                 *SCHEMA.write().unwrap() = Schema::load(&schema_path, None).ok();
+
+                // The history of one game makes no sense in another.
+                if self.game_selected().read().unwrap().key() != game.key() {
+                    self.mod_list_history().write().unwrap().clear();
+                    self.update_mod_list_history_buttons();
+                }
+
                 *self.game_selected().write().unwrap() = game.clone();
 
                 // Trigger an update of all game configs, just in case one needs update.
@@ -735,75 +779,40 @@ impl AppUI {
         let item = QStandardItem::from_q_string(&QString::from_std_str("No saves"));
         self.actions_ui().save_model().append_row_q_standard_item(item.into_ptr());
 
-        // If we have a save folder for the game, read its saves and load them to the save combo.
-        if let Some(ref config_path) = game.config_path(game_path) {
-            let mut game_saves = self.game_saves.write().unwrap();
-            game_saves.clear();
-
-            let save_path = config_path.join("save_games");
-            if let Ok(mut saves_paths) = files_from_subdir(&save_path, false) {
-
-                // Sort them by date, then reverse, so the most recent one is first.
-                saves_paths.sort_by_key(|x| x.metadata().unwrap().modified().unwrap());
-                saves_paths.reverse();
-
-                for save_path in &saves_paths {
-                    let mut save = RFile::new_from_file_path(save_path)?;
-                    save.guess_file_type()?;
-
-                    let mut save = Save::default();
-                    save.set_path(save_path.to_path_buf());
-                    save.set_name(save_path.file_name().unwrap().to_string_lossy().to_string());
-
-                    /*
-                    if let Some(RFileDecoded::ESF(file)) = save.decode(&None, false, true)? {
-                        let mut save = Save::default();
-                        save.set_path(save_path.to_path_buf());
-                        save.set_name(save_path.file_name().unwrap().to_string_lossy().to_string());
-                        let mut mods = vec![];
-
-                        let root_node = file.root_node();
-                        if let NodeType::Record(node) = root_node {
-                            if node.name() == "CAMPAIGN_SAVE_GAME" {
-                                for children in node.children() {
-                                    for child in children {
-                                        if let NodeType::Record(node) = child {
-                                            if node.name() == "SAVE_GAME_HEADER" {
-                                                for children in node.children() {
-                                                    for child in children {
-                                                        if let NodeType::Record(node) = child {
-                                                            if node.name() == "mod_history_block_name" {
-                                                                for children in node.children() {
-                                                                    if let NodeType::Ascii(pack_name) = &children[0] {
-                                                                        //if let NodeType::Ascii(pack_folder) = &children[1] {
-                                                                            mods.push(pack_name.to_owned());
-                                                                        //}
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
+        // If we have a save folder for the game, load its saves to the save combo. Their mods are only read when they're selected.
+        let mut game_saves = self.game_saves.write().unwrap();
+        *game_saves = Save::saves_for_game(game, game_path)?;
+
+        for save in game_saves.iter() {
+            let item = QStandardItem::from_q_string(&QString::from_std_str(save.name()));
+            if !save.mods().is_empty() {
+                item.set_tool_tip(&QString::from_std_str(format!("{}\n{}", tr("save_mods"), save.mods().join("\n"))));
+            }
 
-                        save.set_mods(mods);
+            self.actions_ui().save_model().append_row_q_standard_item(item.into_ptr());
+        }
 
+        Ok(())
+    }
 
-                    }*/
-                    let item = QStandardItem::from_q_string(&QString::from_std_str(save.name()));
-                    self.actions_ui().save_model().append_row_q_standard_item(item.into_ptr());
+    /// This function reads the mods of the save selected in the save combo, if they're not yet known, and shows them in its tooltip.
+    pub unsafe fn read_selected_save(&self) {
 
-                    game_saves.push(save);
-                }
-            }
+        // First option is no save load. Any index above that is a save.
+        let save_index = self.actions_ui().save_combobox().current_index();
+        if save_index < 1 {
+            return;
         }
 
-        Ok(())
+        let game = self.game_selected().read().unwrap().clone();
+        if let Some(save) = self.game_saves.write().unwrap().get_mut(save_index as usize - 1) {
+            save.read_mods_if_needed(&game);
+
+            let item = self.actions_ui().save_model().item_1a(save_index);
+            if !item.is_null() && !save.mods().is_empty() {
+                item.set_tool_tip(&QString::from_std_str(format!("{}\n{}", tr("save_mods"), save.mods().join("\n"))));
+            }
+        }
     }
 
     pub unsafe fn load_mods_to_ui(&self, game: &GameInfo, game_path: &Path, skip_network_update: bool) -> Result<Option<Receiver<Response>>> {
@@ -815,6 +824,65 @@ impl AppUI {
             self.mod_list_ui().load(game, mods)?;
             self.pack_list_ui().load(mods, game, game_path, &load_order)?;
 
+            // Restart the watcher, so it watches the folders of this game. The old one stops when dropped.
+            *self.pack_watcher().write().unwrap() = Some(PackWatcher::new(game, game_path));
+            self.pack_watcher_timer().start_0a();
+
+            Ok(network_receiver)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// This function updates the mod list with the packs the watcher found added, changed or removed from the mod folders.
+    ///
+    /// Only the rows of the affected mods are updated, unless new mods appeared, in which case the mod list is rebuilt.
+    pub unsafe fn apply_pack_changes(&self, changes: &PackChanges) -> Result<Option<Receiver<Response>>> {
+        if changes.is_empty() {
+            return Ok(None);
+        }
+
+        info!("Packs changed on disk: {} added, {} changed, {} removed.", changes.added().len(), changes.changed().len(), changes.removed().len());
+
+        let game = self.game_selected().read().unwrap().clone();
+        let game_path = setting_path(game.key());
+
+        let mut game_config = self.game_config().write().unwrap();
+        if let Some(ref mut game_config) = *game_config {
+            let installed_before = game_config.mods()
+                .values()
+                .filter(|modd| !modd.paths().is_empty())
+                .map(|modd| modd.id().to_owned())
+                .collect::<Vec<_>>();
+
+            // Only ask Steam for data if new packs appeared in the content folder, as those are the only ones it knows about.
+            let content_path = game.content_path(&game_path).map(|path| std::fs::canonicalize(&path).unwrap_or(path));
+            let skip_network_update = match content_path {
+                Ok(ref content_path) => !changes.added().iter().any(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()).starts_with(content_path)),
+                Err(_) => true,
+            };
+
+            let mut load_order = self.game_load_order().write().unwrap();
+            let network_receiver = game_config.update_mod_list(&game, &game_path, &mut load_order, skip_network_update)?;
+
+            let installed_after = game_config.mods()
+                .values()
+                .filter(|modd| !modd.paths().is_empty())
+                .map(|modd| modd.id().to_owned())
+                .collect::<Vec<_>>();
+
+            if installed_after.iter().any(|mod_id| !installed_before.contains(mod_id)) {
+                self.mod_list_ui().load(&game, game_config)?;
+            } else {
+                let removed = installed_before.into_iter()
+                    .filter(|mod_id| !installed_after.contains(mod_id))
+                    .collect::<Vec<_>>();
+
+                self.mod_list_ui().update(&game, game_config.mods(), &removed)?;
+            }
+
+            self.pack_list_ui().load(game_config, &game, &game_path, &load_order)?;
+
             Ok(network_receiver)
         } else {
             Ok(None)
@@ -892,6 +960,37 @@ impl AppUI {
         set_setting_bool("factoryReset", false);
     }
 
+    /// This function checks if the translations repo needs updating, and updates it if so.
+    ///
+    /// Errors are reported, but they don't stop the launch. We just use the translations we have.
+    pub unsafe fn update_translations(&self) {
+        let receiver = CENTRAL_COMMAND.send_network(Command::CheckTranslationsUpdates);
+        let response_thread = CENTRAL_COMMAND.recv_try(&receiver);
+        match response_thread {
+            Response::APIResponseGit(ref response) => {
+                match response {
+                    GitResponse::NewUpdate |
+                    GitResponse::NoLocalFiles |
+                    GitResponse::Diverged => {
+                        let receiver = CENTRAL_COMMAND.send_background(Command::UpdateTranslations);
+                        let response_thread = CENTRAL_COMMAND.recv_try(&receiver);
+
+                        // Show the error, but continue anyway.
+                        if let Response::Error(error) = response_thread {
+                            show_dialog(self.main_window(), tre("translation_download_error", &[&error.to_string()]), false);
+                        }
+                    }
+                    GitResponse::NoUpdate => {}
+                }
+            }
+
+            Response::Error(error) => {
+                show_dialog(self.main_window(), tre("translation_download_error", &[&error.to_string()]), false);
+            }
+            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response_thread:?}"),
+        }
+    }
+
     pub unsafe fn launch_game(&self) -> Result<()> {
         let mut folder_list = String::new();
         let mut pack_list = String::new();
@@ -900,12 +999,21 @@ impl AppUI {
         let data_path = game.data_path(&game_path)?;
 
         // Setup the launch options stuff.
-        prepare_launch_options(self, &game, &game_path, &data_path, &mut folder_list)?;
+        let launch_options = self.actions_ui().launch_options();
+        if launch_options.translations_language().is_some() {
+            self.update_translations();
+        }
+
+        let pack_paths = (0..self.pack_list_ui().model().row_count_0a())
+            .map(|index| PathBuf::from(self.pack_list_ui().model().item_2a(index, 2).text().to_std_string()))
+            .collect::<Vec<_>>();
+
+        prepare_launch_options(&launch_options, &game, &game_path, &data_path, &pack_paths, &mut folder_list)?;
 
         // If we have "merge all mods" checked, we need to load the entire load order into a single pack, and load that pack instead of the entire load order.
         //
         // TODO: Review this before re-enabling merged mods. This pretty sure breaks on older games.
-        if self.actions_ui().merge_all_mods_checkbox().is_enabled() && self.actions_ui().merge_all_mods_checkbox().is_checked() {
+        if *launch_options.merge_all_mods() {
             let temp_path_file_name = format!("{}_{}.pack", MERGE_ALL_PACKS_PACK_NAME, self.game_selected().read().unwrap().key());
             let temp_path = data_path.join(&temp_path_file_name);
             pack_list.push_str(&format!("mod \"{}\";", temp_path_file_name));
@@ -944,31 +1052,8 @@ impl AppUI {
 
         // If our folder list contains the secondary folder, we need to make sure we create the masks folder in it,
         // and mask in there all non-enabled movie files.
-        let secondary_mods_path = secondary_mods_path(game.key()).unwrap_or_else(|_| PathBuf::new());
-        if secondary_mods_path.is_dir() && folder_list.contains(&secondary_mods_path.to_string_lossy().to_string()) {
-            let masks_path = secondary_mods_path.join(SECONDARY_FOLDER_NAME);
-
-            // Remove all files in it so previous maskings do not interfere.
-            if masks_path.is_dir() {
-                std::fs::remove_dir_all(&masks_path)?;
-            }
-
-            DirBuilder::new().recursive(true).create(&masks_path)?;
-
-            let mut mask_pack = Pack::new_with_version(game.pfh_version_by_file_type(PFHFileType::Movie));
-            mask_pack.set_pfh_file_type(PFHFileType::Movie);
-
-            if let Some(ref game_config) = *self.game_config().read().unwrap() {
-                for path in std::fs::read_dir(secondary_mods_path)? {
-                    let file_name = path?.file_name().to_string_lossy().to_string();
-
-                    if let Some(modd) = game_config.mods().get(&file_name) {
-                        if modd.pack_type() == &PFHFileType::Movie && !modd.enabled(&data_path) {
-                            mask_pack.save(Some(&masks_path.join(file_name)), &game, &None)?;
-                        }
-                    }
-                }
-            }
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            mask_disabled_secondary_movie_packs(game_config, &game, &data_path, &folder_list)?;
         }
 
         // Check if we are loading a save. First option is no save load. Any index above that is a save.
@@ -982,90 +1067,219 @@ impl AppUI {
             }
         }
 
-        // NOTE: On Empire and Napoleon we need to use the user_script, not the custom file, as it doesn't seem to work.
-        // Older versions of shogun 2 also used the user_script, but the latest update enabled use of custom mod lists.
-        let file_path = if *game.raw_db_version() >= 1 {
-            game_path.join(CUSTOM_MOD_LIST_FILE_NAME)
-        } else {
+        LoadOrder::save_mod_list_file(&game, &game_path, &folder_list, &pack_list)?;
+
+        // Keep a snapshot of what we're launching, so we can go back to it if a later change breaks something.
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            let load_order = self.game_load_order().read().unwrap();
+            if let Err(error) = LaunchSnapshot::save_for_game(&game, ModListState::new(game_config, &load_order, &data_path)) {
+                error!("Error saving the launch snapshot: {}", error);
+            }
+        }
 
-            // Games may fail to launch if we don't have this path created, which is done the first time we start the game.
-            let config_path = game.config_path(&game_path).ok_or(anyhow!("Error getting the game's config path."))?;
-            let scripts_path = config_path.join("scripts");
-            DirBuilder::new().recursive(true).create(&scripts_path)?;
+        if game.executable_path(&game_path).is_none() {
+            return Err(anyhow!("Executable path not found. Is the game folder configured correctly in the settings?"));
+        }
 
-            // Empire has its own user script.
-            if game.key() == KEY_EMPIRE {
-                scripts_path.join(USER_SCRIPT_EMPIRE_FILE_NAME)
-            } else {
-                scripts_path.join(USER_SCRIPT_FILE_NAME)
+        self.toggle_main_window(false);
+
+        let event_loop = qt_core::QEventLoop::new_0a();
+        event_loop.process_events_0a();
+
+        let start_date = SystemTime::now();
+
+        // Launch is done through workshopper on Windows to get the Steam Api up, and through the launcher command from the settings on Linux.
+        let wait_for_finish = setting_bool("check_logs");
+        let result = launch_game_with_mod_list(&game, &game_path, &extra_args, wait_for_finish);
+
+        // Check the logs post-launch, if there's any log to check.
+        if setting_bool("check_logs") {
+            self.check_logs(&game, &game_path, &start_date)?;
+        }
+
+        self.toggle_main_window(true);
+
+        result
+    }
+
+    /// This function checks if the save selected was made with different mods than the ones in the load order,
+    /// and offers to restore the load order of the save before launching the game.
+    ///
+    /// Returns if the game should be launched.
+    pub unsafe fn check_save_load_order(&self) -> Result<bool> {
+
+        // First option is no save load. Any index above that is a save.
+        let save_index = self.actions_ui().save_combobox().current_index();
+        if save_index < 1 {
+            return Ok(true);
+        }
+
+        self.read_selected_save();
+        let save = match self.game_saves.read().unwrap().get(save_index as usize - 1) {
+            Some(save) => save.clone(),
+            None => return Ok(true),
+        };
+
+        let differences = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => save.load_order_differences(game_config, &self.game_load_order().read().unwrap()),
+            None => return Ok(true),
+        };
+
+        // If we cannot read the mods of the save, let the user decide if they want to risk it.
+        let (missing, extra) = match differences {
+            Ok(differences) => differences,
+            Err(error) => {
+                let dialog = QMessageBox::from_q_widget(self.main_window());
+                dialog.set_window_title(&qtr("save_load_order_mismatch_title"));
+                dialog.set_icon(q_message_box::Icon::Warning);
+                dialog.set_text(&QString::from_std_str(tre("save_mods_unreadable", &[&error.to_string()])));
+                dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::No);
+
+                return Ok(dialog.exec() == q_message_box::StandardButton::Yes.to_int());
             }
         };
 
-        let mut file = BufWriter::new(File::create(file_path)?);
+        if missing.is_empty() && extra.is_empty() {
+            return Ok(true);
+        }
 
-        // Napoleon, Empire and Shogun 2 require the user.script.txt or mod list file (for Shogun's latest update) to be in UTF-16 LE. What the actual fuck.
-        if *game.raw_db_version() < 2 {
-            file.write_string_u16(&folder_list)?;
-            file.write_string_u16(&pack_list)?;
-        } else {
-            file.write_all(folder_list.as_bytes())?;
-            file.write_all(pack_list.as_bytes())?;
+        let list = |mods: &[String]| mods.iter().map(|pack_name| format!("<li>{}</li>", pack_name)).collect::<Vec<_>>().join("\n");
+        let mut message = String::new();
+        if !missing.is_empty() {
+            message.push_str(&tre("save_mods_missing", &[&list(&missing)]));
         }
 
-        file.flush()?;
+        if !extra.is_empty() {
+            message.push_str(&tre("save_mods_extra", &[&list(&extra)]));
+        }
 
-        // Launch is done through workshopper to getup the Steam Api.
-        //
-        // Here we just build the commands and pass them to workshopper.
-        match game.executable_path(&game_path) {
-            Some(exec_game) => {
-                if cfg!(target_os = "windows") {
+        message.push_str(&tr("save_restore_load_order"));
 
-                    // For post-shogun 2 games, we use the same command to bypass the launcher.
-                    let command = if *game.raw_db_version() >= 1 {
+        let dialog = QMessageBox::from_q_widget(self.main_window());
+        dialog.set_window_title(&qtr("save_load_order_mismatch_title"));
+        dialog.set_icon(q_message_box::Icon::Warning);
+        dialog.set_text(&QString::from_std_str(message));
+        dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::No | q_message_box::StandardButton::Cancel);
 
-                        let mut command = format!("cmd /C start /W /d \"{}\" \"{}\" {};", game_path.to_string_lossy().replace('\\', "/"), exec_game.file_name().unwrap().to_string_lossy(), CUSTOM_MOD_LIST_FILE_NAME);
+        let response = dialog.exec();
+        if response == q_message_box::StandardButton::Cancel.to_int() {
+            Ok(false)
+        } else {
+            if response == q_message_box::StandardButton::Yes.to_int() {
+                self.load_order_from_shared_load_order(&SharedLoadOrder::from_mods(save.shareable_mod_list()))?;
+            }
 
-                        for arg in &extra_args {
-                            command.push(' ');
-                            command.push_str(arg);
-                        }
+            Ok(true)
+        }
+    }
 
-                        command
-                    }
+    /// This function checks if any mod in the load order is missing dependencies, and offers to fix them before launching the game.
+    ///
+    /// Installed dependencies are enabled, and the ones from the Workshop are downloaded. Returns if the game should be launched.
+    pub unsafe fn check_missing_dependencies(&self) -> Result<bool> {
+        let game_info = self.game_selected().read().unwrap().clone();
+        let game_path = setting_path(game_info.key());
+        let game_data_path = game_info.data_path(&game_path)?;
+
+        let missing = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => self.game_load_order().read().unwrap().missing_dependencies(game_config, &game_data_path),
+            None => return Ok(true),
+        };
 
-                    // Empire and Napoleon do not have a launcher. We can make our lives easier calling steam instead of launching the game manually.
-                    else {
-                        format!("cmd /C start /W /d \"{}\" \"{}\"", game_path.to_string_lossy().replace('\\', "/"), exec_game.file_name().unwrap().to_string_lossy())
-                    };
+        if missing.is_empty() {
+            return Ok(true);
+        }
 
-                    self.toggle_main_window(false);
+        // List the dependencies grouped by the mod that needs them.
+        let list = missing.iter()
+            .chunk_by(|dependency| dependency.mod_id())
+            .into_iter()
+            .map(|(mod_id, dependencies)| format!("<li><b>{}</b><ul>{}</ul></li>", mod_id, dependencies
+                .map(|dependency| match dependency.status() {
+                    MissingDependencyStatus::Disabled(_) => format!("<li>{}: {}</li>", dependency.pack_name(), tr("missing_dependency_disabled")),
+                    MissingDependencyStatus::NotDownloaded(steam_id) => format!("<li>{}: <a href=\"https://steamcommunity.com/sharedfiles/filedetails/?id={}\">{}</a></li>", dependency.pack_name(), steam_id, tr("missing_dependency_not_downloaded")),
+                    MissingDependencyStatus::NotFound => format!("<li>{}: {}</li>", dependency.pack_name(), tr("missing_dependency_not_found")),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+            ))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let steam_ids = missing.iter()
+            .filter_map(|dependency| match dependency.status() {
+                MissingDependencyStatus::NotDownloaded(steam_id) => Some(steam_id.to_owned()),
+                _ => None,
+            })
+            .unique()
+            .collect::<Vec<_>>();
 
-                    let event_loop = qt_core::QEventLoop::new_0a();
-                    event_loop.process_events_0a();
+        let can_be_fixed = !steam_ids.is_empty() || missing.iter().any(|dependency| matches!(dependency.status(), MissingDependencyStatus::Disabled(_)));
 
-                    let start_date = SystemTime::now();
-                    let command = BASE64_STANDARD.encode(command);
+        let dialog = QMessageBox::from_q_widget(self.main_window());
+        dialog.set_window_title(&qtr("missing_dependencies_title"));
+        dialog.set_icon(q_message_box::Icon::Warning);
 
-                    let wait_for_finish = setting_bool("check_logs");
-                    let result = crate::mod_manager::integrations::launch_game(&game, &command, wait_for_finish);
+        if can_be_fixed {
+            dialog.set_text(&qtre("missing_dependencies_fixable", &[&list]));
+            dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::No | q_message_box::StandardButton::Cancel);
+        } else {
+            dialog.set_text(&qtre("missing_dependencies_not_fixable", &[&list]));
+            dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::Cancel);
+        }
 
-                    // Check the logs post-launch, if there's any log to check.
-                    if setting_bool("check_logs") {
-                        self.check_logs(&game, &game_path, &start_date)?;
-                    }
+        let response = dialog.exec();
+        if response == q_message_box::StandardButton::Cancel.to_int() {
+            return Ok(false);
+        }
 
-                    self.toggle_main_window(true);
+        // Yes on unfixable dependencies means launch anyway. No means launch without fixing them.
+        if !can_be_fixed || response == q_message_box::StandardButton::No.to_int() {
+            return Ok(true);
+        }
 
-                    result
-                } else if cfg!(target_os = "linux") {
-                    Err(anyhow!("Unsupported OS."))
-                } else {
-                    Err(anyhow!("Unsupported OS."))
+        // First, enable the installed ones, both in the backend and in the UI.
+        if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            let mut load_order = self.game_load_order().write().unwrap();
+            let enabled = load_order.enable_missing_dependencies(game_config, &game_data_path);
+
+            if !enabled.is_empty() {
+                self.mod_list_ui().model().block_signals(true);
+
+                for category in 0..self.mod_list_ui().model().row_count_0a() {
+                    let cat_item = self.mod_list_ui().model().item_1a(category);
+                    for mod_row in 0..cat_item.row_count() {
+                        let mod_item = cat_item.child_1a(mod_row);
+                        if !mod_item.is_null() && enabled.contains(&mod_item.data_1a(VALUE_MOD_ID).to_string().to_std_string()) {
+                            mod_item.set_check_state(CheckState::Checked);
+                        }
+                    }
                 }
+
+                self.mod_list_ui().model().block_signals(false);
+
+                load_order.save(&game_info)?;
+                self.pack_list_ui().load(game_config, &game_info, &game_path, &load_order)?;
+                self.data_list_ui().set_enabled(false);
             }
-            None => Err(anyhow!("Executable path not found. Is the game folder configured correctly in the settings?"))
+
+            // Enable the ones we're going to download too, so they're in the load order once the mod list is reloaded.
+            game_config.mods_mut()
+                .values_mut()
+                .filter(|modd| modd.paths().is_empty() && modd.steam_id().as_ref().map(|steam_id| steam_ids.contains(steam_id)).unwrap_or(false))
+                .for_each(|modd| modd.set_enabled(true));
+
+            game_config.save(&game_info)?;
         }
+
+        // Then, download the ones from the Workshop. This reloads the mod list, so let the user check it before launching.
+        if !steam_ids.is_empty() {
+            self.download_subscribed_mods(&Some(steam_ids))?;
+            show_dialog(self.main_window(), tr("mods_downloaded"), true);
+            return Ok(false);
+        }
+
+        Ok(true)
     }
 
     pub unsafe fn load_profile(&self, profile_name: Option<String>, is_autostart: bool) -> Result<()> {
@@ -1116,18 +1330,21 @@ impl AppUI {
 
                 // Then do the same for the backend. Keep in mind that if it's an autostart we have to avoid saving these changes to disk.
                 if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
-                    game_config.mods_mut().values_mut().for_each(|modd| { modd.set_enabled(false); });
-
-                    for mod_id in profile.load_order().mods() {
-                        if let Some(ref mut modd) = game_config.mods_mut().get_mut(mod_id) {
-                            modd.set_enabled(true);
-                        }
+                    let mut load_order = self.game_load_order().write().unwrap();
+                    if !is_autostart {
+                        self.record_mod_list_change(game_config, &load_order);
                     }
 
-                    // Replace the current load order with the one from the profile, and update it.
-                    *self.game_load_order().write().unwrap() = profile.load_order().clone();
-                    let mut load_order = self.game_load_order().write().unwrap();
-                    load_order.update(game_config, &game_data_path);
+                    profile.apply(game_config, &mut load_order, &game_data_path);
+
+                    // Restore the launch options and the save of the profile. The launch options get persisted through the UI slots.
+                    self.actions_ui().set_launch_options(profile.launch_options());
+
+                    let save_index = match profile.save() {
+                        Some(save) => self.actions_ui().save_combobox().find_text_1a(&QString::from_std_str(save)),
+                        None => 0,
+                    };
+                    self.actions_ui().save_combobox().set_current_index(save_index.max(0));
 
                     // Reload the pack list.
 
@@ -1158,6 +1375,17 @@ impl AppUI {
         profile.set_id(profile_name.to_owned());
         profile.set_game(self.game_selected().read().unwrap().key().to_string());
         profile.set_load_order(self.game_load_order().read().unwrap().clone());
+        profile.set_launch_options(self.actions_ui().launch_options());
+
+        // First option is no save load. Any index above that is a save.
+        if self.actions_ui().save_combobox().current_index() > 0 {
+            profile.set_save(Some(self.actions_ui().save_combobox().current_text().to_std_string()));
+        }
+
+        // Keep the link to the Workshop collection of the profile, so publishing it again updates the same collection.
+        if let Some(old_profile) = self.game_profiles().read().unwrap().get(&profile_name) {
+            profile.set_workshop_collection(old_profile.workshop_collection().clone());
+        }
 
         self.game_profiles().write().unwrap().insert(profile_name.to_owned(), profile.clone());
 
@@ -1279,11 +1507,15 @@ impl AppUI {
         let string_text_edit: QPtr<QTextEdit> = find_widget(&main_widget.static_upcast(), "string_text_edit")?;
         let modlist_mode_radio_button: QPtr<QRadioButton> = find_widget(&main_widget.static_upcast(), "modlist_mode_radio_button")?;
         let runcher_mode_radio_button: QPtr<QRadioButton> = find_widget(&main_widget.static_upcast(), "runcher_mode_radio_button")?;
+        let ca_launcher_mode_radio_button: QPtr<QRadioButton> = find_widget(&main_widget.static_upcast(), "ca_launcher_mode_radio_button")?;
+        let json_mode_radio_button: QPtr<QRadioButton> = find_widget(&main_widget.static_upcast(), "json_mode_radio_button")?;
         let button_box: QPtr<QDialogButtonBox> = find_widget(&main_widget.static_upcast(), "button_box")?;
         button_box.button(StandardButton::Ok).released().connect(dialog.slot_accept());
 
         modlist_mode_radio_button.set_text(&qtr("import_string_modlist_mode"));
         runcher_mode_radio_button.set_text(&qtr("import_string_runcher_mode"));
+        ca_launcher_mode_radio_button.set_text(&qtr("import_string_ca_launcher_mode"));
+        json_mode_radio_button.set_text(&qtr("import_string_json_mode"));
         runcher_mode_radio_button.set_checked(true);
 
         let mode_group = QButtonGroup::new_1a(&dialog);
@@ -1291,6 +1523,8 @@ impl AppUI {
         // Configure the `Game Selected` Menu.
         mode_group.add_button_1a(&modlist_mode_radio_button);
         mode_group.add_button_1a(&runcher_mode_radio_button);
+        mode_group.add_button_1a(&ca_launcher_mode_radio_button);
+        mode_group.add_button_1a(&json_mode_radio_button);
 
         if let Some(ref string) = string {
             dialog.set_window_title(&qtr("load_order_string_title_copy"));
@@ -1299,6 +1533,8 @@ impl AppUI {
 
             modlist_mode_radio_button.set_visible(false);
             runcher_mode_radio_button.set_visible(false);
+            ca_launcher_mode_radio_button.set_visible(false);
+            json_mode_radio_button.set_visible(false);
         } else {
             dialog.set_window_title(&qtr("load_order_string_title_paste"));
             info_label.set_text(&qtr("load_order_string_info_paste"));
@@ -1310,10 +1546,15 @@ impl AppUI {
         }
 
         if dialog.exec() == 1 && string.is_none() {
+            let string = string_text_edit.to_plain_text().to_std_string();
             let mode = if runcher_mode_radio_button.is_checked() {
-                ImportedLoadOrderMode::Runcher(string_text_edit.to_plain_text().to_std_string())
+                ImportedLoadOrderMode::Runcher(string)
+            } else if ca_launcher_mode_radio_button.is_checked() {
+                ImportedLoadOrderMode::CALauncher(string)
+            } else if json_mode_radio_button.is_checked() {
+                ImportedLoadOrderMode::Json(string)
             } else {
-                ImportedLoadOrderMode::Modlist(string_text_edit.to_plain_text().to_std_string())
+                ImportedLoadOrderMode::Modlist(string)
             };
 
             Ok(Some(mode))
@@ -1322,46 +1563,19 @@ impl AppUI {
         }
     }
 
-    pub unsafe fn load_order_from_shareable_mod_list(&self, shareable_mod_list: &[ShareableMod]) -> Result<()> {
+    /// This function replaces the current load order with the provided one, reporting the mods we couldn't import.
+    ///
+    /// If the load order includes launch options, the user is asked before applying them.
+    pub unsafe fn load_order_from_shared_load_order(&self, shared: &SharedLoadOrder) -> Result<()> {
         if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
-
-            // Before we begin, we need to set all mods to disable. Otherwise, new load orders would get mods mixed up.
-            game_config.mods_mut().iter_mut().for_each(|(_, modd)| { modd.set_enabled(false); });
-
-            let mut missing = vec![];
-            let mut wrong_hash = vec![];
-            let mut ids = vec![];
-
-            for modd in shareable_mod_list {
-                match game_config.mods_mut().get_mut(modd.id()) {
-                    Some(modd_local) => {
-                        if let Some(path) = modd_local.paths().first() {
-                            if !modd.hash().is_empty() {
-                                let current_hash = try_digest(path.as_path())?;
-                                if &current_hash != modd.hash() {
-                                    wrong_hash.push(modd.clone());
-                                }
-                            }
-
-                            modd_local.set_enabled(true);
-                            ids.push(modd_local.id().to_owned());
-                        }
-                    },
-                    None => missing.push(modd.clone()),
-                }
-            }
-
-            // Once we're done updating the game config, we need to update the load order.
-            //
-            // We need manual order to respect the provided load order, as it may not be automatic.
             let game = self.game_selected().read().unwrap();
             let game_path = setting_path(game.key());
             let game_data_path = game.data_path(&game_path)?;
 
             let mut load_order = self.game_load_order().write().unwrap();
-            load_order.set_mods(ids);
-            load_order.set_automatic(false);
-            load_order.update(game_config, &game_data_path);
+            self.record_mod_list_change(game_config, &load_order);
+
+            let (missing, wrong_hash) = shared.apply(game_config, &mut load_order, &game_data_path)?;
             load_order.save(&game)?;
 
             self.mod_list_ui().load(&game, game_config)?;
@@ -1396,9 +1610,173 @@ impl AppUI {
             }
         }
 
+        // Launch options are applied through the UI, so they get persisted by its slots.
+        if let Some(launch_options) = shared.launch_options() {
+            let mut launch_options = launch_options.clone();
+            launch_options.disable_unsupported(&self.game_selected().read().unwrap());
+
+            if launch_options != self.actions_ui().launch_options() {
+                let dialog = QMessageBox::from_q_widget(self.main_window());
+                dialog.set_window_title(&qtr("import_launch_options_title"));
+                dialog.set_icon(q_message_box::Icon::Question);
+                dialog.set_text(&qtr("import_launch_options_question"));
+                dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::No);
+
+                if dialog.exec() == q_message_box::StandardButton::Yes.to_int() {
+                    self.actions_ui().set_launch_options(&launch_options);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// This function writes the enabled mods to the mod data file of the official launcher, so they're used when launching the game without Runcher.
+    pub unsafe fn export_load_order_to_launcher(&self) -> Result<()> {
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            let game = self.game_selected().read().unwrap();
+            let game_path = setting_path(game.key());
+            let game_data_path = game.data_path(&game_path)?;
+
+            let load_order = self.game_load_order().read().unwrap();
+            let path = load_order.export_to_ca_launcher(game_config, &game, &game_data_path)?;
+            show_dialog(self.main_window(), tre("export_to_launcher_success", &[&path.to_string_lossy()]), true);
+        }
+
+        Ok(())
+    }
+
+    /// This function exports the current load order as a modpack, to a path chosen by the user.
+    pub unsafe fn export_modpack(&self) -> Result<()> {
+        let path = QFileDialog::get_save_file_name_4a(
+            self.main_window(),
+            &qtr("export_modpack"),
+            &QString::new(),
+            &QString::from_std_str(format!("{} (*.{MODPACK_EXTENSION})", tr("modpack_file_type"))),
+        ).to_std_string();
+
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        let mut path = PathBuf::from(path);
+        if path.extension().is_none() {
+            path.set_extension(MODPACK_EXTENSION);
+        }
+
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            let game = self.game_selected().read().unwrap();
+            let game_path = setting_path(game.key());
+            let game_data_path = game.data_path(&game_path)?;
+            let load_order = self.game_load_order().read().unwrap().clone();
+            let launch_options = self.actions_ui().launch_options();
+
+            self.toggle_main_window(false);
+            let receiver = CENTRAL_COMMAND.send_background(Command::ExportModpack(path, Box::new(game.clone()), game_config.clone(), game_data_path, load_order, Some(launch_options)));
+            let response = CENTRAL_COMMAND.recv_try(&receiver);
+            self.toggle_main_window(true);
+
+            match response {
+                Response::ModpackManifest(manifest) => show_dialog(self.main_window(), tre("export_modpack_success", &[
+                    &manifest.bundled_packs().len().to_string(),
+                    &manifest.workshop_mods().len().to_string()
+                ]), true),
+                Response::Error(error) => return Err(error),
+                _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+            }
+        }
+
         Ok(())
     }
 
+    /// This function imports a modpack chosen by the user, extracting its packs and applying its load order.
+    pub unsafe fn import_modpack(&self) -> Result<()> {
+        let path = QFileDialog::get_open_file_name_4a(
+            self.main_window(),
+            &qtr("import_modpack"),
+            &QString::new(),
+            &QString::from_std_str(format!("{} (*.{MODPACK_EXTENSION})", tr("modpack_file_type"))),
+        ).to_std_string();
+
+        if path.is_empty() {
+            return Ok(());
+        }
+
+        let game = self.game_selected().read().unwrap().clone();
+        let game_path = setting_path(game.key());
+        self.toggle_main_window(false);
+        let receiver = CENTRAL_COMMAND.send_background(Command::ImportModpack(PathBuf::from(path), Box::new(game), game_path));
+        let response = CENTRAL_COMMAND.recv_try(&receiver);
+        self.toggle_main_window(true);
+
+        let manifest = match response {
+            Response::ModpackManifest(manifest) => manifest,
+            Response::Error(error) => return Err(error),
+            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+        };
+
+        // Reload the mod list so the extracted packs are in it before applying the load order. They're not from the Workshop, so no need to ask for their data.
+        self.change_game_selected(true, true)?;
+        self.load_order_from_shared_load_order(manifest.load_order())
+    }
+
+    /// This function replaces the current load order with the one of a Workshop collection, downloading the mods we don't have yet.
+    ///
+    /// Returns the receiver of the online data of the reloaded mod list, if any.
+    pub unsafe fn import_workshop_collection(&self) -> Result<Option<Receiver<Response>>> {
+        let input = QInputDialog::get_text_3a(
+            self.main_window(),
+            &qtr("import_workshop_collection"),
+            &qtr("import_workshop_collection_label"),
+        ).to_std_string();
+
+        let collection_id = match workshop_item_id(&input) {
+            Some(collection_id) => collection_id,
+            None if input.trim().is_empty() => return Ok(None),
+            None => return Err(anyhow!("{} is not a valid Workshop collection id or link.", input.trim())),
+        };
+
+        let game = self.game_selected().read().unwrap().clone();
+        self.toggle_main_window(false);
+        let receiver = CENTRAL_COMMAND.send_network(Command::RequestCollectionMods(Box::new(game.clone()), collection_id));
+        let response = CENTRAL_COMMAND.recv_try(&receiver);
+
+        let items = match response {
+            Response::VecMod(items) => items,
+            Response::Error(error) => {
+                self.toggle_main_window(true);
+                return Err(error)
+            },
+            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+        };
+
+        // Download the items we don't have. Workshopper subscribes to them if we're not subscribed yet.
+        let missing = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => items.iter()
+                .filter_map(|item| item.steam_id().clone())
+                .filter(|steam_id| !game_config.mods()
+                    .values()
+                    .any(|modd| modd.steam_id().as_ref() == Some(steam_id) && !modd.paths().is_empty()))
+                .collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        let result = if !missing.is_empty() {
+            crate::mod_manager::integrations::download_subscribed_mods(&game, &Some(missing), true)
+        } else {
+            Ok(())
+        };
+
+        self.toggle_main_window(true);
+        result?;
+
+        // Reload the mod list so the downloaded mods are in it before applying the load order.
+        let network_receiver = self.change_game_selected(true, false)?;
+        self.load_order_from_shared_load_order(&SharedLoadOrder::from_workshop_collection(&items))?;
+
+        Ok(network_receiver)
+    }
+
     pub unsafe fn batch_toggle_selected_mods(&self, toggle: bool) -> Result<()> {
 
         // Lock the signals for the model, until the last item, so we avoid repeating full updates of the load order.
@@ -1422,6 +1800,8 @@ impl AppUI {
         self.mod_list_ui().model().block_signals(false);
 
         if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
+
             for category in 0..self.mod_list_ui().model().row_count_0a() {
                 let cat_item = self.mod_list_ui().model().item_2a(category, 0);
                 for mod_row in 0..cat_item.row_count() {
@@ -1468,6 +1848,7 @@ impl AppUI {
             }
 
             if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+                self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
                 game_config.create_category(&name);
 
                 let game = self.game_selected().read().unwrap();
@@ -1485,6 +1866,10 @@ impl AppUI {
             return Err(anyhow!("Dude, did you just tried to delete the {} category?!! You monster!!!", DEFAULT_CATEGORY));
         }
 
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
+        }
+
         for cat_to_delete in &selection {
 
             // Update the backend.
@@ -1547,6 +1932,8 @@ impl AppUI {
             cat_item.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(&new_cat_name)), 2);
 
             if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+                self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
+
                 if let Some(cat) = game_config.categories_mut().remove(&old_cat_name) {
                     game_config.categories_mut().insert(new_cat_name.to_owned(), cat);
 
@@ -1573,6 +1960,7 @@ impl AppUI {
 
         // We need to sort the backend first, then remove all rows from the view, sort them like in the backend, and re-add them.
         if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
             let gc_copy = game_config.clone();
 
             if let Some(ref mut mods) = game_config.categories_mut().get_mut(&cat_name) {
@@ -1656,6 +2044,7 @@ impl AppUI {
         }
 
         if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            self.record_mod_list_change(game_config, &self.game_load_order().read().unwrap());
 
             // Categories move.
             //
@@ -1794,6 +2183,10 @@ impl AppUI {
             return Ok(());
         }
 
+        if let Some(ref game_config) = *self.game_config().read().unwrap() {
+            self.record_mod_list_change(game_config, &load_order);
+        }
+
         // This one is easier than with categories: we just calculate the offset, take the items at selected positions, then re-add them in their new position.
         let packs_to_move = selection.iter().rev().map(|x| x.data_1a(VALUE_MOD_ID).to_string().to_std_string()).collect::<Vec<_>>();
         let offset = load_order.mods().iter()
@@ -1828,6 +2221,114 @@ impl AppUI {
         Ok(())
     }
 
+    /// This function stores the current state of the mod list in the undo history. Call it right before changing the mod list.
+    pub unsafe fn record_mod_list_change(&self, game_config: &GameConfig, load_order: &LoadOrder) {
+        let game = self.game_selected().read().unwrap();
+        let game_path = setting_path(game.key());
+        if let Ok(game_data_path) = game.data_path(&game_path) {
+            self.mod_list_history().write().unwrap().push(ModListState::new(game_config, load_order, &game_data_path));
+            self.update_mod_list_history_buttons();
+        }
+    }
+
+    pub unsafe fn update_mod_list_history_buttons(&self) {
+        let history = self.mod_list_history().read().unwrap();
+        self.pack_list_ui().undo_button().set_enabled(history.can_undo());
+        self.pack_list_ui().redo_button().set_enabled(history.can_redo());
+    }
+
+    /// This function undoes the last change to the mod list, or redoes the last undone change.
+    pub unsafe fn undo_mod_list_change(&self, redo: bool) -> Result<()> {
+        if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            let game = self.game_selected().read().unwrap();
+            let game_path = setting_path(game.key());
+            let game_data_path = game.data_path(&game_path)?;
+            let mut load_order = self.game_load_order().write().unwrap();
+
+            let current = ModListState::new(game_config, &load_order, &game_data_path);
+            let state = {
+                let mut history = self.mod_list_history().write().unwrap();
+                if redo {
+                    history.redo(current)
+                } else {
+                    history.undo(current)
+                }
+            };
+
+            if let Some(state) = state {
+                self.apply_mod_list_state(game_config, &mut load_order, &game, &game_path, &state)?;
+            }
+        }
+
+        self.update_mod_list_history_buttons();
+        Ok(())
+    }
+
+    /// This function restores the mod list to the state it had when the game was launched. The restore can be undone.
+    pub unsafe fn restore_launch_snapshot(&self, snapshot: &LaunchSnapshot) -> Result<()> {
+        if let Some(ref mut game_config) = *self.game_config().write().unwrap() {
+            let game = self.game_selected().read().unwrap();
+            let game_path = setting_path(game.key());
+            let mut load_order = self.game_load_order().write().unwrap();
+
+            self.record_mod_list_change(game_config, &load_order);
+            self.apply_mod_list_state(game_config, &mut load_order, &game, &game_path, snapshot.state())?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn apply_mod_list_state(&self, game_config: &mut GameConfig, load_order: &mut LoadOrder, game: &GameInfo, game_path: &Path, state: &ModListState) -> Result<()> {
+        let game_data_path = game.data_path(game_path)?;
+        state.apply(game_config, load_order, &game_data_path);
+
+        load_order.save(game)?;
+        game_config.save(game)?;
+
+        self.mod_list_ui().load(game, game_config)?;
+        self.pack_list_ui().load(game_config, game, game_path, load_order)?;
+        self.data_list_ui().set_enabled(false);
+
+        Ok(())
+    }
+
+    pub unsafe fn generate_launch_snapshots_submenu(app_ui: &Rc<AppUI>) {
+        let menu = app_ui.pack_list_ui().snapshots_menu();
+        menu.clear();
+
+        let game = app_ui.game_selected().read().unwrap().clone();
+        let snapshots = match LaunchSnapshot::snapshots_for_game(&game) {
+            Ok(snapshots) => snapshots,
+            Err(error) => return show_dialog(app_ui.main_window(), error, false),
+        };
+
+        if snapshots.is_empty() {
+            let action = menu.add_action_q_string(&qtr("launch_snapshots_empty"));
+            action.set_enabled(false);
+            return;
+        }
+
+        let date_format_str = setting_string("date_format");
+        let date_format = time::format_description::parse(&date_format_str).ok();
+
+        for snapshot in snapshots {
+            let date = OffsetDateTime::from_unix_timestamp(*snapshot.date() as i64).ok()
+                .and_then(|date| date.format(date_format.as_ref()?).ok())
+                .unwrap_or_else(|| snapshot.date().to_string());
+
+            let action = menu.add_action_q_string(&qtre("launch_snapshot", &[&date, &snapshot.state().enabled().len().to_string()]));
+            let slot = SlotNoArgs::new(menu, clone!(
+                app_ui => move || {
+                    if let Err(error) = app_ui.restore_launch_snapshot(&snapshot) {
+                        show_dialog(app_ui.main_window(), error, false);
+                    }
+                }
+            ));
+
+            action.triggered().connect(&slot);
+        }
+    }
+
     pub unsafe fn generate_open_in_tools_submenu(app_ui: &Rc<AppUI>) {
         let menu = app_ui.mod_list_ui().open_in_tool_menu();
         menu.clear();
@@ -2254,6 +2755,26 @@ impl AppUI {
                         PreUploadInfo::default()
                     };
 
+                    // Check the pack for problems the user should fix before uploading it, and what changed since the last upload.
+                    // This reads the vanilla files and the whole pack, so it may take a while.
+                    let pack_path = modd.paths().first().ok_or_else(|| anyhow!("Mod Path not found."))?;
+                    let preview_path = default_preview_path(pack_path);
+                    self.toggle_main_window(false);
+                    let receiver_preflight = CENTRAL_COMMAND.send_background(Command::GeneratePreflightReport(Box::new(game.clone()), setting_path(game.key()), pack_path.to_path_buf(), preview_path.to_path_buf()));
+                    let receiver_changelog = CENTRAL_COMMAND.send_background(Command::ChangelogSinceLastUpload(Box::new(game.clone()), pack_path.to_path_buf()));
+                    let preflight = match CENTRAL_COMMAND.recv_try(&receiver_preflight) {
+                        Response::PreflightReport(preflight) => Ok(preflight),
+                        Response::Error(error) => Err(error),
+                        response => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+                    };
+                    let changelog = match CENTRAL_COMMAND.recv_try(&receiver_changelog) {
+                        Response::OptionString(changelog) => Ok(changelog),
+                        Response::Error(error) => Err(error),
+                        response => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+                    };
+                    self.toggle_main_window(true);
+                    let preflight = preflight?;
+
                     // If no errors were found, load the UI Template.
                     let template_path = if cfg!(debug_assertions) { WORKSHOP_UPLOAD_VIEW_DEBUG } else { WORKSHOP_UPLOAD_VIEW_RELEASE };
                     let main_widget = load_template(self.main_window(), template_path)?;
@@ -2262,13 +2783,17 @@ impl AppUI {
                     let title_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "title_label")?;
                     let description_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "description_label")?;
                     let changelog_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "changelog_label")?;
+                    let preview_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "preview_label")?;
                     let tag_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "tag_label")?;
                     let visibility_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "visibility_label")?;
+                    let preflight_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "preflight_label")?;
 
                     let title_line_edit: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "title_line_edit")?;
                     let description_text_edit: QPtr<QTextEdit> = find_widget(&main_widget.static_upcast(), "description_text_edit")?;
                     let changelog_text_edit: QPtr<QTextEdit> = find_widget(&main_widget.static_upcast(), "changelog_text_edit")?;
-                    let tag_combo_box: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "tag_combo_box")?;
+                    let preview_line_edit: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "preview_line_edit")?;
+                    let preview_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "preview_button")?;
+                    let tag_list_widget: QPtr<QListWidget> = find_widget(&main_widget.static_upcast(), "tag_list_widget")?;
                     let visibility_combo_box: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "visibility_combo_box")?;
 
                     let button_box: QPtr<QDialogButtonBox> = find_widget(&main_widget.static_upcast(), "button_box")?;
@@ -2278,12 +2803,94 @@ impl AppUI {
                     title_label.set_text(&qtr("upload_workshop_title"));
                     description_label.set_text(&qtr("upload_workshop_description"));
                     changelog_label.set_text(&qtr("upload_workshop_changelog"));
+                    preview_label.set_text(&qtr("upload_workshop_preview"));
                     tag_label.set_text(&qtr("upload_workshop_tag"));
                     visibility_label.set_text(&qtr("upload_workshop_visibility"));
+                    preflight_label.set_text(&qtr("upload_workshop_preflight"));
+
+                    // Slot to check the preview image each time it changes. The rest of the report doesn't depend on it, so we don't check the pack again.
+                    let main_ptr = main_widget.static_upcast();
+                    let preview_changed_slot = SlotNoArgs::new(&main_widget, move || {
+                        let preview_line_edit: QPtr<QLineEdit> = find_widget(&main_ptr, "preview_line_edit").unwrap();
+                        let preview_image_label: QPtr<QLabel> = find_widget(&main_ptr, "preview_image_label").unwrap();
+                        let preview_info_label: QPtr<QLabel> = find_widget(&main_ptr, "preview_info_label").unwrap();
+                        let preflight_text_edit: QPtr<QTextEdit> = find_widget(&main_ptr, "preflight_text_edit").unwrap();
+                        let button_box: QPtr<QDialogButtonBox> = find_widget(&main_ptr, "button_box").unwrap();
+
+                        let preview_path = PathBuf::from(preview_line_edit.text().to_std_string());
+                        let mut preflight = preflight.clone();
+                        preflight.set_preview(&preview_path);
+
+                        // Show the image as the Workshop will, with the info needed to tell if it's going to be cropped.
+                        let pixmap = QPixmap::from_q_string(&preview_line_edit.text());
+                        if pixmap.is_null() {
+                            preview_image_label.clear();
+                            preview_info_label.set_text(&qtr("upload_workshop_preview_invalid"));
+                        } else {
+                            preview_image_label.set_pixmap(&pixmap.scaled_4a(128, 128, AspectRatioMode::KeepAspectRatio, TransformationMode::SmoothTransformation));
+
+                            let size = preview_path.metadata().map(|metadata| metadata.len() / 1024).unwrap_or_default();
+                            let mut info = tre("upload_workshop_preview_info", &[&pixmap.width().to_string(), &pixmap.height().to_string(), &size.to_string()]);
+                            if pixmap.width() != pixmap.height() {
+                                info = format!("{} {}", info, tr("upload_workshop_preview_not_square"));
+                            }
+
+                            preview_info_label.set_text(&QString::from_std_str(info));
+                        }
+
+                        // Blocking problems disable the upload until they're fixed.
+                        if preflight.issues().is_empty() {
+                            preflight_text_edit.set_html(&qtr("upload_workshop_preflight_ok"));
+                        } else {
+                            let issues = preflight.issues().iter()
+                                .map(|issue| format!("<li><b>{}:</b> {}</li>", if issue.is_blocking() { tr("upload_workshop_preflight_error") } else { tr("upload_workshop_preflight_warning") }, issue))
+                                .collect::<Vec<_>>()
+                                .join("\n");
+
+                            let key = if preflight.is_blocking() { "upload_workshop_preflight_blocking" } else { "upload_workshop_preflight_warnings" };
+                            preflight_text_edit.set_html(&qtre(key, &[&issues]));
+                        }
+
+                        button_box.button(StandardButton::Ok).set_enabled(!preflight.is_blocking());
+                    });
 
+                    // Slot for the preview dialog.
+                    let main_ptr = main_widget.static_upcast();
+                    let preview_search_slot = SlotNoArgs::new(&main_widget, move || {
+                        let preview_line_edit: QPtr<QLineEdit> = find_widget(&main_ptr, "preview_line_edit").unwrap();
+
+                        let file_dialog = QFileDialog::from_q_widget_q_string(
+                            &preview_line_edit,
+                            &qtr("upload_workshop_preview_select"),
+                        );
+
+                        file_dialog.set_file_mode(FileMode::ExistingFile);
+                        file_dialog.set_name_filter(&QString::from_std_str(format!("{} (*.png)", tr("upload_workshop_preview_file_type"))));
+
+                        // Start in the folder of the current image, if there is one.
+                        let old_path = PathBuf::from(preview_line_edit.text().to_std_string());
+                        if let Some(old_folder) = old_path.parent().filter(|folder| folder.is_dir()) {
+                            file_dialog.set_directory_q_string(&QString::from_std_str(old_folder.to_string_lossy()));
+                        }
+
+                        if file_dialog.exec() == 1 {
+                            let selected_files = file_dialog.selected_files();
+                            let path = selected_files.at(0);
+                            preview_line_edit.set_text(path);
+                        }
+                    });
+
+                    preview_line_edit.text_changed().connect(&preview_changed_slot);
+                    preview_button.released().connect(&preview_search_slot);
+                    preview_line_edit.set_text(&QString::from_std_str(preview_path.to_string_lossy()));
+
+                    // Tags are checkable, as mods can have more than one.
                     let tags = game.steam_workshop_tags()?;
-                    for tag in &tags {
-                        tag_combo_box.add_item_q_string(&QString::from_std_str(tag));
+                    for (index, tag) in tags.iter().enumerate() {
+                        tag_list_widget.add_item_q_string(&QString::from_std_str(tag));
+
+                        let check_state = if mod_data.tags.contains(tag) { CheckState::Checked } else { CheckState::Unchecked };
+                        tag_list_widget.item(index as i32).set_check_state(check_state);
                     }
 
                     visibility_combo_box.add_item_q_string(&qtr("upload_workshop_visibility_public"));
@@ -2291,16 +2898,14 @@ impl AppUI {
                     visibility_combo_box.add_item_q_string(&qtr("upload_workshop_visibility_private"));
                     visibility_combo_box.add_item_q_string(&qtr("upload_workshop_visibility_unlisted"));
 
+                    // If we know what changed since the last upload, use it as changelog template. Failing to read it is not a reason to block the upload.
+                    let changelog = changelog.ok().flatten();
+
                     // If we got data from the workshop, populate it with that.
                     if mod_data.published_file_id > 0 {
                         title_line_edit.set_text(&QString::from_std_str(mod_data.title));
                         description_text_edit.set_plain_text(&QString::from_std_str(mod_data.description));
-                        changelog_text_edit.set_plain_text(&QString::from_std_str("me forgot changelog. Me sorry."));
-
-                        // For tag selection, we expect to have two. We need to pick the one that's not "mod".
-                        if let Some(selected_tag) = mod_data.tags.iter().find_or_first(|x| &**x != "mod") {
-                            tag_combo_box.set_current_text(&QString::from_std_str(selected_tag));
-                        }
+                        changelog_text_edit.set_plain_text(&QString::from_std_str(changelog.unwrap_or_default()));
 
                         visibility_combo_box.set_current_index(match mod_data.visibility {
                             PublishedFileVisibilityDerive::Public => 0,
@@ -2313,7 +2918,7 @@ impl AppUI {
                     // Otherwise, put default data there.
                     else {
                         title_line_edit.set_text(&QString::from_std_str(modd.id()));
-                        changelog_text_edit.set_plain_text(&QString::from_std_str("Initial release."));
+                        changelog_text_edit.set_plain_text(&QString::from_std_str(changelog.unwrap_or_else(|| "Initial release.".to_owned())));
                         visibility_combo_box.set_current_index(2);
                     }
 
@@ -2321,7 +2926,12 @@ impl AppUI {
                         let mut title = title_line_edit.text().to_std_string();
                         let description = description_text_edit.to_plain_text().to_std_string();
                         let changelog = changelog_text_edit.to_plain_text().to_std_string();
-                        let tags = vec![tag_combo_box.current_text().to_std_string()];
+                        let preview_path = PathBuf::from(preview_line_edit.text().to_std_string());
+                        let tags = (0..tag_list_widget.count())
+                            .map(|index| tag_list_widget.item(index))
+                            .filter(|item| item.check_state() == CheckState::Checked)
+                            .map(|item| item.text().to_std_string())
+                            .collect::<Vec<_>>();
                         let visibility = visibility_combo_box.current_index() as u32;
 
                         // We need at least a title. So if we don't have one, use the default one.
@@ -2329,7 +2939,7 @@ impl AppUI {
                             title = modd.id().to_string();
                         }
 
-                        crate::mod_manager::integrations::upload_mod_to_workshop(&game, modd, &title, &description, &tags, &changelog, &Some(visibility), true).map(Some)
+                        crate::mod_manager::integrations::upload_mod_to_workshop(&game, modd, &preview_path, &title, &description, &tags, &changelog, &Some(visibility), true, false).map(Some)
                     } else {
                         Ok(None)
                     }
@@ -2349,7 +2959,7 @@ impl AppUI {
     pub unsafe fn download_subscribed_mods(&self, published_file_ids: &Option<Vec<String>>) -> Result<()> {
         self.toggle_main_window(false);
 
-        crate::mod_manager::integrations::download_subscribed_mods(&self.game_selected().read().unwrap(), published_file_ids)?;
+        crate::mod_manager::integrations::download_subscribed_mods(&self.game_selected().read().unwrap(), published_file_ids, false)?;
 
         self.toggle_main_window(true);
 
@@ -2359,239 +2969,107 @@ impl AppUI {
         Ok(())
     }
 
-    pub unsafe fn check_logs(&self, game: &GameInfo, game_path: &Path, start_date: &SystemTime) -> Result<()> {
-
-        // NOTE: THIS IS A HACK. WE NEED TO USE SOME KIND OF CACHED DATA, NOT REMAKE IT HERE!!!!
-        let game_config = self.game_config().read().unwrap().clone().unwrap();
-        let load_order = self.game_load_order().read().unwrap();
-        let pack = self.data_list_ui().generate_data(&game_config, game, game_path, &load_order)?;
-
-        let vanilla_paths = game.ca_packs_paths(game_path)?;
-        let files = files_from_subdir(&game_path, false)?;
-        let paths = files.iter()
-            .filter(|path| {
-                let modified = path.metadata().unwrap().modified().unwrap();
-                //let start_date = &SystemTime::from(std::time::UNIX_EPOCH);
-                modified > *start_date && path.extension().is_some() && path.extension().unwrap() == "txt"
-            })
+    /// This function returns the Workshop ids of the selected mods. Mods not from the Workshop are ignored.
+    pub unsafe fn mod_list_selection_steam_ids(&self) -> Vec<String> {
+        let mod_ids = self.mod_list_selection()
+            .iter()
+            .map(|x| x.data_1a(VALUE_MOD_ID).to_string().to_std_string())
             .collect::<Vec<_>>();
 
-        let mut breaks = vec![];
-        for path in &paths {
-            let mut data = String::new();
-            let mut file = BufReader::new(File::open(path)?);
-
-            // This fails in the clockwork one due to being windows-1252
-            if file.read_to_string(&mut data).is_ok() {
-
-                // Normal error.
-                /*
-                ********************
-                SCRIPT ERROR, timestamp <375.0s>
-                ERROR - SCRIPT HAS FAILED - event callback was called after receiving event [WorldStartRound] but the script failed with this error message:
-                [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:609: attempt to get length of field '?' (a nil value)
-
-                The callstack of the failed script is:
-
-                stack traceback:
-                    [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:609: in function 'trigger_pre_invasion_1'
-                    [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:313: in function 'callback'
-                    [string "script\_lib\lib_core.lua"]:1930: in function <[string "script\_lib\lib_core.lua"]:1930>
-                    [C]: in function 'xpcall'
-                    [string "script\_lib\lib_core.lua"]:1930: in function 'event_protected_callback'
-                    [string "script\_lib\lib_core.lua"]:1991: in function 'event_callback'
-                    [string "script\_lib\lib_core.lua"]:2051: in function <[string "script\_lib\lib_core.lua"]:2051>
-
-                The callstack of the script which established the failed listener is:
-                stack traceback:
-                    [string "script\_lib\lib_core.lua"]:1908: in function 'add_listener'
-                    [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:260: in function 'set_status'
-                    [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:565: in function 'trigger_the_great_bastion_improved'
-                    [string "script\campaign\dynamic_disasters\disaster_the_great_bastion_improved.lua"]:486: in function 'start'
-                    [string "script\campaign\mod\dynamic_disasters.lua"]:606: in function <[string "script\campaign\mod\dynamic_disasters.lua"]:536>
-                    (tail call): ?
-                    [string "script\_lib\lib_core.lua"]:1930: in function <[string "script\_lib\lib_core.lua"]:1930>
-                    [C]: in function 'xpcall'
-                    [string "script\_lib\lib_core.lua"]:1930: in function 'event_protected_callback'
-                    [string "script\_lib\lib_core.lua"]:1991: in function 'event_callback'
-                    [string "script\_lib\lib_core.lua"]:2051: in function <[string "script\_lib\lib_core.lua"]:2051>
-                ********************
-                 */
-                let normal_errors = data.match_indices("SCRIPT ERROR, timestamp").collect::<Vec<_>>();
-                for (start_error, _) in normal_errors {
-                    if let Some(end_error) = data[start_error..].find("********************") {
-                        let message = data[start_error..start_error + end_error].to_owned();
-                        let mut script_break = ScriptBreak::default();
-                        script_break.full_log = message.to_owned();
-
-                        let start_path = "[string \"";
-                        let end_path = "\"]:";
-                        let mut paths = vec![];
-                        for (start_path_pos, _) in message.match_indices(start_path) {
-                            if let Some(end_path_pos) = message[start_path_pos + 9..].find(&end_path) {
-                                let path = message[start_path_pos + 9..start_path_pos + 9 + end_path_pos].replace("\\", "/");
-                                paths.push(path);
-                            }
-                        }
+        match *self.game_config().read().unwrap() {
+            Some(ref game_config) => mod_ids.iter()
+                .filter_map(|x| game_config.mods().get(x))
+                .filter_map(|x| x.steam_id().clone())
+                .unique()
+                .collect::<Vec<_>>(),
+            None => vec![],
+        }
+    }
 
-                        // NOTE: pack finding only works if the pack that caused it is in the current run. Take that into account for tests.
-                        for path in &paths {
-                            if let Some(file) = pack.file(&path, true) {
-                                if let Some(pack_name) = file.container_name() {
-                                    if !pack_name.is_empty() && vanilla_paths.iter().all(|x| &x.file_name().unwrap().to_string_lossy().to_string() != pack_name) {
-                                        script_break.posible_pack = pack_name.to_owned();
-
-                                        // This is only valid in newer games!!!
-                                        let modd = game_config.mods().get(pack_name);
-                                        script_break.posible_pack_mod = modd
-                                            .map(|modd| modd.name().to_string())
-                                            .unwrap_or_else(|| String::new());
-                                        script_break.posible_pack_link = modd
-                                            .map(|modd| modd.steam_id()
-                                                .clone()
-                                                .map(|id| format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", id)))
-                                            .flatten();
-                                        break;
-                                    }
-                                }
-                            }
-                        }
+    pub unsafe fn unsubscribe_from_workshop(&self, published_file_ids: &[String]) -> Result<()> {
+        if published_file_ids.is_empty() {
+            return Err(anyhow!("None of the selected mods are from the Workshop."));
+        }
 
-                        breaks.push(script_break);
-                    }
-                }
+        if !self.are_you_sure("are_you_sure_unsubscribe") {
+            return Ok(());
+        }
 
-                // Big Fat error.
-                /*
-                [out] <1593.9s>  BIG FAT SCRIPT ERROR
-                [out] <1593.9s>  [string "script\campaign\mod\meh_blightwing_duchy_campaign_features.lua"]:63: attempt to call method 'character_subtype_key' (a nil value)
-                [out] <1593.9s>  stack traceback:
-                    [string "script\_lib\mod\pj_error_wrapping.lua"]:50: in function 'condition'
-                    [string "script\_lib\lib_core.lua"]:1928: in function <[string "script\_lib\lib_core.lua"]:1928>
-                    [C]: in function 'xpcall'
-                    [string "script\_lib\lib_core.lua"]:1928: in function 'event_protected_callback'
-                    [string "script\_lib\lib_core.lua"]:1965: in function 'event_callback'
-                    [string "script\_lib\lib_core.lua"]:2051: in function <[string "script\_lib\lib_core.lua"]:2051>
-                [out] <1594.1s>   & Removing effect bundle [wh3_main_bundle_force_crackdown_corruption] from military force with cqi [80]
-                [out] <1594.1s>   & Removing effect bundle [ovn_fimir_fog_diktat_empty] from the force of character with cqi [159]
-                [out] <1594.1s>  DrunkFlamingo: Checking faction ally outposts for faction: wh2_dlc17_bst_malagor (temp tomb king ally fix)
-
-                 */
-                let big_fat_errors = data.match_indices("BIG FAT SCRIPT ERROR").collect::<Vec<_>>();
-                for (start_error, _) in big_fat_errors {
-
-                    // For end we use the third out.
-                    if let Some(first) = data[start_error..].find("[out]") {
-                        if let Some(second) = data[start_error + first + 3 ..].find("[out]") {
-                            if let Some(end_error) = data[start_error + first + 3 + second + 3..].find("[out]") {
-                                let message = data[start_error..start_error + first + 3 + second + 3 + end_error].to_owned();
-                                let mut script_break = ScriptBreak::default();
-                                script_break.full_log = message.to_owned();
-
-                                let start_path = "[string \"";
-                                let end_path = "\"]:";
-                                let mut paths = vec![];
-                                for (start_path_pos, _) in message.match_indices(start_path) {
-                                    if let Some(end_path_pos) = message[start_path_pos + 9..].find(&end_path) {
-                                        let path = message[start_path_pos + 9..start_path_pos + 9 + end_path_pos].replace("\\", "/");
-                                        paths.push(path);
-                                    }
-                                }
+        self.toggle_main_window(false);
+        let result = unsubscribe(&self.game_selected().read().unwrap(), published_file_ids);
+        self.toggle_main_window(true);
+        result?;
 
-                                // NOTE: pack finding only works if the pack that caused it is in the current run. Take that into account for tests.
-                                for path in &paths {
-                                    if let Some(file) = pack.file(&path, true) {
-                                        if let Some(pack_name) = file.container_name() {
-                                            if !pack_name.is_empty() && vanilla_paths.iter().all(|x| &x.file_name().unwrap().to_string_lossy().to_string() != pack_name) {
-                                                script_break.posible_pack = pack_name.to_owned();
-
-                                                // This is only valid in newer games!!!
-                                                let modd = game_config.mods().get(pack_name);
-                                                script_break.posible_pack_mod = modd
-                                                    .map(|modd| modd.name().to_string())
-                                                    .unwrap_or_else(|| String::new());
-                                                script_break.posible_pack_link = modd
-                                                    .map(|modd| modd.steam_id()
-                                                        .clone()
-                                                        .map(|id| format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", id)))
-                                                    .flatten();
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
+        // Once done, do a reload of the mod list.
+        self.actions_ui().reload_button().click();
 
-                                breaks.push(script_break);
-                            }
-                        }
-                    }
-                }
+        Ok(())
+    }
 
-                // File-loading errors.
-                /*
-                [out] <2.8s>            Failed to load mod file [script\campaign\mod\test_errors_1.lua], error is: cannot open test_errors_1: No such file or directory. Will attempt to require() this file to generate a more meaningful error message:
-                [out] <2.8s>                error loading module test_errors_1 from file test_errors_1:[string "script\campaign\mod\test_errors_1.lua"]:2: 'then' expected near 'aaaaa'
-                [out] <2.8s>        Failed to load mod: [script\campaign\mod\test_errors_1.lua]
+    /// This function subscribes again to the provided mods and downloads them. Broken legacy mods are unsubscribed first, so Steam downloads them again.
+    pub unsafe fn resubscribe_to_workshop(&self, published_file_ids: &[String]) -> Result<()> {
+        if published_file_ids.is_empty() {
+            return Err(anyhow!("None of the selected mods are from the Workshop."));
+        }
 
+        self.toggle_main_window(false);
+        let result = subscribe(&self.game_selected().read().unwrap(), published_file_ids, true);
+        self.toggle_main_window(true);
+        result?;
 
-                [out] <2.8s>            Failed to execute loaded mod file [script\campaign\mod\test_error_3.lua], error is: [string "script\campaign\mod\test_error_3.lua"]:1: attempt to call global 'test_func' (a nil value)
-                [out] <2.8s>        Failed to load mod: [script\campaign\mod\test_error_3.lua]
+        // Once done, do a reload of the mod list.
+        self.actions_ui().reload_button().click();
 
-                 */
-                let fail_load_errors = data.match_indices("Failed to load mod file").collect::<Vec<_>>();
-                let fail_execute_errors = data.match_indices("Failed to execute loaded mod file").collect::<Vec<_>>();
-                for (start_error, _) in fail_load_errors.into_iter().chain(fail_execute_errors.into_iter()) {
+        Ok(())
+    }
 
-                    // For end we use the third out.
-                    if let Some(end_error) = data[start_error..].find("Failed to load mod:") {
-                        let message = data[start_error..start_error + end_error].to_owned();
-                        let mut script_break = ScriptBreak::default();
-                        script_break.full_log = message.to_owned();
+    /// This function removes the content folders of the Workshop items we're no longer subscribed to, after asking the user.
+    pub unsafe fn remove_orphaned_content_folders(&self) -> Result<()> {
+        let game = self.game_selected().read().unwrap().clone();
+        let game_path = setting_path(game.key());
 
-                        // PJ for some reason uses requires that fail when the CA loader does its thing. We need to ignore his mod.
-                        if message.contains("Failed to load mod file [script\\campaign\\mod\\pj_") {
-                            continue;
-                        }
+        self.toggle_main_window(false);
+        let subscribed_items = request_subscribed_items(&game);
+        self.toggle_main_window(true);
 
-                        let start_path = "[string \"";
-                        let end_path = "\"]:";
-                        let mut paths = vec![];
-                        for (start_path_pos, _) in message.match_indices(start_path) {
-                            if let Some(end_path_pos) = message[start_path_pos + 9..].find(&end_path) {
-                                let path = message[start_path_pos + 9..start_path_pos + 9 + end_path_pos].replace("\\", "/");
-                                paths.push(path);
-                            }
-                        }
+        let folders = orphaned_content_folders(&game, &game_path, &subscribed_items?)?;
+        if folders.is_empty() {
+            show_dialog(self.main_window(), tr("remove_orphaned_content_none"), true);
+            return Ok(());
+        }
 
-                        // NOTE: pack finding only works if the pack that caused it is in the current run. Take that into account for tests.
-                        for path in &paths {
-                            if let Some(file) = pack.file(&path, true) {
-                                if let Some(pack_name) = file.container_name() {
-                                    if !pack_name.is_empty() && vanilla_paths.iter().all(|x| &x.file_name().unwrap().to_string_lossy().to_string() != pack_name) {
-                                        script_break.posible_pack = pack_name.to_owned();
-
-                                        // This is only valid in newer games!!!
-                                        let modd = game_config.mods().get(pack_name);
-                                        script_break.posible_pack_mod = modd
-                                            .map(|modd| modd.name().to_string())
-                                            .unwrap_or_else(|| String::new());
-                                        script_break.posible_pack_link = modd
-                                            .map(|modd| modd.steam_id()
-                                                .clone()
-                                                .map(|id| format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", id)))
-                                            .flatten();
-                                        break;
-                                    }
-                                }
-                            }
-                        }
+        let list = folders.iter().map(|folder| format!("<li>{}</li>", folder.to_string_lossy())).join("");
+        let dialog = QMessageBox::from_q_widget(self.main_window());
+        dialog.set_window_title(&qtr("remove_orphaned_content"));
+        dialog.set_icon(q_message_box::Icon::Warning);
+        dialog.set_text(&qtre("remove_orphaned_content_question", &[&list]));
+        dialog.set_standard_buttons(q_message_box::StandardButton::Yes | q_message_box::StandardButton::No);
 
-                        breaks.push(script_break);
-                    }
-                }
+        if dialog.exec() == q_message_box::StandardButton::Yes.to_int() {
+            for folder in &folders {
+                std::fs::remove_dir_all(folder)?;
             }
+
+            // Once done, do a reload of the mod list.
+            self.actions_ui().reload_button().click();
         }
 
+        Ok(())
+    }
+
+    pub unsafe fn check_logs(&self, game: &GameInfo, game_path: &Path, start_date: &SystemTime) -> Result<()> {
+
+        // NOTE: THIS IS A HACK. WE NEED TO USE SOME KIND OF CACHED DATA, NOT REMAKE IT HERE!!!!
+        let game_config = self.game_config().read().unwrap().clone().unwrap();
+        let load_order = self.game_load_order().read().unwrap();
+        let data_files = load_order.data_files(&game_config, game, game_path)?;
+
+        let vanilla_pack_names = log_analysis::vanilla_pack_names(game, game_path)?;
+
+        // NOTE: pack finding only works if the pack that caused it is in the current run. Take that into account for tests.
+        let paths = log_analysis::logs_modified_since(game_path, start_date)?;
+        let breaks = log_analysis::analyze_logs(&paths, &data_files, &vanilla_pack_names, &game_config)?;
+
         // If breaks are detected, show the dialog with them.
         if !breaks.is_empty() {
 
@@ -2640,7 +3118,7 @@ impl AppUI {
                     }
                 ));
 
-                item_log.set_text(&QString::from_std_str(&script_break.full_log));
+                item_log.set_text(&QString::from_std_str(script_break.full_log()));
 
                 row.append_q_standard_item(&item_pack.into_ptr().as_mut_raw_ptr());
                 row.append_q_standard_item(&item_log.into_ptr().as_mut_raw_ptr());
@@ -2658,6 +3136,392 @@ impl AppUI {
         Ok(())
     }
 
+    /// This function shows a dialog with the DB rows and loc keys defined by more than one pack of the load order.
+    pub unsafe fn show_data_conflicts(&self) -> Result<()> {
+        let report = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => {
+                self.toggle_main_window(false);
+
+                let event_loop = qt_core::QEventLoop::new_0a();
+                event_loop.process_events_0a();
+
+                let report = DataConflictReport::generate_with_loaded_schema(game_config, &self.game_load_order().read().unwrap());
+                self.toggle_main_window(true);
+                report?
+            },
+            None => return Ok(()),
+        };
+
+        if report.rows().is_empty() {
+            show_dialog(self.main_window(), tr("data_conflicts_none"), true);
+            return Ok(());
+        }
+
+        let template_path = if cfg!(debug_assertions) { DATA_CONFLICTS_VIEW_DEBUG } else { DATA_CONFLICTS_VIEW_RELEASE };
+        let main_widget = load_template(self.main_window(), template_path)?;
+        let dialog = main_widget.static_downcast::<QDialog>();
+
+        let explanation_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "explanation_label")?;
+        let explanation_groupbox: QPtr<QGroupBox> = find_widget(&main_widget.static_upcast(), "explanation_groupbox")?;
+        let conflicts_tree_view: QPtr<QTreeView> = find_widget(&main_widget.static_upcast(), "conflicts_tree_view")?;
+        explanation_label.set_text(&qtr("data_conflicts_explanation"));
+        explanation_groupbox.set_title(&qtr("data_conflicts_explanation_title"));
+        dialog.set_window_title(&qtr("data_conflicts_title"));
+
+        let conflicts_tree_model = QStandardItemModel::new_1a(&conflicts_tree_view);
+        conflicts_tree_view.set_model(&conflicts_tree_model);
+
+        // Setup the tree.
+        conflicts_tree_model.set_column_count(3);
+
+        let item_row = QStandardItem::from_q_string(&qtr("data_conflicts_row"));
+        let item_pack = QStandardItem::from_q_string(&qtr("pack_name"));
+        let item_values = QStandardItem::from_q_string(&qtr("data_conflicts_values"));
+
+        conflicts_tree_model.set_horizontal_header_item(0, item_row.into_ptr());
+        conflicts_tree_model.set_horizontal_header_item(1, item_pack.into_ptr());
+        conflicts_tree_model.set_horizontal_header_item(2, item_values.into_ptr());
+
+        // Load the data to the tree. Each row shows the values the game uses, with all the definitions of the row as children.
+        for conflict in report.rows() {
+            let row = QListOfQStandardItem::new();
+
+            let item_row = QStandardItem::new();
+            let item_pack = QStandardItem::new();
+            let item_values = QStandardItem::new();
+            item_row.set_editable(false);
+            item_pack.set_editable(false);
+            item_values.set_editable(false);
+
+            item_row.set_text(&QString::from_std_str(format!("{}: {}", conflict.table(), conflict.key())));
+            if let Some(winner) = conflict.winner() {
+                item_pack.set_text(&QString::from_std_str(winner.pack()));
+                item_values.set_text(&QString::from_std_str(winner.values().join(", ")));
+            }
+
+            for entry in conflict.entries() {
+                let child_row = QListOfQStandardItem::new();
+
+                let item_path = QStandardItem::new();
+                let item_entry_pack = QStandardItem::new();
+                let item_entry_values = QStandardItem::new();
+                item_path.set_editable(false);
+                item_entry_pack.set_editable(false);
+                item_entry_values.set_editable(false);
+
+                item_path.set_text(&QString::from_std_str(entry.path()));
+                item_entry_pack.set_text(&QString::from_std_str(entry.pack()));
+                item_entry_values.set_text(&QString::from_std_str(entry.values().join(", ")));
+
+                child_row.append_q_standard_item(&item_path.into_ptr().as_mut_raw_ptr());
+                child_row.append_q_standard_item(&item_entry_pack.into_ptr().as_mut_raw_ptr());
+                child_row.append_q_standard_item(&item_entry_values.into_ptr().as_mut_raw_ptr());
+
+                item_row.append_row_q_list_of_q_standard_item(child_row.into_ptr().as_ref().unwrap());
+            }
+
+            row.append_q_standard_item(&item_row.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_pack.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_values.into_ptr().as_mut_raw_ptr());
+
+            conflicts_tree_model.append_row_q_list_of_q_standard_item(row.into_ptr().as_ref().unwrap());
+        }
+
+        conflicts_tree_view.header().resize_sections(ResizeMode::ResizeToContents);
+
+        dialog.set_modal(true);
+        dialog.exec();
+
+        Ok(())
+    }
+
+    /// This function shows a dialog with how much of each pack of the load order is translated to the language selected in the launch options.
+    pub unsafe fn show_translation_coverage(&self) -> Result<()> {
+        let language = match self.actions_ui().launch_options().translations_language() {
+            Some(language) => language.to_owned(),
+            None => {
+                show_dialog(self.main_window(), tr("translation_coverage_no_language"), false);
+                return Ok(());
+            }
+        };
+
+        let report = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => {
+                self.toggle_main_window(false);
+
+                let event_loop = qt_core::QEventLoop::new_0a();
+                event_loop.process_events_0a();
+
+                let game = self.game_selected().read().unwrap();
+                let report = TranslationCoverageReport::generate(game_config, &game, &self.game_load_order().read().unwrap(), &language);
+                self.toggle_main_window(true);
+                report?
+            },
+            None => return Ok(()),
+        };
+
+        if report.packs().is_empty() {
+            show_dialog(self.main_window(), tr("translation_coverage_none"), true);
+            return Ok(());
+        }
+
+        let template_path = if cfg!(debug_assertions) { TRANSLATION_COVERAGE_VIEW_DEBUG } else { TRANSLATION_COVERAGE_VIEW_RELEASE };
+        let main_widget = load_template(self.main_window(), template_path)?;
+        let dialog = main_widget.static_downcast::<QDialog>();
+
+        let explanation_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "explanation_label")?;
+        let explanation_groupbox: QPtr<QGroupBox> = find_widget(&main_widget.static_upcast(), "explanation_groupbox")?;
+        let coverage_tree_view: QPtr<QTreeView> = find_widget(&main_widget.static_upcast(), "coverage_tree_view")?;
+        let button_box: QPtr<QDialogButtonBox> = find_widget(&main_widget.static_upcast(), "button_box")?;
+        button_box.button(StandardButton::Close).released().connect(dialog.slot_reject());
+        button_box.button(StandardButton::Save).set_text(&qtr("translation_coverage_export"));
+
+        let (total, translated) = report.totals();
+        explanation_label.set_text(&qtre("translation_coverage_explanation", &[&translated.to_string(), &total.to_string(), &language]));
+        explanation_groupbox.set_title(&qtr("translation_coverage_explanation_title"));
+        dialog.set_window_title(&qtre("translation_coverage_title", &[&language]));
+
+        let coverage_tree_model = QStandardItemModel::new_1a(&coverage_tree_view);
+        coverage_tree_view.set_model(&coverage_tree_model);
+
+        // Setup the tree.
+        coverage_tree_model.set_column_count(5);
+
+        let item_pack = QStandardItem::from_q_string(&qtr("pack_name"));
+        let item_coverage = QStandardItem::from_q_string(&qtr("translation_coverage_coverage"));
+        let item_translated = QStandardItem::from_q_string(&qtr("translation_coverage_translated"));
+        let item_untranslated = QStandardItem::from_q_string(&qtr("translation_coverage_untranslated"));
+        let item_needs_retranslation = QStandardItem::from_q_string(&qtr("translation_coverage_needs_retranslation"));
+
+        coverage_tree_model.set_horizontal_header_item(0, item_pack.into_ptr());
+        coverage_tree_model.set_horizontal_header_item(1, item_coverage.into_ptr());
+        coverage_tree_model.set_horizontal_header_item(2, item_translated.into_ptr());
+        coverage_tree_model.set_horizontal_header_item(3, item_untranslated.into_ptr());
+        coverage_tree_model.set_horizontal_header_item(4, item_needs_retranslation.into_ptr());
+
+        // Load the data to the tree. Each row shows the counts of a pack, with the keys that need work as children.
+        for pack in report.packs() {
+            let row = QListOfQStandardItem::new();
+
+            let item_pack = QStandardItem::new();
+            let item_coverage = QStandardItem::new();
+            let item_translated = QStandardItem::new();
+            let item_untranslated = QStandardItem::new();
+            let item_needs_retranslation = QStandardItem::new();
+            item_pack.set_editable(false);
+            item_coverage.set_editable(false);
+            item_translated.set_editable(false);
+            item_untranslated.set_editable(false);
+            item_needs_retranslation.set_editable(false);
+
+            item_pack.set_text(&QString::from_std_str(pack.pack()));
+            item_translated.set_text(&QString::from_std_str(pack.translated().to_string()));
+            item_untranslated.set_text(&QString::from_std_str(pack.untranslated().len().to_string()));
+            item_needs_retranslation.set_text(&QString::from_std_str(pack.needs_retranslation().len().to_string()));
+
+            if *pack.has_translation() {
+                item_coverage.set_text(&QString::from_std_str(format!("{:.1}%", pack.percentage())));
+            } else {
+                item_coverage.set_text(&qtr("translation_coverage_no_translation"));
+            }
+
+            let keys = pack.untranslated().iter().map(|key| (key, "translation_coverage_untranslated"))
+                .chain(pack.needs_retranslation().iter().map(|key| (key, "translation_coverage_needs_retranslation")))
+                .sorted();
+
+            for (key, status) in keys {
+                let child_row = QListOfQStandardItem::new();
+
+                let item_key = QStandardItem::new();
+                let item_status = QStandardItem::new();
+                item_key.set_editable(false);
+                item_status.set_editable(false);
+
+                item_key.set_text(&QString::from_std_str(key));
+                item_status.set_text(&qtr(status));
+
+                child_row.append_q_standard_item(&item_key.into_ptr().as_mut_raw_ptr());
+                child_row.append_q_standard_item(&item_status.into_ptr().as_mut_raw_ptr());
+
+                item_pack.append_row_q_list_of_q_standard_item(child_row.into_ptr().as_ref().unwrap());
+            }
+
+            row.append_q_standard_item(&item_pack.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_coverage.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_translated.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_untranslated.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_needs_retranslation.into_ptr().as_mut_raw_ptr());
+
+            coverage_tree_model.append_row_q_list_of_q_standard_item(row.into_ptr().as_ref().unwrap());
+        }
+
+        coverage_tree_view.header().resize_sections(ResizeMode::ResizeToContents);
+
+        // Slot to export the report, including the keys that need work, for the translators.
+        let main_ptr: QPtr<QWidget> = main_widget.static_upcast();
+        let export_slot = SlotNoArgs::new(&main_widget, move || {
+            let path = QFileDialog::get_save_file_name_4a(
+                &main_ptr,
+                &qtr("translation_coverage_export"),
+                &QString::new(),
+                &QString::from_std_str(format!("{} (*.json)", tr("translation_coverage_file_type"))),
+            ).to_std_string();
+
+            if path.is_empty() {
+                return;
+            }
+
+            let mut path = PathBuf::from(path);
+            if path.extension().is_none() {
+                path.set_extension("json");
+            }
+
+            let export = || -> Result<()> {
+                let mut file = BufWriter::new(File::create(&path)?);
+                file.write_all(&serde_json::to_vec_pretty(&report)?)?;
+                Ok(())
+            };
+
+            if let Err(error) = export() {
+                show_dialog(&main_ptr, error, false);
+            }
+        });
+
+        button_box.button(StandardButton::Save).released().connect(&export_slot);
+
+        dialog.set_modal(true);
+        dialog.exec();
+
+        Ok(())
+    }
+
+    /// This function shows an editor for the local translation of the selected mod, to the language selected in the launch options.
+    ///
+    /// Local translations have priority over the ones from the translation hub, so the next launch uses whatever gets saved here.
+    pub unsafe fn edit_local_translation(&self) -> Result<()> {
+        let selection = self.mod_list_selection();
+        if selection.len() != 1 || selection[0].data_1a(VALUE_IS_CATEGORY).to_bool() {
+            return Ok(());
+        }
+
+        let language = match self.actions_ui().launch_options().translations_language() {
+            Some(language) => language.to_owned(),
+            None => {
+                show_dialog(self.main_window(), tr("translation_coverage_no_language"), false);
+                return Ok(());
+            }
+        };
+
+        let mod_id = selection[0].data_1a(VALUE_MOD_ID).to_string().to_std_string();
+        let pack_path = match *self.game_config().read().unwrap() {
+            Some(ref game_config) => game_config.mods().get(&mod_id)
+                .and_then(|modd| modd.paths().first())
+                .cloned()
+                .ok_or_else(|| anyhow!("Mod Path not found."))?,
+            None => return Ok(()),
+        };
+
+        let pack_name = pack_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let game = self.game_selected().read().unwrap().clone();
+
+        self.toggle_main_window(false);
+        let event_loop = qt_core::QEventLoop::new_0a();
+        event_loop.process_events_0a();
+
+        let entries = TranslationOverride::load(&game, &pack_path, &language);
+        self.toggle_main_window(true);
+        let mut entries = entries?;
+
+        if entries.is_empty() {
+            show_dialog(self.main_window(), tr("translation_editor_nothing_to_translate"), false);
+            return Ok(());
+        }
+
+        let template_path = if cfg!(debug_assertions) { TRANSLATION_EDITOR_VIEW_DEBUG } else { TRANSLATION_EDITOR_VIEW_RELEASE };
+        let main_widget = load_template(self.main_window(), template_path)?;
+        let dialog = main_widget.static_downcast::<QDialog>();
+
+        let explanation_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "explanation_label")?;
+        let explanation_groupbox: QPtr<QGroupBox> = find_widget(&main_widget.static_upcast(), "explanation_groupbox")?;
+        let filter_line_edit: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "filter_line_edit")?;
+        let translations_table_view: QPtr<QTableView> = find_widget(&main_widget.static_upcast(), "translations_table_view")?;
+        let button_box: QPtr<QDialogButtonBox> = find_widget(&main_widget.static_upcast(), "button_box")?;
+        button_box.button(StandardButton::Save).released().connect(dialog.slot_accept());
+        button_box.button(StandardButton::Cancel).released().connect(dialog.slot_reject());
+
+        explanation_label.set_text(&qtr("translation_editor_explanation"));
+        explanation_groupbox.set_title(&qtr("translation_coverage_explanation_title"));
+        filter_line_edit.set_placeholder_text(&qtr("translation_editor_filter"));
+        dialog.set_window_title(&qtre("translation_editor_title", &[&pack_name, &language]));
+
+        let translations_table_filter = QSortFilterProxyModel::new_1a(&translations_table_view);
+        let translations_table_model = QStandardItemModel::new_1a(&translations_table_filter);
+        translations_table_view.set_model(&translations_table_filter);
+        translations_table_filter.set_source_model(&translations_table_model);
+        translations_table_filter.set_filter_key_column(-1);
+        translations_table_filter.set_filter_case_sensitivity(CaseSensitivity::CaseInsensitive);
+        filter_line_edit.text_changed().connect(translations_table_filter.slot_set_filter_fixed_string());
+
+        // Setup the table.
+        translations_table_model.set_column_count(4);
+
+        let item_key = QStandardItem::from_q_string(&qtr("translation_editor_key"));
+        let item_original = QStandardItem::from_q_string(&qtr("translation_editor_original"));
+        let item_hub = QStandardItem::from_q_string(&qtr("translation_editor_hub"));
+        let item_local = QStandardItem::from_q_string(&qtr("translation_editor_local"));
+
+        translations_table_model.set_horizontal_header_item(0, item_key.into_ptr());
+        translations_table_model.set_horizontal_header_item(1, item_original.into_ptr());
+        translations_table_model.set_horizontal_header_item(2, item_hub.into_ptr());
+        translations_table_model.set_horizontal_header_item(3, item_local.into_ptr());
+
+        // Load the data to the table. Only the local translation is editable.
+        for entry in &entries {
+            let row = QListOfQStandardItem::new();
+
+            let item_key = QStandardItem::from_q_string(&QString::from_std_str(entry.key()));
+            let item_original = QStandardItem::from_q_string(&QString::from_std_str(entry.original()));
+            let item_hub = QStandardItem::from_q_string(&QString::from_std_str(entry.hub()));
+            let item_local = QStandardItem::from_q_string(&QString::from_std_str(entry.local()));
+            item_key.set_editable(false);
+            item_original.set_editable(false);
+            item_hub.set_editable(false);
+
+            // Outdated translations from the hub are not used ingame, so make them easy to spot.
+            if *entry.hub_outdated() {
+                let font = item_hub.font();
+                font.set_italic(true);
+                item_hub.set_font(&font);
+                item_hub.set_tool_tip(&qtr("translation_editor_hub_outdated"));
+            }
+
+            row.append_q_standard_item(&item_key.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_original.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_hub.into_ptr().as_mut_raw_ptr());
+            row.append_q_standard_item(&item_local.into_ptr().as_mut_raw_ptr());
+
+            translations_table_model.append_row_q_list_of_q_standard_item(row.into_ptr().as_ref().unwrap());
+        }
+
+        translations_table_view.horizontal_header().set_default_section_size(300);
+        translations_table_view.resize_column_to_contents(0);
+
+        dialog.set_modal(true);
+        if dialog.exec() == 1 {
+
+            // Rows in the model are in the same order as the entries, no matter how the view is filtered.
+            for (row, entry) in entries.iter_mut().enumerate() {
+                let local = translations_table_model.item_2a(row as i32, 3).text().to_std_string();
+                entry.set_local(local);
+            }
+
+            TranslationOverride::save(&game, &pack_name, &language, &entries)?;
+            show_dialog(self.main_window(), tre("translation_editor_saved", &[&pack_name]), true);
+        }
+
+        Ok(())
+    }
+
     pub unsafe fn open_data_file_with_rpfm(&self) -> Result<()> {
         let tools = self.tools().read().unwrap();
         if let Some(tool) = tools.tools().iter().find(|tool| tool.path().ends_with("rpfm_ui.exe")) {
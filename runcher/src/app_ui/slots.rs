@@ -19,6 +19,7 @@ use qt_core::QUrl;
 use qt_core::SlotNoArgs;
 use qt_core::SlotOfBool;
 use qt_core::SlotOfDouble;
+use qt_core::SlotOfInt;
 use qt_core::SlotOfQModelIndexInt;
 use qt_core::SlotOfQString;
 
@@ -52,6 +53,7 @@ pub struct AppUISlots {
     toggle_enable_translations: QBox<SlotOfQString>,
     change_unit_multiplier: QBox<SlotOfDouble>,
     toggle_universal_rebalancer: QBox<SlotOfQString>,
+    read_selected_save: QBox<SlotOfInt>,
     open_settings: QBox<SlotNoArgs>,
     open_folders_submenu: QBox<SlotNoArgs>,
     open_game_root_folder: QBox<SlotNoArgs>,
@@ -74,6 +76,10 @@ pub struct AppUISlots {
 
     copy_load_order: QBox<SlotNoArgs>,
     paste_load_order: QBox<SlotNoArgs>,
+    export_to_launcher: QBox<SlotNoArgs>,
+    export_modpack: QBox<SlotNoArgs>,
+    import_modpack: QBox<SlotNoArgs>,
+    import_workshop_collection: QBox<SlotNoArgs>,
     reload: QBox<SlotNoArgs>,
     download_subscribed_mods: QBox<SlotNoArgs>,
     load_profile: QBox<SlotNoArgs>,
@@ -84,20 +90,31 @@ pub struct AppUISlots {
     disable_selected: QBox<SlotNoArgs>,
     upload_to_workshop: QBox<SlotNoArgs>,
     download_from_workshop: QBox<SlotNoArgs>,
+    unsubscribe_from_workshop: QBox<SlotNoArgs>,
+    resubscribe_to_workshop: QBox<SlotNoArgs>,
+    remove_orphaned_content: QBox<SlotNoArgs>,
     category_create: QBox<SlotNoArgs>,
     category_delete: QBox<SlotNoArgs>,
     category_rename: QBox<SlotNoArgs>,
     category_move: QBox<SlotOfQModelIndexInt>,
     category_sort: QBox<SlotNoArgs>,
     mod_list_context_menu_open: QBox<SlotNoArgs>,
+    edit_local_translation: QBox<SlotNoArgs>,
     copy_to_secondary: QBox<SlotNoArgs>,
     move_to_secondary: QBox<SlotNoArgs>,
 
     pack_toggle_auto_sorting: QBox<SlotOfBool>,
+    pack_data_conflicts: QBox<SlotNoArgs>,
+    pack_translation_coverage: QBox<SlotNoArgs>,
     pack_move: QBox<SlotOfQModelIndexInt>,
+    mod_list_undo: QBox<SlotNoArgs>,
+    mod_list_redo: QBox<SlotNoArgs>,
+    launch_snapshots_menu_open: QBox<SlotNoArgs>,
 
     data_view_reload: QBox<SlotNoArgs>,
     open_file_with_rpfm: QBox<SlotNoArgs>,
+
+    check_pack_changes: QBox<SlotNoArgs>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -109,8 +126,18 @@ impl AppUISlots {
 
         let launch_game = SlotNoArgs::new(&view.main_window, clone!(
             view => move || {
-                if let Err(error) = view.launch_game() {
-                    show_dialog(view.main_window(), error, false);
+                let launch = view.check_save_load_order().and_then(|launch| if launch {
+                    view.check_missing_dependencies()
+                } else {
+                    Ok(false)
+                });
+
+                match launch {
+                    Ok(true) => if let Err(error) = view.launch_game() {
+                        show_dialog(view.main_window(), error, false);
+                    },
+                    Ok(false) => {},
+                    Err(error) => show_dialog(view.main_window(), error, false),
                 }
             }
         ));
@@ -171,6 +198,12 @@ impl AppUISlots {
             }
         ));
 
+        let read_selected_save = SlotOfInt::new(view.main_window(), clone!(
+            view => move |_| {
+                view.read_selected_save();
+            }
+        ));
+
         let open_settings = SlotNoArgs::new(&view.main_window, clone!(
             view => move || {
             view.open_settings();
@@ -268,6 +301,7 @@ impl AppUISlots {
             if item.column() == 0 {
                 if let Some(ref mut game_config) = *view.game_config().write().unwrap() {
                     let mod_id = item.data_1a(VALUE_MOD_ID).to_string().to_std_string();
+                    view.record_mod_list_change(game_config, &view.game_load_order().read().unwrap());
 
                     // Update the mod's status.
                     if let Some(modd) = game_config.mods_mut().get_mut(&mod_id) {
@@ -351,7 +385,8 @@ impl AppUISlots {
                     if let Ok(game_data_path) = game_info.data_path(&game_path) {
 
                         let load_order = view.game_load_order().read().unwrap().clone();
-                        let receiver = CENTRAL_COMMAND.send_background(Command::GetStringFromLoadOrder(game_config.clone(), game_data_path, load_order));
+                        let launch_options = view.actions_ui().launch_options();
+                        let receiver = CENTRAL_COMMAND.send_background(Command::GetStringFromLoadOrder(Box::new(game_info.clone()), game_config.clone(), game_data_path, load_order, Some(launch_options)));
                         let response = CENTRAL_COMMAND.recv_try(&receiver);
                         match response {
                             Response::String(response) => {
@@ -375,11 +410,12 @@ impl AppUISlots {
                     Ok(mode) => if let Some(mode) = mode {
                         view.toggle_main_window(false);
 
-                        let receiver = CENTRAL_COMMAND.send_background(Command::GetLoadOrderFromString(mode));
+                        let game = view.game_selected().read().unwrap().clone();
+                        let receiver = CENTRAL_COMMAND.send_background(Command::GetLoadOrderFromString(mode, Box::new(game)));
                         let response = CENTRAL_COMMAND.recv_try(&receiver);
                         match response {
-                            Response::VecShareableMods(response) => {
-                                if let Err(error) = view.load_order_from_shareable_mod_list(&response) {
+                            Response::SharedLoadOrder(response) => {
+                                if let Err(error) = view.load_order_from_shared_load_order(&response) {
                                     show_dialog(view.main_window(), error, false);
                                 }
                             }
@@ -394,6 +430,41 @@ impl AppUISlots {
             }
         ));
 
+        let export_to_launcher = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.export_load_order_to_launcher() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let export_modpack = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.export_modpack() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let import_modpack = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.import_modpack() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let import_workshop_collection = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                match view.import_workshop_collection() {
+                    Ok(network_receiver) => if let Err(error) = view.update_mod_list_with_online_data(&network_receiver) {
+                        show_dialog(view.main_window(), error, false);
+                    }
+                    Err(error) => show_dialog(view.main_window(), error, false),
+                }
+            }
+        ));
+
         let reload = SlotNoArgs::new(&view.main_window, clone!(
             view => move || {
 
@@ -439,6 +510,31 @@ impl AppUISlots {
             }
         ));
 
+        let unsubscribe_from_workshop = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.unsubscribe_from_workshop(&view.mod_list_selection_steam_ids()) {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let resubscribe_to_workshop = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                match view.resubscribe_to_workshop(&view.mod_list_selection_steam_ids()) {
+                    Ok(_) => show_dialog(view.main_window(), tr("mods_downloaded"), true),
+                    Err(error) => show_dialog(view.main_window(), error, false),
+                }
+            }
+        ));
+
+        let remove_orphaned_content = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.remove_orphaned_content_folders() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
         let load_profile = SlotNoArgs::new(&view.main_window, clone!(
             view => move || {
                 if let Err(error) = view.load_profile(None, false) {
@@ -540,6 +636,14 @@ impl AppUISlots {
             }
         ));
 
+        let edit_local_translation = SlotNoArgs::new(&view.main_window, clone!(
+            view => move || {
+                if let Err(error) = view.edit_local_translation() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
         let copy_to_secondary = SlotNoArgs::new(&view.main_window, clone!(
             view => move || {
                 {
@@ -599,6 +703,7 @@ impl AppUISlots {
                     let game_path = setting_path(game.key());
                     if let Ok(game_data_path) = game.data_path(&game_path) {
                         let mut load_order = view.game_load_order().write().unwrap();
+                        view.record_mod_list_change(game_config, &load_order);
                         load_order.set_automatic(toggled);
                         load_order.update(game_config, &game_data_path);
 
@@ -617,6 +722,22 @@ impl AppUISlots {
             }
         ));
 
+        let pack_data_conflicts = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+                if let Err(error) = view.show_data_conflicts() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let pack_translation_coverage = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+                if let Err(error) = view.show_translation_coverage() {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
         let pack_move = SlotOfQModelIndexInt::new(view.main_window(), clone!(
             view => move |_, dest_row| {
                 if view.pack_list_ui().automatic_order_button().is_checked() {
@@ -629,6 +750,27 @@ impl AppUISlots {
             }
         ));
 
+        let mod_list_undo = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+                if let Err(error) = view.undo_mod_list_change(false) {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let mod_list_redo = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+                if let Err(error) = view.undo_mod_list_change(true) {
+                    show_dialog(view.main_window(), error, false);
+                }
+            }
+        ));
+
+        let launch_snapshots_menu_open = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+                AppUI::generate_launch_snapshots_submenu(&view);
+            }
+        ));
 
         let data_view_reload = SlotNoArgs::new(view.main_window(), clone!(
             view => move || {
@@ -661,6 +803,25 @@ impl AppUISlots {
             }
         }));
 
+        let check_pack_changes = SlotNoArgs::new(view.main_window(), clone!(
+            view => move || {
+
+            // Don't touch the UI while it's busy with something else. The changes will wait until the next check.
+            if !view.main_window().is_enabled() {
+                return;
+            }
+
+            let changes = view.pack_watcher().read().unwrap().as_ref().and_then(|watcher| watcher.changes());
+            if let Some(changes) = changes {
+                match view.apply_pack_changes(&changes) {
+                    Ok(network_receiver) => if let Err(error) = view.update_mod_list_with_online_data(&network_receiver) {
+                        show_dialog(view.main_window(), error, false);
+                    }
+                    Err(error) => show_dialog(view.main_window(), error, false),
+                }
+            }
+        }));
+
         Self {
             launch_game,
             toggle_logging,
@@ -670,6 +831,7 @@ impl AppUISlots {
             toggle_enable_translations,
             change_unit_multiplier,
             toggle_universal_rebalancer,
+            read_selected_save,
             open_settings,
             open_folders_submenu,
             open_game_root_folder,
@@ -692,6 +854,10 @@ impl AppUISlots {
 
             copy_load_order,
             paste_load_order,
+            export_to_launcher,
+            export_modpack,
+            import_modpack,
+            import_workshop_collection,
             reload,
             download_subscribed_mods,
 
@@ -703,19 +869,30 @@ impl AppUISlots {
             disable_selected,
             upload_to_workshop,
             download_from_workshop,
+            unsubscribe_from_workshop,
+            resubscribe_to_workshop,
+            remove_orphaned_content,
             category_create,
             category_delete,
             category_rename,
             category_move,
             category_sort,
             mod_list_context_menu_open,
+            edit_local_translation,
             copy_to_secondary,
             move_to_secondary,
 
             pack_toggle_auto_sorting,
+            pack_data_conflicts,
+            pack_translation_coverage,
             pack_move,
+            mod_list_undo,
+            mod_list_redo,
+            launch_snapshots_menu_open,
             data_view_reload,
             open_file_with_rpfm,
+
+            check_pack_changes,
         }
     }
 }
@@ -9,13 +9,11 @@
 //---------------------------------------------------------------------------//
 
 use anyhow::Result;
-use base64::{Engine as _, engine::general_purpose};
 use crossbeam::channel::Sender;
-use rayon::prelude::*;
-use zstd::stream::*;
 
 use std::path::{Path, PathBuf};
 
+use rpfm_lib::games::GameInfo;
 use rpfm_lib::integrations::{git::*, log::*};
 use rpfm_lib::schema::*;
 
@@ -23,8 +21,8 @@ use rpfm_ui_common::settings::error_path;
 
 use crate::CENTRAL_COMMAND;
 use crate::communications::*;
-use crate::games::{TRANSLATIONS_REPO, TRANSLATIONS_BRANCH, TRANSLATIONS_REMOTE};
-use crate::mod_manager::{game_config::GameConfig, load_order::{ImportedLoadOrderMode, LoadOrder}, mods::ShareableMod};
+use crate::games::{launch_options::LaunchOptions, TRANSLATIONS_REPO, TRANSLATIONS_BRANCH, TRANSLATIONS_REMOTE};
+use crate::mod_manager::{game_config::GameConfig, load_order::{ImportedLoadOrderMode, LoadOrder}, modpack::ModpackManifest, preflight::PreflightReport, shared_load_order::{mods_from_mod_list, SharedLoadOrder}, upload_history::changelog_since_last_upload};
 use crate::settings_ui::{schemas_path, translations_remote_path};
 use crate::SCHEMA;
 
@@ -86,62 +84,70 @@ pub fn background_loop() {
                 }
             }
 
-            Command::GetStringFromLoadOrder(game_config, game_data_path, load_order) => {
-                match get_string_from_load_order(game_config, &game_data_path, load_order) {
+            Command::GetStringFromLoadOrder(game, game_config, game_data_path, load_order, launch_options) => {
+                match get_string_from_load_order(&game, &game_config, &game_data_path, &load_order, launch_options) {
                     Ok(encoded) => CentralCommand::send_back(&sender, Response::String(encoded)),
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
                 }
             }
 
-            Command::GetLoadOrderFromString(mode) => {
-                match get_load_order_from_string(mode) {
-                    Ok(mods) => CentralCommand::send_back(&sender, Response::VecShareableMods(mods)),
+            Command::GetLoadOrderFromString(mode, game) => {
+                match get_load_order_from_string(mode, &game) {
+                    Ok(shared) => CentralCommand::send_back(&sender, Response::SharedLoadOrder(shared)),
                     Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
                 }
             }
 
-            Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckTranslationsUpdates | Command::RequestModsData(_,_) => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
+            Command::ExportModpack(path, game, game_config, game_data_path, load_order, launch_options) => {
+                match ModpackManifest::export(&path, &game, &game_config, &load_order, &game_data_path, launch_options) {
+                    Ok(manifest) => CentralCommand::send_back(&sender, Response::ModpackManifest(manifest)),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
+                }
+            }
+
+            Command::ImportModpack(path, game, game_path) => {
+                match ModpackManifest::import(&path, &game, &game_path) {
+                    Ok((manifest, _)) => CentralCommand::send_back(&sender, Response::ModpackManifest(manifest)),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
+                }
+            }
+
+            Command::ChangelogSinceLastUpload(game, pack_path) => {
+                match changelog_since_last_upload(&game, &pack_path) {
+                    Ok(changelog) => CentralCommand::send_back(&sender, Response::OptionString(changelog)),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
+                }
+            }
+
+            Command::GeneratePreflightReport(game, game_path, pack_path, preview_path) => {
+                match PreflightReport::generate_with_loaded_schema(&game, &game_path, &pack_path, &preview_path) {
+                    Ok(report) => CentralCommand::send_back(&sender, Response::PreflightReport(report)),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
+                }
+            }
+
+            Command::CheckUpdates | Command::CheckSchemaUpdates | Command::CheckTranslationsUpdates | Command::RequestModsData(_,_) | Command::RequestCollectionMods(_,_) => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
         }
     }
 }
 
-fn get_string_from_load_order(game_config: GameConfig, game_data_path: &Path, load_order: LoadOrder) -> Result<String> {
-    let mods = load_order.mods()
-        .par_iter()
-        .filter_map(|mod_id| game_config.mods().get(mod_id))
-        .filter(|modd| modd.enabled(game_data_path) && !modd.paths().is_empty())
-        .map(ShareableMod::from)
-        .collect::<Vec<_>>();
-
-    let mods = serde_json::to_string(&mods)?;
-    let mut compressed = vec![];
-    copy_encode(mods.as_bytes(), &mut compressed, 3)?;
-
-    Ok(general_purpose::STANDARD_NO_PAD.encode(compressed))
+/// This function encodes the enabled mods of the provided load order as a string that can be imported in another Runcher instance.
+pub fn get_string_from_load_order(game: &GameInfo, game_config: &GameConfig, game_data_path: &Path, load_order: &LoadOrder, launch_options: Option<LaunchOptions>) -> Result<String> {
+    SharedLoadOrder::new(game, game_config, load_order, game_data_path, launch_options).encode()
 }
 
-fn get_load_order_from_string(mode: ImportedLoadOrderMode) -> Result<Vec<ShareableMod>> {
+/// This function decodes a load order string, from either Runcher (any version), a mod list file, the official launcher or another mod manager.
+///
+/// Runcher strings generated for a game other than the provided one are rejected.
+pub fn get_load_order_from_string(mode: ImportedLoadOrderMode, game: &GameInfo) -> Result<SharedLoadOrder> {
     match mode {
         ImportedLoadOrderMode::Runcher(string) => {
-            let debased = general_purpose::STANDARD_NO_PAD.decode(string.as_bytes())?;
-            let mut decompressed = vec![];
-
-            copy_decode(debased.as_slice(), &mut decompressed)?;
-            serde_json::from_slice(&decompressed).map_err(From::from)
-        }
-        ImportedLoadOrderMode::Modlist(string) => {
-            let mut mods = vec![];
-            for line in string.lines() {
-                if let Some(start) = line.find("mod \"") {
-                    if let Some(mod_id) = line.get(start + 5 .. line.len() - 2) {
-                        let mut modd = ShareableMod::default();
-                        modd.set_id(mod_id.to_owned());
-
-                        mods.push(modd);
-                    }
-                }
-            }
-            Ok(mods)
+            let shared = SharedLoadOrder::decode(&string)?;
+            shared.check_game(game)?;
+            Ok(shared)
         }
+        ImportedLoadOrderMode::Modlist(string) => Ok(SharedLoadOrder::from_mods(mods_from_mod_list(&string))),
+        ImportedLoadOrderMode::CALauncher(string) => SharedLoadOrder::from_ca_launcher(&string, game),
+        ImportedLoadOrderMode::Json(string) => SharedLoadOrder::from_json_export(&string),
     }
 }
@@ -11,20 +11,31 @@
 //! Module with the code to parse cli arguments, for automation.
 
 use anyhow::{anyhow, Result};
-use clap::{builder::PossibleValuesParser, Parser};
+use clap::{builder::PossibleValuesParser, Parser, Subcommand};
 use crossbeam::channel::Receiver;
+use itertools::Itertools;
 
 #[cfg(target_os = "windows")] use std::fs::{read_dir, remove_dir_all};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
 
-use rpfm_lib::games::supported_games::*;
+use rpfm_lib::games::{GameInfo, supported_games::*};
 use rpfm_lib::integrations::log::*;
+use rpfm_lib::schema::Schema;
 
 #[cfg(target_os = "windows")] use rpfm_ui_common::PROGRAM_PATH;
-use rpfm_ui_common::settings::setting_string;
+use rpfm_ui_common::settings::{setting_path, setting_string};
 use rpfm_ui_common::utils::log_to_status_bar;
 
 use crate::app_ui::AppUI;
+use crate::background_thread::{get_load_order_from_string, get_string_from_load_order};
 use crate::communications::Response;
+use crate::games::{launch_options::LaunchOptions, prepare_launch_options, update_translations_repo};
+use crate::mod_manager::{conflicts::{ConflictReport, DataConflictReport}, game_config::GameConfig, history::{LaunchSnapshot, ModListState}, integrations::{download_subscribed_mods, launch_game_with_mod_list, publish_collection, request_collection_mods, request_pre_upload_info, upload_mod_to_workshop, workshop_item_id, PreUploadInfo, PublishedFileVisibilityDerive}, load_order::{ImportedLoadOrderMode, LoadOrder, MissingDependencyStatus}, log_analysis, mask_disabled_secondary_movie_packs, modpack::ModpackManifest, mods::ShareableMod, preflight::{default_preview_path, PreflightReport}, profiles::Profile, shared_load_order::SharedLoadOrder, translations::TranslationCoverageReport, upload_history::changelog_since_last_upload};
+use crate::SCHEMA;
+use crate::settings_ui::{schemas_path, temp_packs_folder};
+use crate::SUPPORTED_GAMES;
 
 //---------------------------------------------------------------------------//
 //                          Struct/Enum Definitions
@@ -35,16 +46,286 @@ use crate::communications::Response;
 pub(crate) struct Cli {
 
     /// Game we we want to start with. Overrides default game.
-    #[arg(short, long, required = false, value_name = "GAME", value_parser = PossibleValuesParser::new(game_keys()))]
+    #[arg(short, long, required = false, global = true, value_name = "GAME", value_parser = PossibleValuesParser::new(game_keys()))]
     game: Option<String>,
 
     /// Profile to start with. Requires a game.
-    #[arg(short, long, required = false, value_name = "PROFILE_NAME")]
+    #[arg(short, long, required = false, global = true, value_name = "PROFILE_NAME")]
     profile: Option<String>,
 
     /// If we should autostart the game/profile combo. Skips the UI. Requires a game, profile is optional.
     #[arg(short, long, required = false)]
     autostart: bool,
+
+    /// Command to execute without UI. If none is provided, the UI is started as usual.
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+
+    /// Manage the mods of the game.
+    Mods {
+        #[command(subcommand)]
+        command: ModsCommands,
+    },
+
+    /// Manage the profiles of the game.
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
+
+    /// Export or import the load order of the game.
+    LoadOrder {
+        #[command(subcommand)]
+        command: LoadOrderCommands,
+    },
+
+    /// Launch the game with the current load order, and the launch options last used for the game.
+    ///
+    /// If a profile is provided, its load order, launch options and save are used instead, without replacing the current ones.
+    Launch {
+
+        /// Print the mod list file that would be passed to the game, without writing it, generating the pack of the launch options or launching the game.
+        #[arg(short, long, required = false)]
+        dry_run: bool,
+    },
+
+    /// Find the script errors in the provided script logs, and the packs of the current load order that may have caused them.
+    AnalyzeLogs {
+
+        /// Script logs to analyze.
+        #[arg(required = true, value_name = "PATH")]
+        paths: Vec<PathBuf>,
+
+        /// Export the report as JSON.
+        #[arg(short, long, required = false)]
+        json: bool,
+
+        /// File to write the report to. If not provided, the report is printed.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ModsCommands {
+
+    /// List the mods installed for the game.
+    List {
+
+        /// Only list enabled mods.
+        #[arg(short, long, required = false)]
+        enabled: bool,
+    },
+
+    /// Enable one or more mods.
+    Enable {
+
+        /// Ids (pack names) of the mods to enable.
+        #[arg(required = true, value_name = "MOD_ID")]
+        ids: Vec<String>,
+    },
+
+    /// Disable one or more mods.
+    Disable {
+
+        /// Ids (pack names) of the mods to disable.
+        #[arg(required = true, value_name = "MOD_ID")]
+        ids: Vec<String>,
+    },
+
+    /// List the dependencies of the enabled mods that are not in the load order.
+    Dependencies {
+
+        /// Enable the missing dependencies that are installed.
+        #[arg(short, long, required = false)]
+        enable: bool,
+
+        /// Download the missing dependencies from the Workshop, if we know them.
+        #[arg(short, long, required = false)]
+        download: bool,
+    },
+
+    /// Check a mod for problems and, if none of them are blocking, upload it to the Workshop. If it was already uploaded, it's updated.
+    Upload {
+
+        /// Id (pack name) of the mod to upload.
+        #[arg(value_name = "MOD_ID")]
+        id: String,
+
+        /// Title of the mod. If not provided, the one in the Workshop is kept, or the mod id is used for new mods.
+        #[arg(short, long, required = false)]
+        title: Option<String>,
+
+        /// Description of the mod. If not provided, the one in the Workshop is kept.
+        #[arg(short, long, required = false)]
+        description: Option<String>,
+
+        /// Changelog of this version of the mod. If not provided, a list of the files changed since the last upload is used, if we know what was uploaded.
+        #[arg(short, long, required = false)]
+        changelog: Option<String>,
+
+        /// Workshop tags of the mod, separated by comma. If not provided, the ones in the Workshop are kept, or the first one of the game is used for new mods.
+        #[arg(long, required = false, value_delimiter = ',')]
+        tags: Vec<String>,
+
+        /// Preview image of the mod. If not provided, the png next to the pack, with its same name, is used.
+        #[arg(long, required = false, value_name = "PATH")]
+        preview: Option<PathBuf>,
+
+        /// Visibility of the mod: 0 for public, 1 for friends only, 2 for private and 3 for unlisted. If not provided, the one in the Workshop is kept, or private is used for new mods.
+        #[arg(short, long, required = false, value_parser = clap::value_parser!(u32).range(0..=3))]
+        visibility: Option<u32>,
+
+        /// Only check the mod for problems, without uploading it.
+        #[arg(long, required = false)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ProfileCommands {
+
+    /// List the profiles available for the game.
+    List,
+
+    /// Apply a profile, replacing the current load order with the one from the profile.
+    Apply {
+
+        /// Name of the profile to apply.
+        #[arg(value_name = "PROFILE_NAME")]
+        name: String,
+    },
+
+    /// Publish the mods of a profile as a Workshop collection, in load order. If the profile was already published, its collection is updated.
+    PublishCollection {
+
+        /// Name of the profile to publish.
+        #[arg(value_name = "PROFILE_NAME")]
+        name: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum LoadOrderCommands {
+
+    /// Export the current load order as a shareable string.
+    Export {
+
+        /// File to write the string to. If not provided, the string is printed.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Include the launch options last used for the game in the string.
+        #[arg(short, long, required = false)]
+        launch_options: bool,
+    },
+
+    /// Import a load order from a shareable string, replacing the current one.
+    Import {
+
+        /// String to import. Use either this or --file.
+        #[arg(required_unless_present = "file", value_name = "STRING")]
+        string: Option<String>,
+
+        /// File to read the string from.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        file: Option<PathBuf>,
+
+        /// Parse the string as a list of `mod "x.pack";` lines instead of a Runcher string.
+        #[arg(short, long, required = false, conflicts_with_all = ["ca_launcher", "json"])]
+        modlist: bool,
+
+        /// Parse the string as the mod data file of the official launcher (or its used mods file) instead of a Runcher string.
+        #[arg(short, long, required = false, conflicts_with = "json")]
+        ca_launcher: bool,
+
+        /// Parse the string as the json export of another mod manager instead of a Runcher string.
+        #[arg(short, long, required = false)]
+        json: bool,
+
+        /// Apply the launch options included in the string, if any.
+        #[arg(short, long, required = false)]
+        launch_options: bool,
+    },
+
+    /// Write the enabled mods of the current load order to the mod data file of the official launcher.
+    ExportLauncher,
+
+    /// Export the current load order as a modpack: an archive with the packs not in the Workshop, and a manifest with the ones that are.
+    ExportModpack {
+
+        /// File to write the modpack to.
+        #[arg(required = true, value_name = "PATH")]
+        output: PathBuf,
+
+        /// Include the launch options last used for the game in the modpack.
+        #[arg(short, long, required = false)]
+        launch_options: bool,
+    },
+
+    /// Import a modpack, extracting its packs and replacing the current load order with the one from the modpack.
+    ImportModpack {
+
+        /// Modpack to import.
+        #[arg(required = true, value_name = "PATH")]
+        path: PathBuf,
+
+        /// Apply the launch options included in the modpack, if any.
+        #[arg(short, long, required = false)]
+        launch_options: bool,
+    },
+
+    /// Replace the current load order with the one of a Workshop collection, subscribing to and downloading the mods not yet downloaded.
+    ImportCollection {
+
+        /// Id of the collection, or link to its Workshop page.
+        #[arg(required = true, value_name = "ID")]
+        id: String,
+    },
+
+    /// List the files overwritten between the packs of the current load order, and which pack wins each one.
+    Conflicts {
+
+        /// Export the report as JSON.
+        #[arg(short, long, required = false)]
+        json: bool,
+
+        /// File to write the report to. If not provided, the report is printed.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
+    /// List the DB rows and loc keys defined with different values by more than one pack of the current load order.
+    DataConflicts {
+
+        /// Export the report as JSON.
+        #[arg(short, long, required = false)]
+        json: bool,
+
+        /// File to write the report to. If not provided, the report is printed.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
+
+    /// List how many loc keys of each pack of the current load order are translated to a language, and which ones are missing or outdated.
+    TranslationCoverage {
+
+        /// Language to check. If not provided, the language selected in the launch options is used.
+        #[arg(long, required = false, value_name = "LANGUAGE")]
+        language: Option<String>,
+
+        /// Export the report as JSON, including the keys that need translation.
+        #[arg(short, long, required = false)]
+        json: bool,
+
+        /// File to write the report to. If not provided, the report is printed.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        output: Option<PathBuf>,
+    },
 }
 
 /// Function to get the supported game keys.
@@ -183,4 +464,587 @@ impl Cli {
 
         Ok((false, network_receiver))
     }
+
+    /// This function executes the subcommand passed through args, if any, without initializing the UI.
+    ///
+    /// Returns None if no subcommand was passed, so the UI can be started as usual.
+    pub fn run_subcommand(&self) -> Option<Result<()>> {
+        let command = self.command.as_ref()?;
+        info!("Command {:?} provided through args. Executing it without UI.", command);
+
+        Some(match command {
+            Commands::Mods { command } => self.mods(command),
+            Commands::Profile { command } => self.profile(command),
+            Commands::LoadOrder { command } => self.load_order(command),
+            Commands::Launch { dry_run } => self.launch(*dry_run),
+            Commands::AnalyzeLogs { paths, json, output } => self.analyze_logs(paths, *json, output),
+        })
+    }
+
+    fn mods(&self, command: &ModsCommands) -> Result<()> {
+        let (game, game_path, data_path, mut game_config, mut load_order) = self.load_game_data()?;
+
+        match command {
+            ModsCommands::List { enabled } => {
+                for modd in game_config.mods().values().sorted_by(|a, b| a.id().cmp(b.id())) {
+                    if modd.paths().is_empty() || (*enabled && !modd.enabled(&data_path)) {
+                        continue;
+                    }
+
+                    let enabled_mark = if modd.enabled(&data_path) { "x" } else { " " };
+                    match modd.steam_id() {
+                        Some(steam_id) => println!("[{}] {} - {} ({})", enabled_mark, modd.id(), modd.name(), steam_id),
+                        None => println!("[{}] {} - {}", enabled_mark, modd.id(), modd.name()),
+                    }
+                }
+
+                Ok(())
+            },
+
+            ModsCommands::Enable { ids } | ModsCommands::Disable { ids } => {
+                let enable = matches!(command, ModsCommands::Enable { .. });
+                for id in ids {
+                    match game_config.mods_mut().get_mut(id) {
+                        Some(modd) => {
+                            if !modd.can_be_toggled(&data_path) {
+                                return Err(anyhow!("Mod {} cannot be toggled.", id));
+                            }
+
+                            modd.set_enabled(enable);
+                        },
+                        None => return Err(anyhow!("Mod {} not found.", id)),
+                    }
+                }
+
+                load_order.update(&game_config, &data_path);
+                load_order.save(game)?;
+                game_config.save(game)
+            },
+
+            ModsCommands::Dependencies { enable, download } => {
+                if *enable {
+                    for mod_id in load_order.enable_missing_dependencies(&mut game_config, &data_path) {
+                        println!("Enabled {}.", mod_id);
+                    }
+
+                    load_order.save(game)?;
+                    game_config.save(game)?;
+                }
+
+                let missing = load_order.missing_dependencies(&game_config, &data_path);
+                for dependency in &missing {
+                    println!("{}", dependency);
+                }
+
+                if *download {
+                    let steam_ids = missing.iter()
+                        .filter_map(|dependency| match dependency.status() {
+                            MissingDependencyStatus::NotDownloaded(steam_id) => Some(steam_id.to_owned()),
+                            _ => None,
+                        })
+                        .unique()
+                        .collect::<Vec<_>>();
+
+                    if !steam_ids.is_empty() {
+
+                        // Enable them before downloading them, so they're in the load order once they're downloaded.
+                        game_config.mods_mut()
+                            .values_mut()
+                            .filter(|modd| modd.paths().is_empty() && modd.steam_id().as_ref().map(|steam_id| steam_ids.contains(steam_id)).unwrap_or(false))
+                            .for_each(|modd| modd.set_enabled(true));
+
+                        game_config.save(game)?;
+                        download_subscribed_mods(game, &Some(steam_ids), false)?;
+                        println!("Dependencies downloaded. Run this command again to check if anything is still missing.");
+                    }
+                }
+
+                Ok(())
+            },
+
+            ModsCommands::Upload { id, title, description, changelog, tags, preview, visibility, dry_run } => {
+                let modd = game_config.mods().get(id).ok_or_else(|| anyhow!("Mod {} not found.", id))?;
+                let pack_path = modd.paths().first().ok_or_else(|| anyhow!("Mod {} is not installed.", id))?;
+                let preview_path = preview.clone().unwrap_or_else(|| default_preview_path(pack_path));
+
+                // Tables are only checked if we have a schema, so missing schemas are not an error here.
+                let schema_path = schemas_path()?.join(game.schema_file_name());
+                let schema = Schema::load(&schema_path, None).ok();
+                let report = PreflightReport::generate(game, &game_path, pack_path, &preview_path, schema.as_ref())?;
+
+                for issue in report.issues() {
+                    println!("{}: {}", if issue.is_blocking() { "Error" } else { "Warning" }, issue);
+                }
+
+                if report.is_blocking() {
+                    return Err(anyhow!("Mod {} has problems that must be fixed before uploading it.", id));
+                }
+
+                if report.issues().is_empty() {
+                    println!("No problems found.");
+                }
+
+                if *dry_run {
+                    return Ok(());
+                }
+
+                // For mods already in the Workshop, use their current data for anything we didn't receive.
+                let mod_data = match modd.steam_id() {
+                    Some(steam_id) => request_pre_upload_info(game, steam_id, modd.creator())?,
+                    None => PreUploadInfo::default(),
+                };
+
+                let title = match title {
+                    Some(title) => title.to_owned(),
+                    None if mod_data.published_file_id > 0 => mod_data.title,
+                    None => modd.id().to_owned(),
+                };
+
+                let description = match description {
+                    Some(description) => description.to_owned(),
+                    None => mod_data.description,
+                };
+
+                // Workshopper picks a tag for mods without any valid one.
+                let tags = if tags.is_empty() {
+                    mod_data.tags
+                } else {
+                    tags.to_vec()
+                };
+
+                let changelog = match changelog {
+                    Some(changelog) => changelog.to_owned(),
+                    None => match changelog_since_last_upload(game, pack_path)? {
+                        Some(changelog) => changelog,
+                        None if mod_data.published_file_id > 0 => String::new(),
+                        None => "Initial release.".to_owned(),
+                    },
+                };
+
+                let visibility = match visibility {
+                    Some(visibility) => *visibility,
+                    None if mod_data.published_file_id > 0 => match mod_data.visibility {
+                        PublishedFileVisibilityDerive::Public => 0,
+                        PublishedFileVisibilityDerive::FriendsOnly => 1,
+                        PublishedFileVisibilityDerive::Private => 2,
+                        PublishedFileVisibilityDerive::Unlisted => 3,
+                    },
+                    None => 2,
+                };
+
+                upload_mod_to_workshop(game, modd, &preview_path, &title, &description, &tags, &changelog, &Some(visibility), true, true)?;
+                println!("Upload of {} finished.", id);
+                Ok(())
+            },
+        }
+    }
+
+    fn profile(&self, command: &ProfileCommands) -> Result<()> {
+        let (game, _, data_path, mut game_config, mut load_order) = self.load_game_data()?;
+
+        match command {
+            ProfileCommands::List => {
+                for profile in Profile::profiles_for_game(game)?.keys().sorted() {
+                    println!("{}", profile);
+                }
+
+                Ok(())
+            },
+
+            ProfileCommands::Apply { name } => {
+                let profile = Profile::load(game, name, false).map_err(|error| anyhow!("Error loading profile {}: {}.", name, error))?;
+                profile.apply(&mut game_config, &mut load_order, &data_path);
+                profile.launch_options().save_to_settings(game);
+
+                load_order.save(game)?;
+                game_config.save(game)
+            },
+
+            ProfileCommands::PublishCollection { name } => {
+                let mut profile = Profile::load(game, name, false).map_err(|error| anyhow!("Error loading profile {}: {}.", name, error))?;
+                let (steam_ids, local_only) = profile.workshop_items(&game_config);
+                if steam_ids.is_empty() {
+                    return Err(anyhow!("Profile {} has no mods from the Workshop, so there's nothing to publish.", name));
+                }
+
+                let collection = publish_collection(game, profile.workshop_collection(), &steam_ids, name, "")?;
+                profile.set_workshop_collection(Some(collection.published_file_id.to_string()));
+                profile.save(game, name)?;
+
+                println!("Profile published as the Workshop collection https://steamcommunity.com/sharedfiles/filedetails/?id={}.", collection.published_file_id);
+
+                for mod_id in &local_only {
+                    println!("Mod not in the Workshop, not included: {}.", mod_id);
+                }
+
+                for steam_id in &collection.failed_items {
+                    println!("Mod could not be added to the collection: https://steamcommunity.com/sharedfiles/filedetails/?id={}.", steam_id);
+                }
+
+                for steam_id in &collection.failed_removals {
+                    println!("Mod could not be removed from the collection: https://steamcommunity.com/sharedfiles/filedetails/?id={}.", steam_id);
+                }
+
+                Ok(())
+            },
+        }
+    }
+
+    fn load_order(&self, command: &LoadOrderCommands) -> Result<()> {
+        let (game, game_path, data_path, mut game_config, mut load_order) = self.load_game_data()?;
+
+        match command {
+            LoadOrderCommands::Export { output, launch_options } => {
+                let launch_options = if *launch_options { Some(LaunchOptions::from_settings(game)) } else { None };
+                let string = get_string_from_load_order(game, &game_config, &data_path, &load_order, launch_options)?;
+                match output {
+                    Some(path) => {
+                        let mut file = BufWriter::new(File::create(path)?);
+                        file.write_all(string.as_bytes())?;
+                        file.flush()?;
+                    },
+                    None => println!("{}", string),
+                }
+
+                Ok(())
+            },
+
+            LoadOrderCommands::Import { string, file, modlist, ca_launcher, json, launch_options } => {
+                let string = match file {
+                    Some(path) => {
+                        let mut file = BufReader::new(File::open(path)?);
+                        let mut string = String::new();
+                        file.read_to_string(&mut string)?;
+                        string
+                    },
+                    None => string.clone().unwrap_or_default(),
+                };
+
+                let mode = if *modlist {
+                    ImportedLoadOrderMode::Modlist(string)
+                } else if *ca_launcher {
+                    ImportedLoadOrderMode::CALauncher(string)
+                } else if *json {
+                    ImportedLoadOrderMode::Json(string)
+                } else {
+                    ImportedLoadOrderMode::Runcher(string.trim().to_owned())
+                };
+
+                let shared = get_load_order_from_string(mode, game)?;
+                let (missing, wrong_hash) = shared.apply(&mut game_config, &mut load_order, &data_path)?;
+
+                load_order.save(game)?;
+                game_config.save(game)?;
+
+                if *launch_options {
+                    match shared.launch_options() {
+                        Some(shared_launch_options) => {
+                            let mut shared_launch_options = shared_launch_options.clone();
+                            shared_launch_options.disable_unsupported(game);
+                            shared_launch_options.save_to_settings(game);
+                        },
+                        None => println!("The string doesn't include launch options. The current ones have been kept."),
+                    }
+                }
+
+                Self::report_import_issues(&missing, &wrong_hash);
+                Ok(())
+            },
+
+            LoadOrderCommands::ExportLauncher => {
+                let path = load_order.export_to_ca_launcher(&game_config, game, &data_path)?;
+                println!("Mod data of the official launcher written to {}.", path.to_string_lossy());
+                Ok(())
+            },
+
+            LoadOrderCommands::ExportModpack { output, launch_options } => {
+                let launch_options = if *launch_options { Some(LaunchOptions::from_settings(game)) } else { None };
+                let manifest = ModpackManifest::export(output, game, &game_config, &load_order, &data_path, launch_options)?;
+
+                println!("Modpack written to {}, with {} bundled packs and {} Workshop mods.", output.to_string_lossy(), manifest.bundled_packs().len(), manifest.workshop_mods().len());
+                Ok(())
+            },
+
+            LoadOrderCommands::ImportModpack { path, launch_options } => {
+                let (manifest, extracted) = ModpackManifest::import(path, game, &game_path)?;
+                for pack_path in &extracted {
+                    println!("Pack extracted: {}.", pack_path.to_string_lossy());
+                }
+
+                // Make sure the extracted packs are in the mod list before applying the load order.
+                game_config.update_mod_list(game, &game_path, &mut load_order, true)?;
+
+                let shared = manifest.load_order();
+                let (missing, wrong_hash) = shared.apply(&mut game_config, &mut load_order, &data_path)?;
+
+                load_order.save(game)?;
+                game_config.save(game)?;
+
+                if *launch_options {
+                    match shared.launch_options() {
+                        Some(shared_launch_options) => {
+                            let mut shared_launch_options = shared_launch_options.clone();
+                            shared_launch_options.disable_unsupported(game);
+                            shared_launch_options.save_to_settings(game);
+                        },
+                        None => println!("The modpack doesn't include launch options. The current ones have been kept."),
+                    }
+                }
+
+                Self::report_import_issues(&missing, &wrong_hash);
+                Ok(())
+            },
+
+            LoadOrderCommands::ImportCollection { id } => {
+                let collection_id = workshop_item_id(id).ok_or_else(|| anyhow!("{} is not a valid Workshop collection id or link.", id))?;
+                let items = request_collection_mods(game, &collection_id)?;
+
+                let missing = items.iter()
+                    .filter_map(|item| item.steam_id().clone())
+                    .filter(|steam_id| !game_config.mods()
+                        .values()
+                        .any(|modd| modd.steam_id().as_ref() == Some(steam_id) && !modd.paths().is_empty()))
+                    .collect::<Vec<_>>();
+
+                if !missing.is_empty() {
+                    println!("Downloading {} mods from the collection.", missing.len());
+                    download_subscribed_mods(game, &Some(missing), true)?;
+
+                    // Make sure the downloaded packs are in the mod list before applying the load order.
+                    game_config.update_mod_list(game, &game_path, &mut load_order, true)?;
+                }
+
+                let shared = SharedLoadOrder::from_workshop_collection(&items);
+                let (missing, wrong_hash) = shared.apply(&mut game_config, &mut load_order, &data_path)?;
+
+                load_order.save(game)?;
+                game_config.save(game)?;
+
+                Self::report_import_issues(&missing, &wrong_hash);
+                Ok(())
+            },
+
+            LoadOrderCommands::Conflicts { json, output } => {
+                let report = ConflictReport::generate(&game_config, game, &game_path, &load_order)?;
+
+                let string = if *json {
+                    serde_json::to_string_pretty(&report)?
+                } else {
+                    report.files().iter()
+                        .map(|file| {
+                            let losers = file.losers().join(", ");
+                            format!("{}: {} wins over {}", file.path(), file.winner().unwrap_or_default(), losers)
+                        })
+                        .join("\n")
+                };
+
+                Self::write_report(&string, output)
+            },
+
+            LoadOrderCommands::DataConflicts { json, output } => {
+                let schema_path = schemas_path()?.join(game.schema_file_name());
+                let schema = Schema::load(&schema_path, None).map_err(|error| anyhow!("Error loading the schema for {}: {}. Make sure the schemas are downloaded.", game.key(), error))?;
+                let report = DataConflictReport::generate(&game_config, &load_order, &schema)?;
+
+                let string = if *json {
+                    serde_json::to_string_pretty(&report)?
+                } else {
+                    report.rows().iter()
+                        .map(|row| {
+                            let entries = row.entries().iter()
+                                .map(|entry| format!("  {} ({}): {}", entry.pack(), entry.path(), entry.values().join(", ")))
+                                .join("\n");
+
+                            format!("{}: {}\n{}", row.table(), row.key(), entries)
+                        })
+                        .join("\n")
+                };
+
+                Self::write_report(&string, output)
+            },
+
+            LoadOrderCommands::TranslationCoverage { language, json, output } => {
+                let language = match language {
+                    Some(language) => language.to_owned(),
+                    None => LaunchOptions::from_settings(game).translations_language().clone()
+                        .ok_or_else(|| anyhow!("No language provided, and there is no translation language selected in the launch options."))?,
+                };
+
+                // Use the same translations the launch would use. If we fail to update them, use the ones we have.
+                if let Err(error) = update_translations_repo() {
+                    eprintln!("Error updating the translations, using the ones already downloaded: {}", error);
+                }
+
+                let report = TranslationCoverageReport::generate(&game_config, game, &load_order, &language)?;
+
+                let string = if *json {
+                    serde_json::to_string_pretty(&report)?
+                } else {
+                    report.packs().iter()
+                        .map(|pack| format!("{}: {:.1}% ({} translated, {} untranslated, {} need retranslation{})",
+                            pack.pack(),
+                            pack.percentage(),
+                            pack.translated(),
+                            pack.untranslated().len(),
+                            pack.needs_retranslation().len(),
+                            if *pack.has_translation() { "" } else { ", no translation found" }
+                        ))
+                        .join("\n")
+                };
+
+                Self::write_report(&string, output)
+            },
+        }
+    }
+
+    fn launch(&self, dry_run: bool) -> Result<()> {
+        let (game, game_path, data_path, mut game_config, mut load_order) = self.load_game_data()?;
+
+        // Launch options use the values last set for the game, unless we got a profile.
+        let (launch_options, save) = match self.profile {
+            Some(ref name) => {
+                let profile = Profile::load(game, name, false).map_err(|error| anyhow!("Error loading profile {}: {}.", name, error))?;
+                profile.apply(&mut game_config, &mut load_order, &data_path);
+
+                let mut launch_options = profile.launch_options().clone();
+                launch_options.disable_unsupported(game);
+                (launch_options, profile.save().clone())
+            },
+            None => (LaunchOptions::from_settings(game), None),
+        };
+
+        // Warn about anything that may have made the automatic sorting produce a broken order.
+        for issue in load_order.dependency_issues() {
+            eprintln!("Warning: {}", issue);
+        }
+
+        for dependency in load_order.missing_dependencies(&game_config, &data_path) {
+            eprintln!("Warning: {}", dependency);
+        }
+
+        let mut folder_list = String::new();
+        let mut pack_list = String::new();
+
+        if !dry_run {
+
+            // Schemas are optional, so don't interrupt the launch due to them not being present.
+            let schema_path = schemas_path()?.join(game.schema_file_name());
+            *SCHEMA.write().unwrap() = Schema::load(&schema_path, None).ok();
+
+            // Same with translations. If we fail to update them, use the ones we have.
+            if launch_options.translations_language().is_some() {
+                if let Err(error) = update_translations_repo() {
+                    eprintln!("Error updating the translations, using the ones already downloaded: {}", error);
+                }
+            }
+
+            prepare_launch_options(&launch_options, game, &game_path, &data_path, &load_order.pack_paths(&game_config), &mut folder_list)?;
+        }
+
+        // On dry runs we don't generate the reserved pack, but the mod list still needs to load it.
+        else {
+            launch_options.reserved_pack_path(game, &data_path, &temp_packs_folder(game)?, &mut folder_list);
+        }
+
+        load_order.build_load_order_string(&game_config, game, &data_path, &mut pack_list, &mut folder_list);
+
+        if dry_run {
+            println!("{}{}", folder_list, pack_list);
+            return Ok(());
+        }
+
+        mask_disabled_secondary_movie_packs(&game_config, game, &data_path, &folder_list)?;
+        LoadOrder::save_mod_list_file(game, &game_path, &folder_list, &pack_list)?;
+
+        if let Err(error) = LaunchSnapshot::save_for_game(game, ModListState::new(&game_config, &load_order, &data_path)) {
+            warn!("Error saving the launch snapshot: {}", error);
+        }
+
+        let mut extra_args = vec![];
+        if let Some(save) = save {
+            extra_args.push("game_startup_mode".to_owned());
+            extra_args.push("campaign_load".to_owned());
+            extra_args.push(save);
+        }
+
+        launch_game_with_mod_list(game, &game_path, &extra_args, false)
+    }
+
+    fn analyze_logs(&self, paths: &[PathBuf], json: bool, output: &Option<PathBuf>) -> Result<()> {
+        let (game, game_path, _, game_config, load_order) = self.load_game_data()?;
+        let data_files = load_order.data_files(&game_config, game, &game_path)?;
+
+        let vanilla_pack_names = log_analysis::vanilla_pack_names(game, &game_path)?;
+
+        let breaks = log_analysis::analyze_logs(paths, &data_files, &vanilla_pack_names, &game_config)?;
+
+        let string = if json {
+            serde_json::to_string_pretty(&breaks)?
+        } else {
+            breaks.iter()
+                .map(|script_break| {
+                    let culprit = if script_break.posible_pack().is_empty() {
+                        "unknown pack".to_owned()
+                    } else {
+                        match script_break.posible_pack_link() {
+                            Some(link) => format!("{} ({}, {})", script_break.posible_pack_mod(), script_break.posible_pack(), link),
+                            None => script_break.posible_pack().to_owned(),
+                        }
+                    };
+
+                    format!("{:?}, possibly caused by {}:\n{}", script_break.kind(), culprit, script_break.full_log().trim_end())
+                })
+                .join("\n\n")
+        };
+
+        Self::write_report(&string, output)
+    }
+
+    /// This function writes a report to the provided file, or prints it if there's no file.
+    fn write_report(report: &str, output: &Option<PathBuf>) -> Result<()> {
+        match output {
+            Some(path) => {
+                let mut file = BufWriter::new(File::create(path)?);
+                file.write_all(report.as_bytes())?;
+                file.flush()?;
+            },
+            None => println!("{}", report),
+        }
+
+        Ok(())
+    }
+
+    /// This function reports the mods we couldn't find, and the ones we found but whose packs are different than the expected ones, after importing a load order.
+    fn report_import_issues(missing: &[ShareableMod], wrong_hash: &[ShareableMod]) {
+        for modd in missing {
+            match modd.steam_id() {
+                Some(steam_id) => println!("Mod not found in the mod list: {} (https://steamcommunity.com/sharedfiles/filedetails/?id={}).", modd.id(), steam_id),
+                None => println!("Mod not found in the mod list: {}.", modd.id()),
+            }
+        }
+
+        for modd in wrong_hash {
+            println!("Mod found, but its pack is different from the one expected: {}.", modd.id());
+        }
+    }
+
+    /// This function loads the game config and load order of the game passed through args (or the default game), without touching the UI.
+    fn load_game_data(&self) -> Result<(&'static GameInfo, PathBuf, PathBuf, GameConfig, LoadOrder)> {
+        let game_key = self.game.clone().unwrap_or_else(|| setting_string("default_game"));
+        let game = SUPPORTED_GAMES.game(&game_key).ok_or(anyhow!("Game {} is not a valid game.", game_key))?;
+        let game_path = setting_path(game.key());
+        let data_path = game.data_path(&game_path)?;
+
+        // Trigger an update of all game configs and profiles, just in case one needs update.
+        let _ = GameConfig::update(game.key());
+
+        let mut load_order = LoadOrder::load(game).unwrap_or_else(|_| Default::default());
+        let mut game_config = GameConfig::load(game, true)?;
+
+        let _ = Profile::update(&game_config, game);
+
+        game_config.update_mod_list(game, &game_path, &mut load_order, true)?;
+
+        Ok((game, game_path, data_path, game_config, load_order))
+    }
 }
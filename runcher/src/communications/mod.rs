@@ -20,7 +20,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use rpfm_lib::games::GameInfo;
 use rpfm_lib::integrations::{log::info, git::GitResponse};
 
-use crate::mod_manager::{game_config::GameConfig, load_order::{ImportedLoadOrderMode, LoadOrder}, mods::{Mod, ShareableMod}};
+use crate::games::launch_options::LaunchOptions;
+use crate::mod_manager::{game_config::GameConfig, load_order::{ImportedLoadOrderMode, LoadOrder}, modpack::ModpackManifest, mods::Mod, preflight::PreflightReport, shared_load_order::SharedLoadOrder};
 use crate::updater_ui::APIResponse;
 
 /// This const is the standard message in case of message communication error. If this happens, crash the program.
@@ -57,9 +58,14 @@ pub enum Command {
     UpdateSchemas(String),
     CheckTranslationsUpdates,
     UpdateTranslations,
-    GetStringFromLoadOrder(GameConfig, PathBuf, LoadOrder),
-    GetLoadOrderFromString(ImportedLoadOrderMode),
+    GetStringFromLoadOrder(Box<GameInfo>, GameConfig, PathBuf, LoadOrder, Option<LaunchOptions>),
+    GetLoadOrderFromString(ImportedLoadOrderMode, Box<GameInfo>),
+    ExportModpack(PathBuf, Box<GameInfo>, GameConfig, PathBuf, LoadOrder, Option<LaunchOptions>),
+    ImportModpack(PathBuf, Box<GameInfo>, PathBuf),
     RequestModsData(Box<GameInfo>, Vec<String>),
+    RequestCollectionMods(Box<GameInfo>, String),
+    ChangelogSinceLastUpload(Box<GameInfo>, PathBuf),
+    GeneratePreflightReport(Box<GameInfo>, PathBuf, PathBuf, PathBuf),
 }
 
 /// This enum defines the responses (messages) you can send to the to the UI thread as result of a command.
@@ -70,9 +76,12 @@ pub enum Response {
     Success,
     Error(Error),
     String(String),
+    OptionString(Option<String>),
     APIResponse(APIResponse),
     APIResponseGit(GitResponse),
-    VecShareableMods(Vec<ShareableMod>),
+    SharedLoadOrder(SharedLoadOrder),
+    ModpackManifest(ModpackManifest),
+    PreflightReport(PreflightReport),
     VecMod(Vec<Mod>),
 }
 
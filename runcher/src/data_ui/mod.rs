@@ -28,7 +28,7 @@ use qt_core::QTimer;
 use cpp_core::CppBox;
 use cpp_core::CppDeletable;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use getset::*;
 use rayon::prelude::*;
 
@@ -38,12 +38,11 @@ use std::rc::Rc;
 use rpfm_ui_common::locale::*;
 use rpfm_ui_common::utils::*;
 
-use rpfm_lib::games::pfh_file_type::PFHFileType;
-use rpfm_lib::files::{FileType, RFile, pack::Pack};
+use rpfm_lib::files::FileType;
 use rpfm_lib::games::GameInfo;
 
 use crate::ffi::*;
-use crate::mod_manager::{game_config::GameConfig, load_order::LoadOrder};
+use crate::mod_manager::{game_config::GameConfig, load_order::LoadOrder, pack_index::DataFile};
 
 use self::pack_tree::*;
 use self::slots::DataListUISlots;
@@ -143,54 +142,6 @@ impl DataListUI {
         self.filter_case_sensitive_button().set_enabled(enable);
     }
 
-    pub fn generate_data(&self, game_config: &GameConfig, game: &GameInfo, game_path: &Path, load_order: &LoadOrder) -> Result<Pack> {
-
-        // Only load this if the game path is actually a path.
-        if game_path.exists() && game_path.is_dir() {
-
-            // Build the full pack list with the vanilla packs.
-            let vanilla_paths = game.ca_packs_paths(game_path)?;
-            let movie_paths = load_order.movies().iter()
-                .filter_map(|mod_id| game_config.mods().get(mod_id))
-                .filter_map(|modd| modd.paths().first())
-                .cloned()
-                .collect::<Vec<_>>();
-
-            let mut base_packs = vanilla_paths.iter().chain(movie_paths.iter())
-                .filter_map(|path| Pack::read_and_merge(&[path.to_path_buf()], true, false, false).ok())
-                .collect::<Vec<_>>();
-
-            base_packs.sort_by(|pack_a, pack_b| if pack_a.pfh_file_type() != pack_b.pfh_file_type() {
-                pack_a.pfh_file_type().cmp(&pack_b.pfh_file_type())
-            } else {
-                pack_a.disk_file_path().cmp(pack_b.disk_file_path())
-            });
-
-            // Generate the "merged pack" from the load order mods, and inject them into the full pack list.
-            let mut mod_packs_sorted = load_order.mods().iter()
-                .filter_map(|mod_id| load_order.packs().get(mod_id))
-                .cloned()
-                .collect::<Vec<_>>();
-
-            // If we have movie packs in the base ones, insert the mods before the movie packs.
-            //
-            // If not, insert them at the end of the list.
-            if let Some(pos) = base_packs.iter().position(|x| x.pfh_file_type() == PFHFileType::Movie) {
-                let mut movie_packs = base_packs.split_off(pos);
-                base_packs.append(&mut mod_packs_sorted);
-                base_packs.append(&mut movie_packs);
-            } else {
-                base_packs.append(&mut mod_packs_sorted);
-            };
-
-            let full_pack = Pack::merge(&base_packs)?;
-
-            Ok(full_pack)
-        } else {
-            Err(anyhow!("Game Path not found."))
-        }
-     }
-
     pub unsafe fn load(&self, game_config: &GameConfig, game: &GameInfo, game_path: &Path, load_order: &LoadOrder) -> Result<()> {
         self.tree_view.update_treeview(true, &mut TreeViewOperation::Clear);
 
@@ -199,10 +150,10 @@ impl DataListUI {
         // Only load this if the game path is actually a path.
         if game_path.exists() && game_path.is_dir() {
             self.set_enabled(true);
-            let full_pack = self.generate_data(game_config, game, game_path, load_order)?;
+            let data_files = load_order.data_files(game_config, game, game_path)?;
 
             // Then, build the tree.
-            let build_data = full_pack.files().par_iter().map(|(_, file)| From::from(file)).collect();
+            let build_data = data_files.par_iter().map(|(_, file)| From::from(file)).collect();
             self.tree_view.update_treeview(true, &mut TreeViewOperation::Build(build_data));
 
             // Enlarge the first column if it's too small, and autoexpand the first node.
@@ -264,12 +215,12 @@ impl DataListUI {
     }
 }
 
-impl From<&RFile> for RFileInfo {
-    fn from(rfile: &RFile) -> Self {
+impl From<&DataFile> for RFileInfo {
+    fn from(file: &DataFile) -> Self {
         Self {
-            path: rfile.path_in_container_raw().to_owned(),
-            container_name: rfile.container_name().clone(),
-            file_type: rfile.file_type(),
+            path: file.path().to_owned(),
+            container_name: Some(file.pack_name().to_owned()),
+            file_type: *file.file_type(),
         }
     }
 }
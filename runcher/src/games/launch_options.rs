@@ -0,0 +1,172 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the launch options, independent from the UI used to set them.
+
+use getset::*;
+use serde::{Deserialize, Serialize};
+
+use std::path::{Path, PathBuf};
+
+use rpfm_lib::games::{GameInfo, supported_games::*};
+
+use rpfm_ui_common::settings::*;
+
+use super::{RESERVED_PACK_NAME, RESERVED_PACK_NAME_ALTERNATIVE};
+
+#[cfg(test)] mod test;
+
+/// Value used by the UI comboboxes to mark an option as not in use.
+const NO_VALUE: &str = "--";
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+#[derive(Clone, Debug, PartialEq, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct LaunchOptions {
+
+    // If we should enable script logging.
+    enable_logging: bool,
+
+    // If we should skip the intro videos.
+    enable_skip_intros: bool,
+
+    // If we should remove the trait limit of characters.
+    remove_trait_limit: bool,
+
+    // If we should merge all mods into a single pack before launching.
+    merge_all_mods: bool,
+
+    // Language to translate the mods to, if any.
+    translations_language: Option<String>,
+
+    // Multiplier to apply to the unit sizes. 1.0 means no change.
+    unit_multiplier: f64,
+
+    // Id of the pack to use as base for the universal rebalancer, if any.
+    universal_rebalancer_base_mod: Option<String>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            enable_logging: false,
+            enable_skip_intros: false,
+            remove_trait_limit: false,
+            merge_all_mods: false,
+            translations_language: None,
+            unit_multiplier: 1.00,
+            universal_rebalancer_base_mod: None,
+        }
+    }
+}
+
+impl LaunchOptions {
+
+    /// This function builds the launch options for the provided game from the values stored in the settings.
+    ///
+    /// Options not supported by the game are ignored.
+    pub fn from_settings(game: &GameInfo) -> Self {
+        let unit_multiplier = setting_f32(&format!("unit_multiplier_{}", game.key())) as f64;
+
+        let mut options = Self {
+            enable_logging: setting_bool(&format!("enable_logging_{}", game.key())),
+            enable_skip_intros: setting_bool(&format!("enable_skip_intros_{}", game.key())),
+            remove_trait_limit: setting_bool(&format!("remove_trait_limit_{}", game.key())),
+            merge_all_mods: setting_bool(&format!("merge_all_mods_{}", game.key())),
+            translations_language: Self::optional_value(setting_string(&format!("enable_translations_{}", game.key()))),
+            unit_multiplier: if unit_multiplier == 0.00 { 1.00 } else { unit_multiplier },
+            universal_rebalancer_base_mod: Self::optional_value(setting_string(&format!("universal_rebalancer_{}", game.key()))),
+        };
+
+        options.disable_unsupported(game);
+        options
+    }
+
+    /// This function stores the launch options in the settings, as the last ones used for the provided game.
+    pub fn save_to_settings(&self, game: &GameInfo) {
+        set_setting_bool(&format!("enable_logging_{}", game.key()), self.enable_logging);
+        set_setting_bool(&format!("enable_skip_intros_{}", game.key()), self.enable_skip_intros);
+        set_setting_bool(&format!("remove_trait_limit_{}", game.key()), self.remove_trait_limit);
+        set_setting_bool(&format!("merge_all_mods_{}", game.key()), self.merge_all_mods);
+        set_setting_string(&format!("enable_translations_{}", game.key()), self.translations_language.as_deref().unwrap_or(NO_VALUE));
+        set_setting_f32(&format!("unit_multiplier_{}", game.key()), self.unit_multiplier as f32);
+        set_setting_string(&format!("universal_rebalancer_{}", game.key()), self.universal_rebalancer_base_mod.as_deref().unwrap_or(NO_VALUE));
+    }
+
+    /// This function returns if any of the options needs the reserved pack to be generated.
+    pub fn needs_reserved_pack(&self) -> bool {
+        self.enable_logging ||
+            self.enable_skip_intros ||
+            self.translations_language.is_some() ||
+            self.universal_rebalancer_base_mod.is_some() ||
+            self.unit_multiplier != 1.00
+    }
+
+    /// This function returns the path the reserved pack for these options has to be saved to, or None if they don't need one.
+    ///
+    /// If the game loads it from the temp packs folder, the line to add that folder is pushed to the folder list.
+    pub fn reserved_pack_path(&self, game: &GameInfo, data_path: &Path, temp_packs_folder: &Path, folder_list: &mut String) -> Option<PathBuf> {
+        if !self.needs_reserved_pack() {
+            return None;
+        }
+
+        // We need to use an alternative name for Shogun 2, Rome 2, Attila and Thrones because their load order logic for movie packs seems... either different or broken.
+        let reserved_pack_name = if game.key() == KEY_SHOGUN_2 || game.key() == KEY_ROME_2 || game.key() == KEY_ATTILA || game.key() == KEY_THRONES_OF_BRITANNIA {
+            RESERVED_PACK_NAME_ALTERNATIVE
+        } else {
+            RESERVED_PACK_NAME
+        };
+
+        // Support for add_working_directory seems to be only present in rome 2 and newer games. For older games, we drop the pack into /data.
+        if *game.raw_db_version() >= 1 {
+            folder_list.push_str(&format!("add_working_directory \"{}\";\n", temp_packs_folder.to_string_lossy()));
+            Some(temp_packs_folder.join(reserved_pack_name))
+        } else {
+            Some(data_path.join(reserved_pack_name))
+        }
+    }
+
+    /// This function disables the options the provided game doesn't support.
+    ///
+    /// Keep this in sync with the options enabled per game in `setup_actions`.
+    pub fn disable_unsupported(&mut self, game: &GameInfo) {
+        if !matches!(game.key(), KEY_PHARAOH_DYNASTIES | KEY_PHARAOH | KEY_WARHAMMER_3 | KEY_TROY | KEY_WARHAMMER_2) {
+            self.enable_logging = false;
+        }
+
+        if game.key() != KEY_WARHAMMER_3 {
+            self.remove_trait_limit = false;
+            self.universal_rebalancer_base_mod = None;
+        }
+
+        if game.key() != KEY_WARHAMMER_3 && game.key() != KEY_THREE_KINGDOMS {
+            self.unit_multiplier = 1.00;
+        }
+
+        // Disabled in all games until the performance problems are fixed.
+        self.merge_all_mods = false;
+    }
+
+    /// This function turns the empty and "--" values the UI uses for "no value" into None.
+    fn optional_value(value: String) -> Option<String> {
+        if value.is_empty() || value == NO_VALUE {
+            None
+        } else {
+            Some(value)
+        }
+    }
+}
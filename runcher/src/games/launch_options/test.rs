@@ -0,0 +1,52 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the mod list lines the launch options produce.
+
+use super::*;
+use crate::SUPPORTED_GAMES;
+
+fn reserved_pack_path(launch_options: &LaunchOptions, game_key: &str) -> (Option<PathBuf>, String) {
+    let game = SUPPORTED_GAMES.game(game_key).unwrap();
+    let mut launch_options = launch_options.clone();
+    launch_options.disable_unsupported(game);
+
+    let mut folder_list = String::new();
+    let path = launch_options.reserved_pack_path(game, Path::new("/game/data"), Path::new("/config/temp_packs"), &mut folder_list);
+    (path, folder_list)
+}
+
+#[test]
+fn test_reserved_pack_path() {
+
+    // Default options don't touch the mod list.
+    assert_eq!(reserved_pack_path(&LaunchOptions::default(), KEY_WARHAMMER_3), (None, String::new()));
+
+    // Newer games load the reserved pack from the temp packs folder.
+    let mut launch_options = LaunchOptions::default();
+    launch_options.set_enable_skip_intros(true);
+    assert_eq!(reserved_pack_path(&launch_options, KEY_WARHAMMER_3), (
+        Some(PathBuf::from("/config/temp_packs").join(RESERVED_PACK_NAME)),
+        "add_working_directory \"/config/temp_packs\";\n".to_owned()
+    ));
+
+    // Some games need the alternative name to load it after the movie packs.
+    assert_eq!(reserved_pack_path(&launch_options, KEY_ATTILA).0, Some(PathBuf::from("/config/temp_packs").join(RESERVED_PACK_NAME_ALTERNATIVE)));
+
+    // Older games don't support add_working_directory, so it goes to /data.
+    assert_eq!(reserved_pack_path(&launch_options, KEY_EMPIRE), (Some(PathBuf::from("/game/data").join(RESERVED_PACK_NAME)), String::new()));
+
+    // Options the game doesn't support don't need the reserved pack.
+    let mut launch_options = LaunchOptions::default();
+    launch_options.set_unit_multiplier(2.0);
+    launch_options.set_remove_trait_limit(true);
+    assert!(reserved_pack_path(&launch_options, KEY_WARHAMMER_3).0.is_some());
+    assert_eq!(reserved_pack_path(&launch_options, KEY_TROY), (None, String::new()));
+}
@@ -18,23 +18,21 @@ use std::path::{PathBuf, Path};
 
 use rpfm_extensions::dependencies::Dependencies;
 use rpfm_extensions::optimizer::Optimizable;
-use rpfm_extensions::translator::*;
 
-use rpfm_lib::files::{Container, ContainerPath, EncodeableExtraData, FileType, loc::Loc, pack::Pack, RFile, RFileDecoded, table::DecodedData};
+use rpfm_lib::files::{Container, EncodeableExtraData, FileType, loc::Loc, pack::Pack, RFile, RFileDecoded, table::DecodedData};
 use rpfm_lib::games::{*, pfh_file_type::PFHFileType, supported_games::*};
-use rpfm_lib::integrations::git::GitResponse;
+use rpfm_lib::integrations::git::{GitIntegration, GitResponse};
 use rpfm_lib::utils::files_from_subdir;
 
-use rpfm_ui_common::locale::tre;
 use rpfm_ui_common::settings::*;
-use rpfm_ui_common::utils::show_dialog;
 
 use crate::app_ui::AppUI;
-use crate::CENTRAL_COMMAND;
-use crate::communications::*;
+use crate::mod_manager::translations::load_pack_translation;
 use crate::SCHEMA;
 use crate::settings_ui::{temp_packs_folder, translations_local_path, translations_remote_path};
 
+use self::launch_options::LaunchOptions;
+
 const EMPTY_CA_VP8: [u8; 595] = [
     0x43, 0x41, 0x4d, 0x56, 0x01, 0x00, 0x29, 0x00, 0x56, 0x50, 0x38, 0x30, 0x80, 0x02, 0xe0, 0x01, 0x55, 0x55,
     0x85, 0x42, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x4a, 0x02, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
@@ -114,6 +112,8 @@ pub const TRANSLATIONS_BRANCH: &str = "master";
 pub const VANILLA_LOC_NAME: &str = "vanilla_english.tsv";
 pub const VANILLA_FIXES_NAME: &str = "vanilla_fixes_";
 
+pub mod launch_options;
+
 mod attila;
 mod empire;
 mod napoleon;
@@ -131,42 +131,23 @@ mod warhammer_3;
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_launch_options(app_ui: &AppUI, game: &GameInfo, game_path: &Path, data_path: &Path, folder_list: &mut String) -> Result<()> {
-    let actions_ui = app_ui.actions_ui();
+/// This function generates the reserved pack for the provided launch options, if any of them needs it.
+///
+/// The pack paths are the paths of the packs in the load order, in the order they're going to be loaded.
+pub fn prepare_launch_options(launch_options: &LaunchOptions, game: &GameInfo, game_path: &Path, data_path: &Path, pack_paths: &[PathBuf], folder_list: &mut String) -> Result<()> {
 
     // We only use the reserved pack if we need to.
-    if (actions_ui.enable_logging_checkbox().is_enabled() && actions_ui.enable_logging_checkbox().is_checked()) ||
-        (actions_ui.enable_skip_intro_checkbox().is_enabled() && actions_ui.enable_skip_intro_checkbox().is_checked()) ||
-        (actions_ui.enable_translations_combobox().is_enabled() && actions_ui.enable_translations_combobox().current_index() != 0) ||
-        (actions_ui.universal_rebalancer_combobox().is_enabled() && actions_ui.universal_rebalancer_combobox().current_index() != 0) ||
-        (actions_ui.unit_multiplier_spinbox().is_enabled() && actions_ui.unit_multiplier_spinbox().value() != 1.00) {
-
-        // We need to use an alternative name for Shogun 2, Rome 2, Attila and Thrones because their load order logic for movie packs seems... either different or broken.
-        let reserved_pack_name = if game.key() == KEY_SHOGUN_2 || game.key() == KEY_ROME_2 || game.key() == KEY_ATTILA || game.key() == KEY_THRONES_OF_BRITANNIA {
-            RESERVED_PACK_NAME_ALTERNATIVE
-        } else {
-            RESERVED_PACK_NAME
-        };
+    let temp_packs_folder = temp_packs_folder(game)?;
+    if let Some(temp_path) = launch_options.reserved_pack_path(game, data_path, &temp_packs_folder, folder_list) {
 
         // If the reserved pack is loaded from a custom folder we need to CLEAR SAID FOLDER before anything else. Otherwise we may end up with old packs messing up stuff.
         if *game.raw_db_version() >= 1 {
-            let temp_packs_folder = temp_packs_folder(&game)?;
             let files = files_from_subdir(&temp_packs_folder, false)?;
             for file in &files {
                 std::fs::remove_file(file)?;
             }
         }
 
-        // Support for add_working_directory seems to be only present in rome 2 and newer games. For older games, we drop the pack into /data.
-        let temp_path = if *game.raw_db_version() >= 1 {
-            let temp_packs_folder = temp_packs_folder(&game)?;
-            let temp_path = temp_packs_folder.join(reserved_pack_name);
-            folder_list.push_str(&format!("add_working_directory \"{}\";\n", temp_packs_folder.to_string_lossy()));
-            temp_path
-        } else {
-            data_path.join(reserved_pack_name)
-        };
-
         // Generate the reserved pack.
         //
         // Note: It has to be a movie pack because otherwise we cannot overwrite the intro files in older games.
@@ -176,33 +157,29 @@ pub unsafe fn prepare_launch_options(app_ui: &AppUI, game: &GameInfo, game_path:
 
         // These are often used for editing tables. We cache them here instead of remaking them in every launch option.
         let mut vanilla_pack = Pack::read_and_merge_ca_packs(game, game_path)?;
-        let paths = (0..app_ui.pack_list_ui().model().row_count_0a())
-            .map(|index| PathBuf::from(app_ui.pack_list_ui().model().item_2a(index, 2).text().to_std_string()))
-            .collect::<Vec<_>>();
-
-        let mut modded_pack = if !paths.is_empty() {
-            Pack::read_and_merge(&paths, true, false, true)?
+        let mut modded_pack = if !pack_paths.is_empty() {
+            Pack::read_and_merge(pack_paths, true, false, true)?
         } else {
             Pack::default()
         };
 
         // Skip videos.
-        prepare_skip_intro_videos(app_ui, &game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
+        prepare_skip_intro_videos(launch_options, game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
 
         // Logging.
-        prepare_script_logging(app_ui, &game, &mut reserved_pack)?;
+        prepare_script_logging(launch_options, game, &mut reserved_pack)?;
 
         // Trait limit removal.
-        prepare_trait_limit_removal(app_ui, &game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
+        prepare_trait_limit_removal(launch_options, game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
 
         // Translations.
-        prepare_translations(app_ui, &game, &mut reserved_pack)?;
+        prepare_translations(launch_options, game, &mut reserved_pack, pack_paths)?;
 
         // Unit multiplier.
-        prepare_unit_multiplier(app_ui, &game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
+        prepare_unit_multiplier(launch_options, game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack)?;
 
         // Universal rebalancer.
-        prepare_universal_rebalancer(app_ui, &game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack, &paths)?;
+        prepare_universal_rebalancer(launch_options, game, &mut reserved_pack, &mut vanilla_pack, &mut modded_pack, pack_paths)?;
 
         let mut encode_data = EncodeableExtraData::default();
         encode_data.set_nullify_dates(true);
@@ -211,11 +188,11 @@ pub unsafe fn prepare_launch_options(app_ui: &AppUI, game: &GameInfo, game_path:
         //
         // Except for napoleon and empire. Those two seem to crash if a movie pack has mod dependencies.
         if game.key() != KEY_EMPIRE && game.key() != KEY_NAPOLEON {
-            let pack_names = paths.iter().map(|path| (true, path.file_name().unwrap().to_string_lossy().to_string())).collect::<Vec<_>>();
+            let pack_names = pack_paths.iter().map(|path| (true, path.file_name().unwrap().to_string_lossy().to_string())).collect::<Vec<_>>();
             reserved_pack.set_dependencies(pack_names);
         }
 
-        reserved_pack.save(Some(&temp_path), &game, &Some(encode_data))?;
+        reserved_pack.save(Some(&temp_path), game, &Some(encode_data))?;
     }
 
     Ok(())
@@ -446,7 +423,7 @@ pub unsafe fn setup_actions(app_ui: &AppUI, game: &GameInfo, game_path: &Path) {
 
                 let mut packs_for_rebalancer = load_order.packs().iter()
                     .filter_map(|(key, pack)| {
-                        if !pack.files_by_type_and_paths(&[FileType::DB], &[ContainerPath::Folder("db/land_units_tables/".to_owned())], true).is_empty() {
+                        if pack.files().iter().any(|file| *file.file_type() == FileType::DB && file.path().to_lowercase().starts_with("db/land_units_tables/")) {
                             Some(key)
                         } else {
                             None
@@ -487,16 +464,17 @@ pub unsafe fn setup_actions(app_ui: &AppUI, game: &GameInfo, game_path: &Path) {
     app_ui.actions_ui().open_game_content_folder().block_signals(false);
 }
 
-pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
+pub fn prepare_unit_multiplier(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
     match *SCHEMA.read().unwrap() {
         Some(ref schema) => {
-            if app_ui.actions_ui().unit_multiplier_spinbox().is_enabled() && app_ui.actions_ui().unit_multiplier_spinbox().value() != 1.00 {
+            let unit_multiplier = *launch_options.unit_multiplier();
+            if unit_multiplier != 1.00 {
                 match game.key() {
                     KEY_PHARAOH_DYNASTIES |
                     KEY_PHARAOH => Ok(()),
-                    KEY_WARHAMMER_3 => warhammer_3::prepare_unit_multiplier(app_ui, game, reserved_pack, vanilla_pack, modded_pack, schema),
+                    KEY_WARHAMMER_3 => warhammer_3::prepare_unit_multiplier(game, reserved_pack, vanilla_pack, modded_pack, schema, unit_multiplier),
                     KEY_TROY => Ok(()),
-                    KEY_THREE_KINGDOMS => three_kingdoms::prepare_unit_multiplier(app_ui, game, reserved_pack, vanilla_pack, modded_pack, schema),
+                    KEY_THREE_KINGDOMS => three_kingdoms::prepare_unit_multiplier(game, reserved_pack, vanilla_pack, modded_pack, schema, unit_multiplier),
                     KEY_WARHAMMER_2 |
                     KEY_WARHAMMER |
                     KEY_THRONES_OF_BRITANNIA |
@@ -515,8 +493,8 @@ pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_
     }
 }
 
-pub unsafe fn prepare_script_logging(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack) -> Result<()> {
-    if app_ui.actions_ui().enable_logging_checkbox().is_enabled() && app_ui.actions_ui().enable_logging_checkbox().is_checked() {
+pub fn prepare_script_logging(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack) -> Result<()> {
+    if *launch_options.enable_logging() {
         match game.key() {
             KEY_PHARAOH | KEY_PHARAOH_DYNASTIES => pharaoh::prepare_script_logging(reserved_pack),
             KEY_WARHAMMER_3 => warhammer_3::prepare_script_logging(reserved_pack),
@@ -537,8 +515,8 @@ pub unsafe fn prepare_script_logging(app_ui: &AppUI, game: &GameInfo, reserved_p
     }
 }
 
-pub unsafe fn prepare_skip_intro_videos(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
-    if app_ui.actions_ui().enable_skip_intro_checkbox().is_enabled() && app_ui.actions_ui().enable_skip_intro_checkbox().is_checked() {
+pub fn prepare_skip_intro_videos(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
+    if *launch_options.enable_skip_intros() {
         match game.key() {
             KEY_PHARAOH | KEY_PHARAOH_DYNASTIES => match *SCHEMA.read().unwrap() {
                 Some(ref schema) => pharaoh::prepare_skip_intro_videos(game, reserved_pack, vanilla_pack, modded_pack, schema),
@@ -565,10 +543,10 @@ pub unsafe fn prepare_skip_intro_videos(app_ui: &AppUI, game: &GameInfo, reserve
     }
 }
 
-pub unsafe fn prepare_trait_limit_removal(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
+pub fn prepare_trait_limit_removal(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack) -> Result<()> {
     match *SCHEMA.read().unwrap() {
         Some(ref schema) => {
-            if app_ui.actions_ui().remove_trait_limit_checkbox().is_enabled() && app_ui.actions_ui().remove_trait_limit_checkbox().is_checked() {
+            if *launch_options.remove_trait_limit() {
                 match game.key() {
                     KEY_PHARAOH | KEY_PHARAOH_DYNASTIES => Ok(()),
                     KEY_WARHAMMER_3 => warhammer_3::prepare_trait_limit_removal(game, reserved_pack, vanilla_pack, modded_pack, schema),
@@ -592,21 +570,51 @@ pub unsafe fn prepare_trait_limit_removal(app_ui: &AppUI, game: &GameInfo, reser
     }
 }
 
+/// This function checks if the translations repo needs updating, and updates it if so.
+///
+/// It blocks until the update is done, so it's meant for launches without UI.
+pub fn update_translations_repo() -> Result<()> {
+    let local_path = translations_remote_path()?;
+    let git_integration = GitIntegration::new(&local_path, TRANSLATIONS_REPO, TRANSLATIONS_BRANCH, TRANSLATIONS_REMOTE);
+    match git_integration.check_update()? {
+        GitResponse::NewUpdate |
+        GitResponse::NoLocalFiles |
+        GitResponse::Diverged => git_integration.update_repo().map_err(From::from),
+        GitResponse::NoUpdate => Ok(()),
+    }
+}
+
+/// This function returns the paths where translations are searched for.
+///
+/// Local has priority over remote, so it goes first.
+pub fn translation_paths() -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Ok(path) = translations_local_path() {
+        paths.push(path);
+    }
+
+    if let Ok(path) = translations_remote_path() {
+        paths.push(path);
+    }
+
+    paths
+}
+
 /// All total war games use the same translation system.
 ///
 /// The only particularity is that all games before warhammer 1 need to merge all translations into a localisation.loc file.
-pub unsafe fn prepare_translations(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack) -> Result<()> {
+///
+/// This doesn't update the translations repo. That has to be done before calling this.
+pub fn prepare_translations(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack, pack_paths: &[PathBuf]) -> Result<()> {
 
     // Translation process:
-    // - Pull new translations from the repo.
-    // - Get language from UI.
+    // - Get language from the launch options.
     // - Get all the paths for available translations.
     // - Get all the packs we need to translate, in z-a order, so the last one has priority.
     // - Make an empty loc to put the translations into.
     // - Apply the fixes file, if found.
     // - For each Pack:
-    //   - Check for translations in the local folder.
-    //   - If not found, check for translations in the remote folder.
+    //   - Check for translations in the local and remote folders, using the local ones for the keys they contain.
     //   - If found in any folder, apply them, or use the english value if there's no translation.
     //   - If none are found, just add the loc to the end of the translated loc.
     //
@@ -615,50 +623,11 @@ pub unsafe fn prepare_translations(app_ui: &AppUI, game: &GameInfo, reserved_pac
     //   - If it's not an old game, check what lines got optimized and re-add them, but from the vanilla translation, so they overwrite any mod using them.
 
     // TODO: Troy has a weird translation system. Check that it works, and check pharaoh too.
-    if app_ui.actions_ui().enable_translations_combobox().is_enabled() && app_ui.actions_ui().enable_translations_combobox().current_index() != 0 {
-
-        // Check if the repo needs updating, and update it if so.
-        let receiver = CENTRAL_COMMAND.send_network(Command::CheckTranslationsUpdates);
-        let response_thread = CENTRAL_COMMAND.recv_try(&receiver);
-        match response_thread {
-            Response::APIResponseGit(ref response) => {
-                match response {
-                    GitResponse::NewUpdate |
-                    GitResponse::NoLocalFiles |
-                    GitResponse::Diverged => {
-                        let receiver = CENTRAL_COMMAND.send_background(Command::UpdateTranslations);
-                        let response_thread = CENTRAL_COMMAND.recv_try(&receiver);
-
-                        // Show the error, but continue anyway.
-                        if let Response::Error(error) = response_thread {
-                            show_dialog(app_ui.main_window(), tre("translation_download_error", &[&error.to_string()]), false);
-                        }
-                    }
-                    GitResponse::NoUpdate => {}
-                }
-            }
-
-            Response::Error(error) => {
-                show_dialog(app_ui.main_window(), tre("translation_download_error", &[&error.to_string()]), false);
-            }
-            _ => panic!("{THREADS_COMMUNICATION_ERROR}{response_thread:?}"),
-        }
-
-        // Get the paths. Local has priority over remote, so it goes first.
-        let mut paths = vec![];
-        if let Ok(path) = translations_local_path() {
-            paths.push(path);
-        }
-
-        if let Ok(path) = translations_remote_path() {
-            paths.push(path);
-        }
+    if let Some(language) = launch_options.translations_language() {
 
+        let paths = translation_paths();
         if !paths.is_empty() {
-            let language = app_ui.actions_ui().enable_translations_combobox().current_text().to_std_string();
-            let mut pack_paths = (0..app_ui.pack_list_ui().model().row_count_0a())
-                .map(|index| PathBuf::from(app_ui.pack_list_ui().model().item_2a(index, 2).text().to_std_string()))
-                .collect::<Vec<_>>();
+            let mut pack_paths = pack_paths.to_vec();
 
             // Reversed so we just get the higher priority stuff at the end, overwriting the rest.
             pack_paths.sort();
@@ -681,7 +650,7 @@ pub unsafe fn prepare_translations(app_ui: &AppUI, game: &GameInfo, reserved_pac
                 if let Some(ref pack_name) = pack_path.file_name().map(|name| name.to_string_lossy().to_string()) {
                     let mut translation_found = false;
 
-                    if let Ok(tr) = PackTranslation::load(&paths, pack_name, game.key(), &language) {
+                    if let Ok(tr) = load_pack_translation(&paths, pack_name, game.key(), language) {
                         for tr in tr.translations().values() {
 
                             // Only add entries for values we actually have translated and up to date.
@@ -902,13 +871,13 @@ pub fn rename_file_name_to_low_priority(file: &mut RFile) {
     file.set_path_in_container_raw(&path.join("/"));
 }
 
-pub unsafe fn prepare_universal_rebalancer(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, mod_paths: &[PathBuf]) -> Result<()> {
+pub fn prepare_universal_rebalancer(launch_options: &LaunchOptions, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, mod_paths: &[PathBuf]) -> Result<()> {
     match *SCHEMA.read().unwrap() {
         Some(ref schema) => {
-            if app_ui.actions_ui().universal_rebalancer_combobox().is_enabled() && app_ui.actions_ui().universal_rebalancer_combobox().current_index() != 0 {
+            if let Some(base_mod_id) = launch_options.universal_rebalancer_base_mod() {
                 match game.key() {
                     KEY_PHARAOH | KEY_PHARAOH_DYNASTIES => Ok(()),
-                    KEY_WARHAMMER_3 => warhammer_3::prepare_universal_rebalancer(app_ui, game, reserved_pack, vanilla_pack, modded_pack, schema, mod_paths),
+                    KEY_WARHAMMER_3 => warhammer_3::prepare_universal_rebalancer(game, reserved_pack, vanilla_pack, modded_pack, schema, base_mod_id, mod_paths),
                     KEY_TROY |
                     KEY_THREE_KINGDOMS |
                     KEY_WARHAMMER_2 |
@@ -45,14 +45,14 @@ const NON_REPLACEABLE_VIDEOS: [&str; 13] = [
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
     let file = RFile::new_from_vec("why not working?!!".as_bytes(), FileType::Text, 0, SCRIPT_DEBUG_ACTIVATOR_PATH);
     reserved_pack.files_mut().insert(SCRIPT_DEBUG_ACTIVATOR_PATH.to_string(), file);
 
     Ok(())
 }
 
-pub unsafe fn prepare_skip_intro_videos(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
+pub fn prepare_skip_intro_videos(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
     let mut videos = vanilla_pack.files_by_path(&ContainerPath::Folder("db/videos_tables/".to_string()), true)
         .into_iter()
         .cloned()
@@ -24,7 +24,7 @@ const INTRO_MOVIE_PATHS_BY_GAME: [&str; 3] = [
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
     for path in INTRO_MOVIE_PATHS_BY_GAME {
         let file = RFile::new_from_vec(&EMPTY_BIK, FileType::Video, 0, path);
         reserved_pack.files_mut().insert(path.to_string(), file);
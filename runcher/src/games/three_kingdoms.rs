@@ -17,7 +17,6 @@ use rpfm_lib::schema::Schema;
 use rpfm_lib::files::{Container, ContainerPath, DecodeableExtraData, EncodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded, table::DecodedData};
 use rpfm_lib::games::GameInfo;
 
-use crate::app_ui::AppUI;
 use crate::games::{EMPTY_CA_VP8, rename_file_name_to_low_priority};
 
 const INTRO_MOVIE_PATHS_BY_GAME: [&str; 2] = [
@@ -29,9 +28,7 @@ const INTRO_MOVIE_PATHS_BY_GAME: [&str; 2] = [
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
-    let unit_multiplier = app_ui.actions_ui().unit_multiplier_spinbox().value();
-
+pub fn prepare_unit_multiplier(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema, unit_multiplier: f64) -> Result<()> {
     let mut kv_key_buildings = vanilla_pack.files_by_path(&ContainerPath::Folder("db/_kv_key_buildings_tables/".to_string()), true)
         .into_iter()
         .cloned()
@@ -285,7 +282,7 @@ pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_
     Ok(())
 }
 
-pub unsafe fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
     for path in INTRO_MOVIE_PATHS_BY_GAME {
         let file = RFile::new_from_vec(&EMPTY_CA_VP8, FileType::Video, 0, path);
         reserved_pack.files_mut().insert(path.to_string(), file);
@@ -34,14 +34,14 @@ const INTRO_MOVIE_KEYS: [&str; 3] = [
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
     let file = RFile::new_from_vec("why not working?!!".as_bytes(), FileType::Text, 0, SCRIPT_DEBUG_ACTIVATOR_PATH);
     reserved_pack.files_mut().insert(SCRIPT_DEBUG_ACTIVATOR_PATH.to_string(), file);
 
     Ok(())
 }
 
-pub unsafe fn prepare_skip_intro_videos(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
+pub fn prepare_skip_intro_videos(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
 
     // Just replacing the files causes the game to crash, so we're going to remove them from the videos table.
     //for path in INTRO_MOVIE_PATHS_BY_GAME {
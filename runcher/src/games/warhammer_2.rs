@@ -26,14 +26,14 @@ const INTRO_MOVIE_PATHS_BY_GAME: [&str; 3] = [
 //                             Implementations
 //-------------------------------------------------------------------------------//
 
-pub unsafe fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
     let file = RFile::new_from_vec("why not working?!!".as_bytes(), FileType::Text, 0, SCRIPT_DEBUG_ACTIVATOR_PATH);
     reserved_pack.files_mut().insert(SCRIPT_DEBUG_ACTIVATOR_PATH.to_string(), file);
 
     Ok(())
 }
 
-pub unsafe fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
     for path in INTRO_MOVIE_PATHS_BY_GAME {
         let file = RFile::new_from_vec(&EMPTY_CA_VP8, FileType::Video, 0, path);
         reserved_pack.files_mut().insert(path.to_string(), file);
@@ -19,7 +19,6 @@ use rpfm_lib::schema::Schema;
 use rpfm_lib::files::{Container, ContainerPath, db::DB, DecodeableExtraData, EncodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded, table::DecodedData};
 use rpfm_lib::games::GameInfo;
 
-use crate::app_ui::AppUI;
 use crate::games::{EMPTY_CA_VP8, rename_file_name_to_low_priority};
 
 const SCRIPT_DEBUG_ACTIVATOR_PATH: &str = "script/enable_console_logging";
@@ -100,7 +99,7 @@ pub struct UniversalRebalancerLandUnit {
 //-------------------------------------------------------------------------------//
 
 
-pub unsafe fn prepare_trait_limit_removal(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
+pub fn prepare_trait_limit_removal(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
     let mut campaign_variables = vanilla_pack.files_by_path(&ContainerPath::Folder("db/campaign_variables_tables/".to_string()), true)
         .into_iter()
         .cloned()
@@ -150,9 +149,7 @@ pub unsafe fn prepare_trait_limit_removal(game: &GameInfo, reserved_pack: &mut P
     Ok(())
 }
 
-pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema) -> Result<()> {
-    let unit_multiplier = app_ui.actions_ui().unit_multiplier_spinbox().value();
-
+pub fn prepare_unit_multiplier(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema, unit_multiplier: f64) -> Result<()> {
     let mut kv_rules = vanilla_pack.files_by_path(&ContainerPath::Folder("db/_kv_rules_tables/".to_string()), true)
         .into_iter()
         .cloned()
@@ -675,14 +672,14 @@ pub unsafe fn prepare_unit_multiplier(app_ui: &AppUI, game: &GameInfo, reserved_
     Ok(())
 }
 
-pub unsafe fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_script_logging(reserved_pack: &mut Pack) -> Result<()> {
     let file = RFile::new_from_vec("why not working?!!".as_bytes(), FileType::Text, 0, SCRIPT_DEBUG_ACTIVATOR_PATH);
     reserved_pack.files_mut().insert(SCRIPT_DEBUG_ACTIVATOR_PATH.to_string(), file);
 
     Ok(())
 }
 
-pub unsafe fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
+pub fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()> {
     for path in INTRO_MOVIE_PATHS_BY_GAME {
         let file = RFile::new_from_vec(&EMPTY_CA_VP8, FileType::Video, 0, path);
         reserved_pack.files_mut().insert(path.to_string(), file);
@@ -691,17 +688,9 @@ pub unsafe fn prepare_skip_intro_videos(reserved_pack: &mut Pack) -> Result<()>
     Ok(())
 }
 
-pub unsafe fn prepare_universal_rebalancer(app_ui: &AppUI, game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema, mod_paths: &[PathBuf]) -> Result<()> {
-    let base_mod_id = app_ui.actions_ui().universal_rebalancer_combobox().current_text().to_std_string();
-    let base_pack_path = (0..app_ui.pack_list_ui().model().row_count_0a())
-        .find_map(|index| {
-            let path = app_ui.pack_list_ui().model().item_2a(index, 2).text().to_std_string();
-            if path.ends_with(&base_mod_id) {
-                Some(path)
-            } else {
-                None
-            }
-        });
+pub fn prepare_universal_rebalancer(game: &GameInfo, reserved_pack: &mut Pack, vanilla_pack: &mut Pack, modded_pack: &mut Pack, schema: &Schema, base_mod_id: &str, mod_paths: &[PathBuf]) -> Result<()> {
+    let base_pack_path = mod_paths.iter()
+        .find(|path| path.to_string_lossy().ends_with(base_mod_id));
 
     match base_pack_path {
         Some(base_pack_path) => {
@@ -711,7 +700,7 @@ pub unsafe fn prepare_universal_rebalancer(app_ui: &AppUI, game: &GameInfo, rese
             dec_extra_data.set_schema(Some(schema));
             let dec_extra_data = Some(dec_extra_data);
 
-            let base_pack = Pack::read_and_merge(&[PathBuf::from(base_pack_path)], true, false, false)?;
+            let base_pack = Pack::read_and_merge(&[base_pack_path.to_path_buf()], true, false, false)?;
             let mut land_units_base = base_pack.files_by_path(&ContainerPath::Folder("db/land_units_tables/".to_string()), true)
                 .into_iter()
                 .cloned()
@@ -35,10 +35,12 @@ use qt_gui::{QPalette, q_palette::{ColorRole, ColorGroup}};
 use qt_core::QCoreApplication;
 use qt_core::QString;
 
+use clap::Parser;
 use lazy_static::lazy_static;
 use regex::Regex;
 
 use std::path::PathBuf;
+use std::process::exit;
 use std::sync::{Arc, atomic::AtomicPtr, RwLock};
 use std::thread;
 
@@ -52,7 +54,9 @@ use rpfm_ui_common::settings::*;
 use rpfm_ui_common::utils::*;
 
 use crate::app_ui::AppUI;
+use crate::cli::Cli;
 use crate::communications::*;
+use crate::mod_manager::pack_index::PackIndexCache;
 use crate::settings_ui::*;
 
 mod actions_ui;
@@ -81,6 +85,9 @@ lazy_static! {
     /// Currently loaded schema.
     static ref SCHEMA: Arc<RwLock<Option<Schema>>> = Arc::new(RwLock::new(None));
 
+    /// Index of the packs we've seen, so we don't need to open them again unless they change.
+    static ref PACK_INDEX: Arc<RwLock<PackIndexCache>> = Arc::new(RwLock::new(PackIndexCache::load().unwrap_or_default()));
+
     /// Sentry client guard, so we can reuse it later on and keep it in scope for the entire duration of the program.
     static ref SENTRY_GUARD: Arc<RwLock<ClientInitGuard>> = Arc::new(RwLock::new(Logger::init(&{
         init_config_path().expect("Error while trying to initialize config path. We're fucked.");
@@ -185,6 +192,19 @@ fn main() {
         info!("Sentry Logging support disabled. Starting...");
     }
 
+    // If we received a command through args, execute it without the UI and exit.
+    let cli = Cli::parse();
+    if let Some(result) = cli.run_subcommand() {
+        match result {
+            Ok(_) => exit(0),
+            Err(error) => {
+                error!("{}", error);
+                eprintln!("{}", error);
+                exit(1)
+            }
+        }
+    }
+
     // Create the background and network threads, where all the magic will happen.
     info!("Initializing threads...");
     let bac_handle = thread::spawn(|| { background_thread::background_loop(); });
@@ -116,6 +116,11 @@ pub struct ModListUI {
 
     upload_to_workshop: QPtr<QAction>,
     download_from_workshop: QPtr<QAction>,
+    unsubscribe_from_workshop: QPtr<QAction>,
+    resubscribe_to_workshop: QPtr<QAction>,
+    remove_orphaned_content: QPtr<QAction>,
+
+    edit_local_translation: QPtr<QAction>,
 
     copy_to_secondary: QPtr<QAction>,
     move_to_secondary: QPtr<QAction>,
@@ -177,8 +182,14 @@ impl ModListUI {
 
         let upload_to_workshop = context_menu.add_action_q_string(&qtr("upload_to_workshop"));
         let download_from_workshop = context_menu.add_action_q_string(&qtr("download_from_workshop"));
+        let unsubscribe_from_workshop = context_menu.add_action_q_string(&qtr("unsubscribe_from_workshop"));
+        let resubscribe_to_workshop = context_menu.add_action_q_string(&qtr("resubscribe_to_workshop"));
+        let remove_orphaned_content = context_menu.add_action_q_string(&qtr("remove_orphaned_content"));
         context_menu.insert_separator(&upload_to_workshop);
 
+        let edit_local_translation = context_menu.add_action_q_string(&qtr("edit_local_translation"));
+        context_menu.insert_separator(&edit_local_translation);
+
         let copy_to_secondary = context_menu.add_action_q_string(&qtr("copy_to_secondary"));
         let move_to_secondary = context_menu.add_action_q_string(&qtr("move_to_secondary"));
         context_menu.insert_separator(&copy_to_secondary);
@@ -212,6 +223,11 @@ impl ModListUI {
 
             upload_to_workshop,
             download_from_workshop,
+            unsubscribe_from_workshop,
+            resubscribe_to_workshop,
+            remove_orphaned_content,
+
+            edit_local_translation,
 
             copy_to_secondary,
             move_to_secondary,
@@ -86,9 +86,13 @@ impl ModListUISlots {
 
             view.upload_to_workshop.set_enabled(all_mods && selection.len() == 1);
             view.download_from_workshop.set_enabled(all_mods);
+            view.unsubscribe_from_workshop.set_enabled(all_mods);
+            view.resubscribe_to_workshop.set_enabled(all_mods);
 
             view.copy_to_secondary.set_enabled(all_mods);
             view.move_to_secondary.set_enabled(all_mods);
+
+            view.edit_local_translation.set_enabled(all_mods && selection.len() == 1);
         }));
 
         let open_in_explorer = SlotNoArgs::new(&view.tree_view, clone!(
@@ -0,0 +1,403 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the code to detect conflicts between the packs of a load order.
+
+use anyhow::{anyhow, Result};
+use getset::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use rpfm_lib::files::{DecodeableExtraData, FileType, pack::Pack, RFile, RFileDecoded};
+use rpfm_lib::games::GameInfo;
+use rpfm_lib::schema::Schema;
+
+use crate::{PACK_INDEX, SCHEMA};
+
+use super::game_config::GameConfig;
+use super::load_order::LoadOrder;
+use super::pack_index::update_pack_index;
+
+#[cfg(test)] mod test;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Report of all the files overwritten between the packs of a load order.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct ConflictReport {
+
+    // Conflicting files, sorted by path.
+    files: Vec<FileConflict>,
+
+    // Game path and size and modification date of the load order packs the report was generated from. Not serialized.
+    #[serde(skip)]
+    game_path: PathBuf,
+    #[serde(skip)]
+    stamps: Vec<(String, u64, u64)>,
+}
+
+/// File present in more than one pack of the load order, or in a pack of the load order and in the vanilla packs.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct FileConflict {
+
+    // Path of the file within the packs.
+    path: String,
+
+    // Names of the packs containing the file, vanilla ones included, from higher to lower priority. The first one is the one the game uses.
+    packs: Vec<String>,
+
+    // Names of the vanilla packs within the packs containing the file.
+    vanilla_packs: Vec<String>,
+}
+
+/// Report of all the DB rows and loc keys defined by more than one pack of a load order.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct DataConflictReport {
+
+    // Conflicting rows, sorted by table and key.
+    rows: Vec<DataConflict>,
+}
+
+/// DB row or loc entry defined with different values by more than one pack of the load order.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct DataConflict {
+
+    // Name of the table of the row. Loc entries use LOC_TABLE_NAME.
+    table: String,
+
+    // Key of the row. If the table has more than one key column, their values are joined by `|`.
+    key: String,
+
+    // Definitions of the row, from higher to lower priority. The first one is the one the game uses.
+    entries: Vec<DataConflictEntry>,
+}
+
+/// Definition of a row in a specific table file.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct DataConflictEntry {
+
+    // Name of the pack containing the table file.
+    pack: String,
+
+    // Path of the table file within the pack.
+    path: String,
+
+    // Values of all the columns of the row.
+    values: Vec<String>,
+}
+
+/// Table file of the load order, decoded for the data conflict report.
+#[derive(Clone, Debug, Default)]
+struct DataConflictTableFile {
+
+    // Name of the table of the file. Loc files use LOC_TABLE_NAME.
+    table: String,
+
+    // Name of the pack containing the file.
+    pack: String,
+
+    // Path of the file within the pack.
+    path: String,
+
+    // Positions of the key columns of the table.
+    key_columns: Vec<usize>,
+
+    // Values of all the columns of each row.
+    rows: Vec<Vec<String>>,
+}
+
+/// Name used as table for loc entries in the data conflict reports.
+pub const LOC_TABLE_NAME: &str = "loc";
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl ConflictReport {
+
+    /// This function generates the conflict report for the provided load order.
+    ///
+    /// Priority is the one the game uses, as returned by [LoadOrder::packs_by_priority]. Paths are compared in lowercase, like the game does.
+    /// Requires the packs of the load order to be loaded.
+    pub fn generate(game_config: &GameConfig, game: &GameInfo, game_path: &Path, load_order: &LoadOrder) -> Result<Self> {
+        let vanilla_paths = game.ca_packs_paths(game_path)?;
+        update_pack_index(&vanilla_paths, true);
+
+        let pack_index = PACK_INDEX.read().unwrap();
+
+        let vanilla_names = vanilla_paths.iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect::<HashSet<_>>();
+
+        let packs = load_order.packs_by_priority(game_config, &vanilla_paths, &pack_index)
+            .into_iter()
+            .map(|(pack_name, pack)| {
+                let vanilla = vanilla_names.contains(&pack_name);
+                (pack_name, vanilla, pack.files().iter().map(|file| file.path().as_str()).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+
+        let mut report = Self::from_packs(&packs);
+        report.game_path = game_path.to_path_buf();
+        report.stamps = Self::stamps(load_order);
+        Ok(report)
+    }
+
+    /// This function builds the report from the provided packs, sorted from higher to lower priority, with a flag marking the vanilla ones and the paths of their files.
+    ///
+    /// Only files in at least one pack of the load order are reported.
+    fn from_packs(packs: &[(String, bool, Vec<&str>)]) -> Self {
+        let load_order_paths = packs.iter()
+            .filter(|(_, vanilla, _)| !vanilla)
+            .flat_map(|(_, _, paths)| paths.iter().map(|path| path.to_lowercase()))
+            .collect::<HashSet<_>>();
+
+        let mut conflicts: HashMap<String, FileConflict> = HashMap::new();
+        for (pack_name, vanilla, paths) in packs {
+            for path in paths {
+                let path_lower = path.to_lowercase();
+                if !load_order_paths.contains(&path_lower) {
+                    continue;
+                }
+
+                let conflict = conflicts.entry(path_lower).or_insert_with(|| FileConflict {
+                    path: path.to_string(),
+                    ..Default::default()
+                });
+
+                conflict.packs.push(pack_name.to_owned());
+                if *vanilla {
+                    conflict.vanilla_packs.push(pack_name.to_owned());
+                }
+            }
+        }
+
+        let mut files = conflicts.into_values()
+            .filter(|conflict| conflict.packs.len() > 1)
+            .collect::<Vec<_>>();
+
+        files.sort_by(|file_a, file_b| file_a.path.cmp(&file_b.path));
+
+        Self {
+            files,
+            ..Default::default()
+        }
+    }
+
+    /// This function returns the size and modification date of the packs of the load order, to know when the report needs to be generated again.
+    fn stamps(load_order: &LoadOrder) -> Vec<(String, u64, u64)> {
+        load_order.movies().iter()
+            .chain(load_order.mods().iter())
+            .filter_map(|mod_id| load_order.packs().get(mod_id).map(|pack| (mod_id.to_owned(), *pack.size(), *pack.modified())))
+            .collect()
+    }
+
+    /// This function returns if the report was generated from the provided game path and load order, with the packs unchanged since then.
+    ///
+    /// Vanilla packs are not checked, as they only change with game updates.
+    pub fn is_up_to_date(&self, game_path: &Path, load_order: &LoadOrder) -> bool {
+        self.game_path == game_path && self.stamps == Self::stamps(load_order)
+    }
+
+    /// This function returns the conflicts the provided pack wins.
+    pub fn conflicts_won_by(&self, pack_name: &str) -> Vec<&FileConflict> {
+        self.files.iter()
+            .filter(|file| file.winner() == Some(pack_name))
+            .collect()
+    }
+
+    /// This function returns the conflicts the provided pack loses against other packs.
+    pub fn conflicts_lost_by(&self, pack_name: &str) -> Vec<&FileConflict> {
+        self.files.iter()
+            .filter(|file| file.losers().iter().any(|pack| pack == pack_name))
+            .collect()
+    }
+}
+
+impl FileConflict {
+
+    /// This function returns the name of the pack whose file the game ends up using.
+    pub fn winner(&self) -> Option<&str> {
+        self.packs.first().map(|pack| pack.as_str())
+    }
+
+    /// This function returns the names of the packs whose file is overwritten, from higher to lower priority.
+    pub fn losers(&self) -> &[String] {
+        self.packs.get(1..).unwrap_or_default()
+    }
+}
+
+impl DataConflictReport {
+
+    /// This function generates the DB and loc conflict report for the provided load order.
+    ///
+    /// The game merges table files by their file name, not by the pack they're in, and the first one defining a key wins.
+    /// So we follow that order, after discarding the table files overwritten by other packs. Vanilla packs are ignored,
+    /// as overwriting vanilla rows is what most mods do. Rows defined with the same values by all the packs are ignored too.
+    ///
+    /// Requires the packs of the load order to be loaded. Only the packs with tables are opened.
+    pub fn generate(game_config: &GameConfig, load_order: &LoadOrder, schema: &Schema) -> Result<Self> {
+
+        // Table files by path, only keeping the one from the highest priority pack. Paths are compared in lowercase, like the game does.
+        let mut files: HashMap<String, (String, RFile)> = HashMap::new();
+        for mod_id in load_order.movies().iter().chain(load_order.mods().iter()) {
+            let has_tables = match load_order.packs().get(mod_id) {
+                Some(pack) => pack.files().iter().any(|file| *file.file_type() == FileType::DB || *file.file_type() == FileType::Loc),
+                None => false,
+            };
+
+            if !has_tables {
+                continue;
+            }
+
+            let path = match game_config.mods().get(mod_id).and_then(|modd| modd.paths().first()) {
+                Some(path) => path,
+                None => continue,
+            };
+
+            let pack_name = match path.file_name() {
+                Some(pack_name) => pack_name.to_string_lossy().to_string(),
+                None => continue,
+            };
+
+            let pack = Pack::read_and_merge(&[path.to_path_buf()], true, false, false)?;
+            for file in pack.files_by_type(&[FileType::DB, FileType::Loc]) {
+                files.entry(file.path_in_container_raw().to_lowercase()).or_insert_with(|| (pack_name.to_owned(), file.clone()));
+            }
+        }
+
+        let mut dec_extra_data = DecodeableExtraData::default();
+        dec_extra_data.set_schema(Some(schema));
+        let dec_extra_data = Some(dec_extra_data);
+
+        let mut table_files = vec![];
+        for (path_lower, (pack_name, mut file)) in files {
+            let table = match file.file_type() {
+                FileType::Loc => LOC_TABLE_NAME.to_owned(),
+                _ => match path_lower.split('/').nth(1) {
+                    Some(table_name) => table_name.to_owned(),
+                    None => continue,
+                },
+            };
+
+            let path = file.path_in_container_raw().to_owned();
+            let (key_columns, data) = match file.decode(&dec_extra_data, false, true) {
+                Ok(Some(RFileDecoded::DB(table))) => (table.definition().key_column_positions(), table.data().to_vec()),
+                Ok(Some(RFileDecoded::Loc(loc))) => (vec![0], loc.data().to_vec()),
+
+                // Tables we cannot decode with the current schema cannot be checked.
+                _ => continue,
+            };
+
+            table_files.push(DataConflictTableFile {
+                table,
+                pack: pack_name,
+                path,
+                key_columns,
+                rows: data.iter()
+                    .map(|row| row.iter().map(|value| value.data_to_string().to_string()).collect())
+                    .collect(),
+            });
+        }
+
+        Ok(Self::from_table_files(table_files))
+    }
+
+    /// This function builds the report from the table files of the load order, already deduplicated by path.
+    ///
+    /// Files of the same table are processed sorted by path, so the first one defining a key is the one the game uses.
+    fn from_table_files(table_files: Vec<DataConflictTableFile>) -> Self {
+        let mut tables: BTreeMap<String, Vec<DataConflictTableFile>> = BTreeMap::new();
+        for file in table_files {
+            tables.entry(file.table.to_owned()).or_default().push(file);
+        }
+
+        let mut rows = vec![];
+        for (table_name, mut files) in tables {
+            files.sort_by(|file_a, file_b| file_a.path.cmp(&file_b.path));
+
+            // Keep the order in which each key was first found, so the report is stable.
+            let mut entries_by_key: HashMap<String, Vec<DataConflictEntry>> = HashMap::new();
+            let mut keys = vec![];
+
+            for file in &files {
+                if file.key_columns.is_empty() {
+                    continue;
+                }
+
+                for row in &file.rows {
+                    let key = file.key_columns.iter()
+                        .filter_map(|column| row.get(*column))
+                        .map(|value| value.to_owned())
+                        .collect::<Vec<_>>()
+                        .join("|");
+
+                    let entries = entries_by_key.entry(key.to_owned()).or_insert_with(|| {
+                        keys.push(key);
+                        vec![]
+                    });
+
+                    entries.push(DataConflictEntry {
+                        pack: file.pack.to_owned(),
+                        path: file.path.to_owned(),
+                        values: row.to_vec(),
+                    });
+                }
+            }
+
+            for key in keys {
+                if let Some(entries) = entries_by_key.remove(&key) {
+                    let multiple_packs = entries.iter().any(|entry| entry.pack != entries[0].pack);
+                    let different_values = entries.iter().any(|entry| entry.values != entries[0].values);
+
+                    if multiple_packs && different_values {
+                        rows.push(DataConflict {
+                            table: table_name.to_owned(),
+                            key,
+                            entries,
+                        });
+                    }
+                }
+            }
+        }
+
+        rows.sort_by(|row_a, row_b| row_a.table.cmp(&row_b.table).then_with(|| row_a.key.cmp(&row_b.key)));
+
+        Self {
+            rows
+        }
+    }
+
+    /// This function generates the report using the currently loaded schema.
+    pub fn generate_with_loaded_schema(game_config: &GameConfig, load_order: &LoadOrder) -> Result<Self> {
+        match *SCHEMA.read().unwrap() {
+            Some(ref schema) => Self::generate(game_config, load_order, schema),
+            None => Err(anyhow!("There is no schema loaded for this game. Make sure the schemas are downloaded.")),
+        }
+    }
+}
+
+impl DataConflict {
+
+    /// This function returns the definition of the row the game ends up using.
+    pub fn winner(&self) -> Option<&DataConflictEntry> {
+        self.entries.first()
+    }
+}
@@ -0,0 +1,92 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the conflict reports.
+
+use super::*;
+
+fn pack<'a>(name: &str, vanilla: bool, paths: &[&'a str]) -> (String, bool, Vec<&'a str>) {
+    (name.to_owned(), vanilla, paths.to_vec())
+}
+
+#[test]
+fn test_file_conflicts_from_packs() {
+    let packs = vec![
+        pack("movies.pack", true, &["ui/loading.png"]),
+        pack("top_mod.pack", false, &["db/units_tables/top", "UI/Loading.png", "ui/skins/button.png"]),
+        pack("bottom_mod.pack", false, &["db/units_tables/bottom", "ui/skins/button.png", "ui/unique.png"]),
+        pack("data.pack", true, &["ui/skins/button.png", "ui/vanilla_only.png"]),
+    ];
+
+    let report = ConflictReport::from_packs(&packs);
+    let paths = report.files().iter().map(|file| file.path().as_str()).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["UI/Loading.png", "ui/skins/button.png"]);
+
+    // Paths are compared in lowercase, and vanilla movie packs win over the load order.
+    let loading = &report.files()[0];
+    assert_eq!(loading.winner(), Some("movies.pack"));
+    assert_eq!(loading.losers(), &["top_mod.pack".to_owned()]);
+    assert_eq!(loading.vanilla_packs(), &vec!["movies.pack".to_owned()]);
+
+    // The top of the load order wins over the bottom, and both over the rest of vanilla packs.
+    let button = &report.files()[1];
+    assert_eq!(button.winner(), Some("top_mod.pack"));
+    assert_eq!(button.losers(), &["bottom_mod.pack".to_owned(), "data.pack".to_owned()]);
+
+    assert_eq!(report.conflicts_won_by("top_mod.pack").len(), 1);
+    assert_eq!(report.conflicts_lost_by("top_mod.pack").len(), 1);
+    assert_eq!(report.conflicts_won_by("bottom_mod.pack").len(), 0);
+    assert_eq!(report.conflicts_lost_by("bottom_mod.pack").len(), 1);
+    assert_eq!(report.conflicts_lost_by("data.pack").len(), 1);
+}
+
+fn table_file(table: &str, pack: &str, path: &str, rows: &[&[&str]]) -> DataConflictTableFile {
+    DataConflictTableFile {
+        table: table.to_owned(),
+        pack: pack.to_owned(),
+        path: path.to_owned(),
+        key_columns: vec![0],
+        rows: rows.iter().map(|row| row.iter().map(|value| value.to_string()).collect()).collect(),
+    }
+}
+
+#[test]
+fn test_data_conflicts_from_table_files() {
+    let report = DataConflictReport::from_table_files(vec![
+
+        // The top mod of the load order, whose fragment sorts after the other one.
+        table_file("units_tables", "top_mod.pack", "db/units_tables/zzz_top_mod", &[
+            &["unit_1", "100"],
+            &["unit_2", "50"],
+            &["unit_3", "10"],
+        ]),
+        table_file("units_tables", "bottom_mod.pack", "db/units_tables/aaa_bottom_mod", &[
+            &["unit_1", "200"],
+            &["unit_2", "50"],
+        ]),
+        table_file(LOC_TABLE_NAME, "top_mod.pack", "text/db/top_mod.loc", &[&["units_name_unit_1", "Top"]]),
+        table_file(LOC_TABLE_NAME, "bottom_mod.pack", "text/db/bottom_mod.loc", &[&["units_name_unit_1", "Bottom"]]),
+    ]);
+
+    // Rows with the same values in all packs are not conflicts.
+    let keys = report.rows().iter().map(|row| (row.table().as_str(), row.key().as_str())).collect::<Vec<_>>();
+    assert_eq!(keys, vec![(LOC_TABLE_NAME, "units_name_unit_1"), ("units_tables", "unit_1")]);
+
+    // Table files are merged by path, not by pack priority, so the fragment that sorts first wins.
+    let unit = &report.rows()[1];
+    assert_eq!(unit.winner().unwrap().pack(), "bottom_mod.pack");
+    assert_eq!(unit.winner().unwrap().values(), &vec!["unit_1".to_owned(), "200".to_owned()]);
+    assert_eq!(unit.entries()[1].pack(), "top_mod.pack");
+    assert_eq!(unit.entries()[1].path(), "db/units_tables/zzz_top_mod");
+
+    let loc = &report.rows()[0];
+    assert_eq!(loc.winner().unwrap().pack(), "bottom_mod.pack");
+    assert_eq!(loc.winner().unwrap().values()[1], "Bottom");
+}
@@ -23,14 +23,13 @@ use std::io::{BufReader, BufWriter, Cursor, Read, Write};
 use std::path::Path;
 use std::time::UNIX_EPOCH;
 
-use rpfm_lib::files::pack::Pack;
 use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
 use rpfm_lib::integrations::log::error;
 
 use crate::games::{RESERVED_PACK_NAME, RESERVED_PACK_NAME_ALTERNATIVE};
 use crate::communications::{Command, Response};
-use crate::mod_manager::{load_order::LoadOrder, mods::Mod};
-use crate::{settings_ui::*, CENTRAL_COMMAND};
+use crate::mod_manager::{load_order::LoadOrder, mods::Mod, pack_index::index_pack_types};
+use crate::{settings_ui::*, CENTRAL_COMMAND, PACK_INDEX};
 
 use super::secondary_mods_packs_paths;
 
@@ -174,6 +173,37 @@ impl GameConfig {
         self.categories_order_mut().retain(|x| x != category);
     }
 
+    /// This function removes from the categories any mod that has no path, and adds any new mod to the default category.
+    pub fn update_categories(&mut self) {
+        for mods in self.categories.values_mut() {
+            mods.retain(|mod_id| match self.mods.get(mod_id) {
+                Some(modd) => !modd.paths().is_empty(),
+                None => false,
+            });
+        }
+
+        let mut mods_to_add = vec![];
+        for modd in self.mods.values() {
+            if !modd.paths().is_empty() && self.categories().iter().all(|(_, mods)| !mods.contains(modd.id())) {
+                mods_to_add.push(modd.id().to_owned());
+            }
+        }
+
+        match self.categories_mut().get_mut(DEFAULT_CATEGORY) {
+            Some(mods) => mods.append(&mut mods_to_add),
+            None => { self.categories_mut().insert(DEFAULT_CATEGORY.to_owned(), mods_to_add); },
+        }
+
+        // If we got a default category, make sure it's always at the end.
+        if let Some(cat) = self.categories_order().last() {
+            if cat != DEFAULT_CATEGORY && self.categories().get(DEFAULT_CATEGORY).is_some() {
+                if let Some(mods) = self.categories_mut().remove(DEFAULT_CATEGORY) {
+                    self.categories_mut().insert(DEFAULT_CATEGORY.to_owned(), mods);
+                }
+            }
+        }
+    }
+
     /// NOTE: This returns a channel receiver for the workshop/equivalent service data request.
     /// This is done so the request doesn't hang the entire load process, as it usually takes 2 or 3 seconds to complete.
     pub fn update_mod_list(&mut self, game: &GameInfo, game_path: &Path, load_order: &mut LoadOrder, skip_network_update: bool) -> Result<Option<Receiver<Response>>> {
@@ -200,22 +230,24 @@ impl GameConfig {
                 // These have less priority.
                 if let Ok(ref content_path) = content_path {
                     if let Some(ref paths) = content_paths {
+                        let pack_types = index_pack_types(paths);
                         let (packs, maps): (Vec<_>, Vec<_>) = paths.par_iter()
-                            .partition_map(|path| match Pack::read_and_merge(&[path.to_path_buf()], true, false, false) {
-                                Ok(pack) => Either::Left((path, pack)),
-                                Err(_) => Either::Right(path),
+                            .zip(pack_types.into_par_iter())
+                            .partition_map(|(path, pack_type)| match pack_type {
+                                Some(pack_type) => Either::Left((path, pack_type)),
+                                None => Either::Right(path),
                             });
 
-                        for (path, pack) in packs {
+                        for (path, pack_type) in packs {
                             let pack_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
-                            if pack.pfh_file_type() == PFHFileType::Mod || pack.pfh_file_type() == PFHFileType::Movie {
+                            if pack_type == PFHFileType::Mod || pack_type == PFHFileType::Movie {
                                 match self.mods_mut().get_mut(&pack_name) {
                                     Some(modd) => {
                                         if !modd.paths().contains(path) {
                                             modd.paths_mut().push(path.to_path_buf());
                                         }
 
-                                        modd.set_pack_type(pack.pfh_file_type());
+                                        modd.set_pack_type(pack_type);
 
                                         let metadata = modd.paths().last().unwrap().metadata()?;
                                         #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -235,7 +267,7 @@ impl GameConfig {
                                         modd.set_name(pack_name.to_owned());
                                         modd.set_id(pack_name.to_owned());
                                         modd.set_paths(vec![path.to_path_buf()]);
-                                        modd.set_pack_type(pack.pfh_file_type());
+                                        modd.set_pack_type(pack_type);
 
                                         let metadata = modd.paths()[0].metadata()?;
                                         #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -342,23 +374,21 @@ impl GameConfig {
                                 false
                             }
                         })
+                        .cloned()
                         .collect::<Vec<_>>();
 
-                    let packs = paths.par_iter()
-                        .map(|path| (path, Pack::read_and_merge(&[path.to_path_buf()], true, false, false)))
-                        .collect::<Vec<_>>();
-
-                    for (path, pack) in packs {
+                    let pack_types = index_pack_types(&paths);
+                    for (path, pack_type) in paths.iter().zip(pack_types) {
                         let pack_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
-                        if let Ok(pack) = pack {
-                            if pack.pfh_file_type() == PFHFileType::Mod || pack.pfh_file_type() == PFHFileType::Movie {
+                        if let Some(pack_type) = pack_type {
+                            if pack_type == PFHFileType::Mod || pack_type == PFHFileType::Movie {
 
                                 match self.mods_mut().get_mut(&pack_name) {
                                     Some(modd) => {
                                         if !modd.paths().contains(path) {
                                             modd.paths_mut().insert(0, path.to_path_buf());
                                         }
-                                        modd.set_pack_type(pack.pfh_file_type());
+                                        modd.set_pack_type(pack_type);
 
                                         let metadata = modd.paths()[0].metadata()?;
                                         #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -375,7 +405,7 @@ impl GameConfig {
                                                 if !modd.paths().contains(path) {
                                                     modd.paths_mut().insert(0, path.to_path_buf());
                                                 }
-                                                modd.set_pack_type(pack.pfh_file_type());
+                                                modd.set_pack_type(pack_type);
 
                                                 let metadata = modd.paths()[0].metadata()?;
                                                 #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -388,7 +418,7 @@ impl GameConfig {
                                                 modd.set_name(pack_name.to_owned());
                                                 modd.set_id(pack_name.to_owned());
                                                 modd.set_paths(vec![path.to_path_buf()]);
-                                                modd.set_pack_type(pack.pfh_file_type());
+                                                modd.set_pack_type(pack_type);
 
                                                 let metadata = modd.paths()[0].metadata()?;
                                                 #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -406,28 +436,21 @@ impl GameConfig {
 
                 // Then finally we process /data packs. These have the highest priority.
                 if let Some(ref paths) = data_paths {
+
+                    // These are not cannonicalized by default.
                     let paths = paths.iter()
-                        .filter(|path| {
-                            if let Ok(canon_path) = std::fs::canonicalize(path) {
-                                let file_name = canon_path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_else(String::new);
-                                !vanilla_packs.contains(&canon_path) && file_name != RESERVED_PACK_NAME && file_name != RESERVED_PACK_NAME_ALTERNATIVE
-                            } else {
-                                false
-                            }
+                        .filter_map(|path| std::fs::canonicalize(path).ok())
+                        .filter(|canon_path| {
+                            let file_name = canon_path.file_name().map(|x| x.to_string_lossy().to_string()).unwrap_or_else(String::new);
+                            !vanilla_packs.contains(canon_path) && file_name != RESERVED_PACK_NAME && file_name != RESERVED_PACK_NAME_ALTERNATIVE
                         })
                         .collect::<Vec<_>>();
 
-                    let packs = paths.par_iter()
-                        .map(|path| (path, Pack::read_and_merge(&[path.to_path_buf()], true, false, false)))
-                        .collect::<Vec<_>>();
-
-                    for (path, pack) in packs {
+                    let pack_types = index_pack_types(&paths);
+                    for (path, pack_type) in paths.into_iter().zip(pack_types) {
                         let pack_name = path.file_name().unwrap().to_string_lossy().as_ref().to_owned();
-                        if let Ok(pack) = pack {
-                            if pack.pfh_file_type() == PFHFileType::Mod || pack.pfh_file_type() == PFHFileType::Movie {
-
-                                // These are not cannonicalized by default.
-                                let path = std::fs::canonicalize(path)?;
+                        if let Some(pack_type) = pack_type {
+                            if pack_type == PFHFileType::Mod || pack_type == PFHFileType::Movie {
 
                                 // Check if the pack corresponds to a bin.
                                 if let Some((_, modd)) = self.mods_mut().iter_mut().find(|(_, modd)| !modd.file_name().is_empty() && modd.file_name().split('/').last().unwrap() == pack_name) {
@@ -444,7 +467,7 @@ impl GameConfig {
                                             if !modd.paths().contains(&path) {
                                                 modd.paths_mut().insert(0, path.to_path_buf());
                                             }
-                                            modd.set_pack_type(pack.pfh_file_type());
+                                            modd.set_pack_type(pack_type);
 
                                             let metadata = modd.paths()[0].metadata()?;
                                             #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -461,7 +484,7 @@ impl GameConfig {
                                                     if !modd.paths().contains(&path) {
                                                         modd.paths_mut().insert(0, path.to_path_buf());
                                                     }
-                                                    modd.set_pack_type(pack.pfh_file_type());
+                                                    modd.set_pack_type(pack_type);
 
                                                     let metadata = modd.paths()[0].metadata()?;
                                                     #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -473,7 +496,7 @@ impl GameConfig {
                                                     modd.set_name(pack_name.to_owned());
                                                     modd.set_id(pack_name.to_owned());
                                                     modd.set_paths(vec![path.to_path_buf()]);
-                                                    modd.set_pack_type(pack.pfh_file_type());
+                                                    modd.set_pack_type(pack_type);
 
                                                     let metadata = modd.paths()[0].metadata()?;
                                                     #[cfg(target_os = "windows")] modd.set_time_created(metadata.created()?.duration_since(UNIX_EPOCH)?.as_secs() as usize);
@@ -492,40 +515,20 @@ impl GameConfig {
             }
         }
 
-        // Update the categories list to remove any mod that has no path, and add any new mod to the default category.
-        for mods in self.categories.values_mut() {
-            mods.retain(|mod_id| match self.mods.get(mod_id) {
-                Some(modd) => !modd.paths().is_empty(),
-                None => false,
-            });
-        }
-
-        let mut mods_to_add = vec![];
-        for modd in self.mods.values() {
-            if !modd.paths().is_empty() && self.categories().iter().all(|(_, mods)| !mods.contains(modd.id())) {
-                mods_to_add.push(modd.id().to_owned());
-            }
-        }
-
-        match self.categories_mut().get_mut(DEFAULT_CATEGORY) {
-            Some(mods) => mods.append(&mut mods_to_add),
-            None => { self.categories_mut().insert(DEFAULT_CATEGORY.to_owned(), mods_to_add); },
-        }
-
-        // If we got a default category, make sure it's always at the end.
-        if let Some(cat) = self.categories_order().last() {
-            if cat != DEFAULT_CATEGORY && self.categories().get(DEFAULT_CATEGORY).is_some() {
-                if let Some(mods) = self.categories_mut().remove(DEFAULT_CATEGORY) {
-                    self.categories_mut().insert(DEFAULT_CATEGORY.to_owned(), mods);
-                }
-            }
-        }
+        self.update_categories();
 
         // Update the current load order to reflect any change related to mods no longer being installed or being added as new.
         let game_data_path = game.data_path(game_path)?;
         load_order.update(self, &game_data_path);
         load_order.save(game)?;
 
+        // Save the pack index, so we don't need to read the packs again the next time.
+        let mut pack_index = PACK_INDEX.write().unwrap();
+        pack_index.prune();
+        if let Err(error) = pack_index.save() {
+            error!("Error saving the pack index: {}", error);
+        }
+
         // Save the GameConfig or we may lost the population.
         self.save(game)?;
 
@@ -0,0 +1,214 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the undo/redo history of the mod list, and the snapshots of the mod list taken each time the game is launched.
+
+use anyhow::Result;
+use getset::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rpfm_lib::games::GameInfo;
+
+use crate::settings_ui::game_config_path;
+
+use super::game_config::GameConfig;
+use super::load_order::LoadOrder;
+
+#[cfg(test)] mod test;
+
+const SNAPSHOTS_FILE_NAME_START: &str = "launch_snapshots_";
+const SNAPSHOTS_FILE_NAME_END: &str = ".json";
+
+/// Max amount of changes we can undo.
+const MAX_HISTORY_LEN: usize = 50;
+
+/// Max amount of launch snapshots we keep per game.
+const MAX_SNAPSHOTS: usize = 20;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// State of the mod list we can go back to: enabled mods, categories and load order.
+#[derive(Clone, Debug, Default, PartialEq, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct ModListState {
+
+    // Ids of the enabled mods, sorted.
+    enabled: Vec<String>,
+
+    categories: BTreeMap<String, Vec<String>>,
+    categories_order: Vec<String>,
+
+    // If the load order was automatic, and its mods in order. Movie packs are not stored, as their order is always automatic.
+    automatic: bool,
+    load_order: Vec<String>,
+}
+
+/// Undo/redo stacks of the mod list of the game selected. Not persisted.
+#[derive(Clone, Debug, Default)]
+pub struct ModListHistory {
+    undo: Vec<ModListState>,
+    redo: Vec<ModListState>,
+}
+
+/// State of the mod list when the game was launched.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct LaunchSnapshot {
+
+    // Date of the launch, in seconds since the unix epoch.
+    date: u64,
+    state: ModListState,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl ModListState {
+
+    pub fn new(game_config: &GameConfig, load_order: &LoadOrder, game_data_path: &Path) -> Self {
+        let mut enabled = game_config.mods()
+            .values()
+            .filter(|modd| modd.enabled(game_data_path))
+            .map(|modd| modd.id().to_owned())
+            .collect::<Vec<_>>();
+
+        enabled.sort();
+
+        Self {
+            enabled,
+            categories: game_config.categories().clone(),
+            categories_order: game_config.categories_order().to_vec(),
+            automatic: *load_order.automatic(),
+            load_order: load_order.mods().to_vec(),
+        }
+    }
+
+    /// This function restores this state into the provided game config and load order.
+    ///
+    /// Mods installed after the state was taken are disabled and added to the default category. Mods no longer installed are ignored.
+    pub fn apply(&self, game_config: &mut GameConfig, load_order: &mut LoadOrder, game_data_path: &Path) {
+        for modd in game_config.mods_mut().values_mut() {
+            let enabled = self.enabled.binary_search(modd.id()).is_ok();
+            modd.set_enabled(enabled);
+        }
+
+        game_config.set_categories(self.categories.clone());
+        game_config.set_categories_order(self.categories_order.to_vec());
+        game_config.update_categories();
+
+        load_order.set_automatic(self.automatic);
+        load_order.set_mods(self.load_order.to_vec());
+        load_order.update(game_config, game_data_path);
+    }
+}
+
+impl ModListHistory {
+
+    /// This function stores the state of the mod list before a change, so the change can be undone.
+    ///
+    /// Storing a new state clears the redo stack.
+    pub fn push(&mut self, state: ModListState) {
+        if self.undo.last() == Some(&state) {
+            return;
+        }
+
+        self.push_undo(state);
+        self.redo.clear();
+    }
+
+    /// This function returns the state to restore to undo the last change, storing the current one so the undo can be redone.
+    pub fn undo(&mut self, current: ModListState) -> Option<ModListState> {
+        let state = self.undo.pop()?;
+        self.redo.push(current);
+        Some(state)
+    }
+
+    /// This function returns the state to restore to redo the last undone change, storing the current one so the redo can be undone.
+    pub fn redo(&mut self, current: ModListState) -> Option<ModListState> {
+        let state = self.redo.pop()?;
+        self.push_undo(current);
+        Some(state)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// This function adds a state to the undo stack, dropping the oldest one if the stack is full.
+    fn push_undo(&mut self, state: ModListState) {
+        self.undo.push(state);
+        if self.undo.len() > MAX_HISTORY_LEN {
+            self.undo.remove(0);
+        }
+    }
+}
+
+impl LaunchSnapshot {
+
+    /// This function returns the launch snapshots of the provided game, with the most recent first.
+    pub fn snapshots_for_game(game: &GameInfo) -> Result<Vec<Self>> {
+        let path = game_config_path()?.join(format!("{SNAPSHOTS_FILE_NAME_START}{}{SNAPSHOTS_FILE_NAME_END}", game.key()));
+        if !path.is_file() {
+            return Ok(vec![]);
+        }
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let snapshots: Vec<Self> = serde_json::from_slice(&data)?;
+        Ok(snapshots)
+    }
+
+    /// This function stores a snapshot of the provided state for the provided game, dated now.
+    ///
+    /// If the state is the same as the one of the last snapshot, only its date is updated. Only the latest snapshots are kept.
+    pub fn save_for_game(game: &GameInfo, state: ModListState) -> Result<()> {
+        let mut snapshots = Self::snapshots_for_game(game).unwrap_or_default();
+        let date = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        if snapshots.first().map(|last| last.state == state).unwrap_or(false) {
+            snapshots[0].date = date;
+        } else {
+            snapshots.insert(0, Self { date, state });
+        }
+
+        snapshots.truncate(MAX_SNAPSHOTS);
+
+        let path = game_config_path()?.join(format!("{SNAPSHOTS_FILE_NAME_START}{}{SNAPSHOTS_FILE_NAME_END}", game.key()));
+
+        // Make sure the path exists to avoid problems with updating schemas.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&serde_json::to_vec(&snapshots)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the undo/redo history of the mod list.
+
+use super::*;
+
+fn state(index: usize) -> ModListState {
+    ModListState {
+        load_order: vec![format!("mod_{index}.pack")],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_push_dedup() {
+    let mut history = ModListHistory::default();
+    history.push(state(0));
+    history.push(state(0));
+    history.push(state(1));
+
+    // Pushing the same state twice in a row only stores it once.
+    assert_eq!(history.undo, vec![state(0), state(1)]);
+}
+
+#[test]
+fn test_push_clears_redo() {
+    let mut history = ModListHistory::default();
+    history.push(state(0));
+    history.push(state(1));
+
+    assert_eq!(history.undo(state(2)), Some(state(1)));
+    assert!(history.can_redo());
+
+    assert_eq!(history.redo(state(1)), Some(state(2)));
+    assert_eq!(history.undo, vec![state(0), state(1)]);
+
+    history.undo(state(2));
+    history.push(state(3));
+    assert!(!history.can_redo());
+    assert_eq!(history.redo(state(3)), None);
+}
+
+#[test]
+fn test_history_cap() {
+    let mut history = ModListHistory::default();
+    for index in 0..MAX_HISTORY_LEN + 10 {
+        history.push(state(index));
+    }
+
+    // Only the newest changes are kept.
+    assert_eq!(history.undo.len(), MAX_HISTORY_LEN);
+    assert_eq!(history.undo.first(), Some(&state(10)));
+
+    // Redoing goes through the same cap.
+    let mut history = ModListHistory::default();
+    history.redo = (0..MAX_HISTORY_LEN + 10).map(state).collect();
+    for index in 0..MAX_HISTORY_LEN + 10 {
+        history.redo(state(1000 + index));
+    }
+
+    assert!(!history.can_redo());
+    assert_eq!(history.undo.len(), MAX_HISTORY_LEN);
+    assert_eq!(history.undo.last(), Some(&state(1000 + MAX_HISTORY_LEN + 9)));
+}
@@ -12,7 +12,8 @@
 //!
 //! For now we only support steam workshop, so all calls are redirected to the steam module.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use base64::prelude::*;
 use serde::Deserialize;
 
 use std::collections::HashMap;
@@ -20,9 +21,14 @@ use std::path::Path;
 
 use rpfm_lib::games::GameInfo;
 
-use crate::mod_manager::mods::Mod;
+use rpfm_ui_common::settings::setting_string;
+
+use crate::mod_manager::{load_order::CUSTOM_MOD_LIST_FILE_NAME, mods::Mod};
 
 mod steam;
+mod workshopper;
+
+#[cfg(test)] mod test;
 
 #[cfg(target_os = "windows")] const CREATE_NO_WINDOW: u32 = 0x08000000;
 #[cfg(target_os = "windows")] const DETACHED_PROCESS: u32 = 0x00000008;
@@ -49,6 +55,16 @@ pub struct PreUploadInfo {
     pub tags: Vec<String>,
 }
 
+/// Collection published to the Workshop, and the items that could not be added to or removed from it.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PublishedCollection {
+    pub published_file_id: u64,
+    pub failed_items: Vec<u64>,
+
+    #[serde(default)]
+    pub failed_removals: Vec<u64>,
+}
+
 //-------------------------------------------------------------------------------//
 //                             Implementations
 //-------------------------------------------------------------------------------//
@@ -57,6 +73,10 @@ pub fn request_mods_data(game: &GameInfo, mod_ids: &[String]) -> Result<Vec<Mod>
     steam::request_mods_data(game, mod_ids)
 }
 
+pub fn request_collection_mods(game: &GameInfo, collection_id: &str) -> Result<Vec<Mod>> {
+    steam::request_collection_mods(game, collection_id)
+}
+
 pub fn request_pre_upload_info(game: &GameInfo, mod_id: &str, owner_id: &str) -> Result<PreUploadInfo> {
     steam::request_pre_upload_info(game, mod_id, owner_id)
 }
@@ -65,16 +85,81 @@ pub fn populate_mods_with_online_data(mods: &mut HashMap<String, Mod>, workshop_
     steam::populate_mods_with_online_data(mods, workshop_items)
 }
 
-pub fn upload_mod_to_workshop(game: &GameInfo, modd: &Mod, title: &str, description: &str, tags: &[String], changelog: &str, visibility: &Option<u32>, force_update: bool) -> Result<()> {
-    steam::upload_mod_to_workshop(game, modd, title, description, tags, changelog, visibility, force_update)
+pub fn upload_mod_to_workshop(game: &GameInfo, modd: &Mod, preview_path: &Path, title: &str, description: &str, tags: &[String], changelog: &str, visibility: &Option<u32>, force_update: bool, wait_for_finish: bool) -> Result<()> {
+    steam::upload_mod_to_workshop(game, modd, preview_path, title, description, tags, changelog, visibility, force_update, wait_for_finish)
+}
+
+pub fn publish_collection(game: &GameInfo, collection_id: &Option<String>, published_file_ids: &[String], title: &str, description: &str) -> Result<PublishedCollection> {
+    steam::publish_collection(game, collection_id, published_file_ids, title, description)
 }
 
 pub fn launch_game(game: &GameInfo, command_to_pass: &str, wait_for_finish: bool) -> Result<()> {
     steam::launch_game(game, command_to_pass, wait_for_finish)
 }
 
-pub fn download_subscribed_mods(game: &GameInfo, published_file_ids: &Option<Vec<String>>) -> Result<()> {
-    steam::download_subscribed_mods(game, published_file_ids)
+/// This function launches the game using the mod list file generated by Runcher, passing it the provided extra args.
+///
+/// On Windows the launch is done through workshopper, to get the Steam Api up. On Linux, it's done through the launcher command from the settings.
+pub fn launch_game_with_mod_list(game: &GameInfo, game_path: &Path, extra_args: &[String], wait_for_finish: bool) -> Result<()> {
+    let exec_game = game.executable_path(game_path).ok_or(anyhow!("Executable path not found. Is the game folder configured correctly in the settings?"))?;
+
+    if cfg!(target_os = "windows") {
+
+        // For post-shogun 2 games, we use the same command to bypass the launcher.
+        let command = if *game.raw_db_version() >= 1 {
+
+            let mut command = format!("cmd /C start /W /d \"{}\" \"{}\" {};", game_path.to_string_lossy().replace('\\', "/"), exec_game.file_name().unwrap().to_string_lossy(), CUSTOM_MOD_LIST_FILE_NAME);
+
+            for arg in extra_args {
+                command.push(' ');
+                command.push_str(arg);
+            }
+
+            command
+        }
+
+        // Empire and Napoleon do not have a launcher. We can make our lives easier calling steam instead of launching the game manually.
+        else {
+            format!("cmd /C start /W /d \"{}\" \"{}\"", game_path.to_string_lossy().replace('\\', "/"), exec_game.file_name().unwrap().to_string_lossy())
+        };
+
+        let command = BASE64_STANDARD.encode(command);
+        launch_game(game, &command, wait_for_finish)
+    } else if cfg!(target_os = "linux") {
+
+        // On Linux we don't go through workshopper. Instead we call the launcher command configured by the user
+        // (steam, proton, wine,...), and pass it the same args we would pass to the exe on Windows.
+        let mut args = vec![];
+        if *game.raw_db_version() >= 1 {
+            args.push(CUSTOM_MOD_LIST_FILE_NAME.to_owned());
+            args.extend_from_slice(extra_args);
+        }
+
+        let launcher_command = setting_string("linux_launch_command");
+        launch_game_linux(game, &launcher_command, &exec_game, &args, wait_for_finish)
+    } else {
+        Err(anyhow!("Unsupported OS."))
+    }
+}
+
+pub fn launch_game_linux(game: &GameInfo, launcher_command: &str, exec_game: &Path, args: &[String], wait_for_finish: bool) -> Result<()> {
+    steam::launch_game_linux(game, launcher_command, exec_game, args, wait_for_finish)
+}
+
+pub fn download_subscribed_mods(game: &GameInfo, published_file_ids: &Option<Vec<String>>, subscribe: bool) -> Result<()> {
+    steam::download_subscribed_mods(game, published_file_ids, subscribe)
+}
+
+pub fn subscribe(game: &GameInfo, published_file_ids: &[String], download: bool) -> Result<()> {
+    steam::subscribe(game, published_file_ids, download)
+}
+
+pub fn unsubscribe(game: &GameInfo, published_file_ids: &[String]) -> Result<()> {
+    steam::unsubscribe(game, published_file_ids)
+}
+
+pub fn request_subscribed_items(game: &GameInfo) -> Result<Vec<String>> {
+    steam::request_subscribed_items(game)
 }
 
 pub fn store_user_id(game: &GameInfo) -> Result<u64> {
@@ -101,3 +186,18 @@ pub fn toggle_game_locked(game: &GameInfo, game_path: &Path, toggle: bool) -> bo
         Err(_) => false,
     }
 }
+
+/// This function returns the id of a Workshop item from either its id, or a link to its page.
+pub fn workshop_item_id(input: &str) -> Option<String> {
+    let input = input.trim();
+    let id = match input.find("id=") {
+        Some(start) => input[start + 3..].split(['&', '#']).next().unwrap_or_default(),
+        None => input,
+    };
+
+    if !id.is_empty() && id.chars().all(|char| char.is_ascii_digit()) {
+        Some(id.to_owned())
+    } else {
+        None
+    }
+}
@@ -10,43 +10,34 @@
 
 use anyhow::{anyhow, Result};
 use base64::prelude::*;
-use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions};
 use regex::Regex;
-use serde::Deserialize;
+use serde::{de::IgnoredAny, Deserialize};
 use steam_workshop_api::{client::Workshop, interfaces::i_steam_user::*};
 
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::{BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
-#[cfg(target_os = "windows")]use std::os::windows::process::CommandExt;
+use std::time::Duration;
 
 use rpfm_lib::files::{EncodeableExtraData, pack::Pack};
 use rpfm_lib::games::GameInfo;
+use rpfm_lib::integrations::log::warn;
 use rpfm_lib::utils::path_to_absolute_string;
 
 use rpfm_ui_common::settings::{setting_path, setting_string};
 
 use crate::mod_manager::mods::Mod;
+use crate::mod_manager::upload_history::UploadSnapshot;
 
-#[cfg(target_os = "windows")]use super::{CREATE_NEW_CONSOLE, CREATE_NO_WINDOW, DETACHED_PROCESS};
-use super::{PreUploadInfo, PublishedFileVisibilityDerive};
+use super::{PreUploadInfo, PublishedCollection, PublishedFileVisibilityDerive};
+use super::workshopper::WorkshopperRequest;
 
 lazy_static::lazy_static! {
     pub static ref REGEX_URL: Regex = Regex::new(r"(\[url=)(.*)(\])(.*)(\[/url\])").unwrap();
-
-    static ref WORKSHOPPER_PATH: String = if cfg!(debug_assertions) {
-        format!(".\\target\\debug\\{}", WORKSHOPPER_EXE)
-    } else {
-        WORKSHOPPER_EXE.to_string()
-    };
 }
 
-const WORKSHOPPER_EXE: &str = "workshopper.exe";
-
-const BAT_UPLOAD_TO_WORKSHOP: &str = "upload-to-workshop.bat";
-const BAT_GET_PUBLISHED_FILE_DETAILS: &str = "get-published-file-details.bat";
+/// Max time without news from a download before we give up on it. Big mods can take a while.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(1800);
 
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
@@ -128,9 +119,28 @@ pub fn request_mods_data(game: &GameInfo, mod_ids: &[String]) -> Result<Vec<Mod>
     }
 
     let workshop_items = request_mods_data_raw(game, mod_ids)?;
+    Ok(mods_from_workshop_items(&workshop_items))
+}
 
+/// This function returns the mods of a Workshop collection, in the order they're in the collection.
+///
+/// Nested collections are expanded in place, and mods in more than one of them are only returned the first time they appear.
+pub fn request_collection_mods(game: &GameInfo, collection_id: &str) -> Result<Vec<Mod>> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
+    let collection_id = collection_id.parse::<u64>().map_err(|_| anyhow!("Invalid collection id: {}.", collection_id))?;
+
+    let workshop_items: Vec<QueryResultDerive> = WorkshopperRequest::new("get-collection-details", steam_id)
+        .arg("-p")
+        .arg(collection_id.to_string())
+        .send()?;
+
+    Ok(mods_from_workshop_items(&workshop_items))
+}
+
+fn mods_from_workshop_items(workshop_items: &[QueryResultDerive]) -> Vec<Mod> {
     let mut mods = vec![];
-    for workshop_item in &workshop_items {
+    for workshop_item in workshop_items {
         let mut modd = Mod::default();
         modd.set_steam_id(Some(workshop_item.published_file_id.to_string()));
 
@@ -145,51 +155,23 @@ pub fn request_mods_data(game: &GameInfo, mod_ids: &[String]) -> Result<Vec<Mod>
         mods.push(modd);
     }
 
-    Ok(mods)
+    mods
 }
 
 pub fn request_mods_data_raw(game: &GameInfo, mod_ids: &[String]) -> Result<Vec<QueryResultDerive>> {
 
-    // Do not call the cmd if there are no mods.
+    // Do not call workshopper if there are no mods.
     if mod_ids.is_empty() {
         return Ok(vec![])
     }
 
     let game_path = setting_path(game.key());
     let steam_id = game.steam_id(&game_path)? as u32;
-    let published_file_ids = mod_ids.join(",");
-    let ipc_channel = rand::random::<u64>().to_string();
-
-    let command_string = format!("{} get-published-file-details -s {steam_id} -p {published_file_ids} -i {ipc_channel} & exit", &*WORKSHOPPER_PATH);
-    let mut file = BufWriter::new(File::create(BAT_GET_PUBLISHED_FILE_DETAILS)?);
-    file.write_all(command_string.as_bytes())?;
-    file.flush()?;
-
-    let mut command = Command::new("cmd");
-    command.arg("/C");
-    command.arg(BAT_GET_PUBLISHED_FILE_DETAILS);
-
-    // This is for creating the terminal window. Without it, the entire process runs in the background and there's no feedback on when it's done.
-    #[cfg(target_os = "windows")] if cfg!(debug_assertions) {
-        command.creation_flags(DETACHED_PROCESS);
-    } else {
-        command.creation_flags(CREATE_NO_WINDOW);
-    }
-
-    command.spawn()?;
-
-    let channel = ipc_channel.to_ns_name::<GenericNamespaced>()?;
-    let server = ListenerOptions::new().name(channel).create_sync()?;
-    let mut stream = server.accept()?;
 
-    let mut message = String::new();
-    stream.read_to_string(&mut message)?;
-
-    if message == "{}" {
-        Err(anyhow!("Error retrieving Steam Workshop data."))
-    } else {
-        serde_json::from_str(&message).map_err(From::from)
-    }
+    WorkshopperRequest::new("get-published-file-details", steam_id)
+        .arg("-p")
+        .arg(mod_ids.join(","))
+        .send()
 }
 
 pub fn request_user_names(user_ids: &[String]) -> Result<HashMap<String, String>> {
@@ -253,7 +235,8 @@ pub fn populate_mods_with_author_names(mods: &mut HashMap<String, Mod>, user_nam
 /// This function uploads a mod to the workshop through workshopper.
 ///
 /// If the mod doesn't yet exists in the workshop, it creates it. If it already exists, it updates it.
-pub fn upload_mod_to_workshop(game: &GameInfo, modd: &Mod, title: &str, description: &str, tags: &[String], changelog: &str, visibility: &Option<u32>, force_update: bool) -> Result<()> {
+/// If we don't wait for the upload to finish, we still wait until it starts, so we can report any error starting it.
+pub fn upload_mod_to_workshop(game: &GameInfo, modd: &Mod, preview_path: &Path, title: &str, description: &str, tags: &[String], changelog: &str, visibility: &Option<u32>, force_update: bool, wait_for_finish: bool) -> Result<()> {
     let game_path = setting_path(game.key());
     let steam_id = game.steam_id(&game_path)? as u32;
 
@@ -273,69 +256,117 @@ pub fn upload_mod_to_workshop(game: &GameInfo, modd: &Mod, title: &str, descript
     // If we have a published_file_id, it means this file exists in the workshop.
     //
     // So, instead of uploading, we just update it.
-    let mut command_string = format!("{} {} -b -s {steam_id} -f \"{pack_path}\" -t {} --tags {}",
-        &*WORKSHOPPER_PATH,
-        match modd.steam_id() {
-            Some(published_file_id) => format!("update --published-file-id {published_file_id}"),
-            None => "upload".to_string(),
-        },
-        BASE64_STANDARD.encode(title),
-        tags.join(",")
-    );
+    let mut request = match modd.steam_id() {
+        Some(published_file_id) => WorkshopperRequest::new("update", steam_id)
+            .arg("--published-file-id")
+            .arg(published_file_id),
+        None => WorkshopperRequest::new("upload", steam_id),
+    };
+
+    // Due to issues passing certain characters to the terminal, we encode the strings to base64 and pass -b.
+    request = request
+        .arg("-b")
+        .arg("-f")
+        .arg(&pack_path)
+        .arg("--preview-path")
+        .arg(path_to_absolute_string(preview_path))
+        .arg("-t")
+        .arg(BASE64_STANDARD.encode(title))
+        .arg("--tags")
+        .arg(tags.join(","));
 
     if !description.is_empty() {
-        command_string.push_str(&format!(" -d {}", BASE64_STANDARD.encode(description)));
+        request = request.arg("-d").arg(BASE64_STANDARD.encode(description));
     }
 
     if !changelog.is_empty() {
-        command_string.push_str(&format!(" -c {}", BASE64_STANDARD.encode(changelog)));
+        request = request.arg("-c").arg(BASE64_STANDARD.encode(changelog));
     }
 
     if let Some(visibility) = visibility {
-        command_string.push_str(&format!(" --visibility {visibility}"));
+        request = request.arg("--visibility").arg(visibility.to_string());
     }
 
-    command_string.push_str(" & exit");
+    // Remember what we uploaded, so the next upload can tell what changed. Failing here doesn't affect the upload, so just log it.
+    //
+    // The snapshot is only built once Steam accepts the upload, so a failed upload doesn't hide its changes from the next one,
+    // and hashing the pack doesn't delay the start of the upload.
+    let pack_path = PathBuf::from(pack_path);
+    let pack_name = pack_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let snapshot_path = pack_path.to_path_buf();
+    let game = game.clone();
+    let save_snapshot = move || if let Err(error) = UploadSnapshot::from_pack(&snapshot_path).and_then(|snapshot| snapshot.save(&game, &pack_name)) {
+        warn!("Error saving what was uploaded of {}: {}", pack_name, error);
+    };
+
+    // Uploads can take a while, so unless we're told to wait, we only wait until the upload starts and let workshopper show the rest of it in its own terminal.
+    let request = request.with_console();
+    if wait_for_finish {
+        request.timeout(None).send::<IgnoredAny>()?;
+        save_snapshot();
+        Ok(())
+    } else {
+        request.start_with(move |result| match result {
+            Ok(_) => save_snapshot(),
+            Err(error) => warn!("Error uploading {} to the Workshop: {}", pack_path.to_string_lossy(), error),
+        })
+    }
+}
 
-    let mut file = BufWriter::new(File::create(BAT_UPLOAD_TO_WORKSHOP)?);
-    file.write_all(command_string.as_bytes())?;
-    file.flush()?;
+/// This function creates a Workshop collection with the provided mods or, if we already have one, makes it contain exactly them, in order.
+///
+/// Title and description are only used when creating the collection.
+pub fn publish_collection(game: &GameInfo, collection_id: &Option<String>, published_file_ids: &[String], title: &str, description: &str) -> Result<PublishedCollection> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
 
-    let mut command = Command::new("cmd");
-    command.arg("/C");
-    command.arg(BAT_UPLOAD_TO_WORKSHOP);
+    let mut request = WorkshopperRequest::new("publish-collection", steam_id)
+        .arg("-b")
+        .arg("-p")
+        .arg(published_file_ids.join(","))
+        .arg("-t")
+        .arg(BASE64_STANDARD.encode(title));
 
-    // This is for creating the terminal window. Without it, the entire process runs in the background and there's no feedback on when it's done.
-    #[cfg(target_os = "windows")]command.creation_flags(CREATE_NEW_CONSOLE);
-    command.spawn()?;
+    if !description.is_empty() {
+        request = request.arg("-d").arg(BASE64_STANDARD.encode(description));
+    }
 
-    Ok(())
+    if let Some(collection_id) = collection_id {
+        request = request.arg("--published-file-id").arg(collection_id);
+    }
+
+    request.send()
 }
 
 /// This function launches a game through workshopper, with access to the Steam Api.
+///
+/// If we don't wait for the game to finish, we still wait until it's launched, so we can report any error launching it.
 pub fn launch_game(game: &GameInfo, command_to_pass: &str, wait_for_finish: bool) -> Result<()> {
     let game_path = setting_path(game.key());
     let steam_id = game.steam_id(&game_path)? as u32;
 
-    let mut command = Command::new("cmd");
-    command.arg("/C");
-    command.arg(&*WORKSHOPPER_PATH);
-    command.arg("launch");
-
     // Due to issues passing certain characters to the terminal, we encode the strings to base64 and pass -b.
-    command.arg("-b");
-    command.arg("-s");
-    command.arg(steam_id.to_string());
-    command.arg("-c");
-    command.arg(command_to_pass);
-
-    // This is for creating the terminal window. Without it, the entire process runs in the background and there's no feedback on when it's done.
-    #[cfg(target_os = "windows")] if cfg!(debug_assertions) {
-        command.creation_flags(DETACHED_PROCESS);
+    let request = WorkshopperRequest::new("launch", steam_id)
+        .arg("-b")
+        .arg("-c")
+        .arg(command_to_pass);
+
+    if wait_for_finish {
+        request.timeout(None).send()
     } else {
-        command.creation_flags(CREATE_NO_WINDOW);
+        request.start()
     }
+}
 
+/// This function launches a game natively on Linux, through the launcher command configured in the settings.
+///
+/// When waiting for the game to finish, we actually wait for the launcher command to finish. Wrappers like proton or wine
+/// only finish when the game does, but `steam -applaunch {app_id}` finishes as soon as Steam takes over the launch.
+pub fn launch_game_linux(game: &GameInfo, launcher_command: &str, exec_game: &Path, args: &[String], wait_for_finish: bool) -> Result<()> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
+
+    let mut command = linux_launch_command(launcher_command, steam_id, exec_game, &game_path, args)?;
     let mut handle = command.spawn()?;
 
     if wait_for_finish {
@@ -345,67 +376,115 @@ pub fn launch_game(game: &GameInfo, command_to_pass: &str, wait_for_finish: bool
     Ok(())
 }
 
+/// This function builds the command to launch a game on Linux from the provided launcher command.
+///
+/// The launcher command can contain the placeholders `{app_id}`, `{exe}` and `{game_path}`, which are replaced
+/// before launching. The args are passed after the launcher command, in the same order they're received.
+pub fn linux_launch_command(launcher_command: &str, steam_id: u32, exec_game: &Path, game_path: &Path, args: &[String]) -> Result<Command> {
+    let launcher_command = launcher_command
+        .replace("{app_id}", &steam_id.to_string())
+        .replace("{exe}", &exec_game.to_string_lossy())
+        .replace("{game_path}", &game_path.to_string_lossy());
+
+    let mut command_split = split_command(&launcher_command).into_iter();
+    let program = command_split.next().ok_or(anyhow!("The launcher command is empty. Check the Linux launcher command in the settings."))?;
+
+    let mut command = Command::new(program);
+    command.args(command_split);
+    command.args(args);
+    command.current_dir(game_path);
+
+    Ok(command)
+}
+
+/// This function splits a command line into its components, keeping together anything between double quotes.
+pub fn split_command(command: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for character in command.chars() {
+        match character {
+            '"' => in_quotes = !in_quotes,
+            ' ' | '\t' if !in_quotes => {
+                if !current.is_empty() {
+                    parts.push(current.to_owned());
+                    current.clear();
+                }
+            },
+            _ => current.push(character),
+        }
+    }
+
+    if !current.is_empty() {
+        parts.push(current);
+    }
+
+    parts
+}
+
 /// This function asks workshopper to get all subscribed items, check which ones are missing, and tell steam to re-download them.
-pub fn download_subscribed_mods(game: &GameInfo, published_file_ids: &Option<Vec<String>>) -> Result<()> {
+///
+/// If `subscribe` is true, the provided items the user is not subscribed to are subscribed to before downloading them.
+pub fn download_subscribed_mods(game: &GameInfo, published_file_ids: &Option<Vec<String>>, subscribe: bool) -> Result<()> {
     let game_path = setting_path(game.key());
     let steam_id = game.steam_id(&game_path)? as u32;
 
-    let mut command = Command::new("cmd");
-    command.arg("/C");
-    command.arg(&*WORKSHOPPER_PATH);
-
-    command.arg("download-subscribed-items");
-    command.arg("-s");
-    command.arg(steam_id.to_string());
+    let mut request = WorkshopperRequest::new("download-subscribed-items", steam_id)
+        .timeout(Some(DOWNLOAD_TIMEOUT));
 
     if let Some(published_file_ids) = published_file_ids {
-        command.arg("-p");
-        command.arg(published_file_ids.join(","));
+        request = request.arg("-p").arg(published_file_ids.join(","));
     }
 
-    // This is for creating the terminal window. Without it, the entire process runs in the background and there's no feedback on when it's done.
-    #[cfg(target_os = "windows")]command.creation_flags(DETACHED_PROCESS);
-
-    let mut handle = command.spawn()?;
-    handle.wait()?;
+    if subscribe {
+        request = request.arg("--subscribe");
+    }
 
-    Ok(())
+    request.send()
 }
 
-pub fn user_id(game: &GameInfo) -> Result<u64> {
+/// This function subscribes to the provided Workshop items through workshopper, re-subscribing to the broken legacy ones.
+pub fn subscribe(game: &GameInfo, published_file_ids: &[String], download: bool) -> Result<()> {
     let game_path = setting_path(game.key());
     let steam_id = game.steam_id(&game_path)? as u32;
-    let ipc_channel = rand::random::<u64>().to_string();
-
-    let mut command = Command::new("cmd");
-    command.arg("/C");
-    command.arg(&*WORKSHOPPER_PATH);
 
-    command.arg("user-id");
-    command.arg("-s");
-    command.arg(steam_id.to_string());
-    command.arg("-i");
-    command.arg(&ipc_channel);
+    let mut request = WorkshopperRequest::new("subscribe", steam_id)
+        .arg("-p")
+        .arg(published_file_ids.join(","));
 
-    // This is for creating the terminal window. Without it, the entire process runs in the background and there's no feedback on when it's done.
-    #[cfg(target_os = "windows")] if cfg!(debug_assertions) {
-        command.creation_flags(DETACHED_PROCESS);
-    } else {
-        command.creation_flags(CREATE_NO_WINDOW);
+    if download {
+        request = request.arg("-d").timeout(Some(DOWNLOAD_TIMEOUT));
     }
 
-    let _ = command.spawn()?;
+    request.send()
+}
+
+/// This function unsubscribes from the provided Workshop items through workshopper.
+pub fn unsubscribe(game: &GameInfo, published_file_ids: &[String]) -> Result<()> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
 
-    let channel = ipc_channel.to_ns_name::<GenericNamespaced>()?;
-    let server = ListenerOptions::new().name(channel).create_sync()?;
-    let mut stream = server.accept()?;
+    WorkshopperRequest::new("unsubscribe", steam_id)
+        .arg("-p")
+        .arg(published_file_ids.join(","))
+        .send()
+}
+
+/// This function returns the ids of the Workshop items the user is subscribed to.
+pub fn request_subscribed_items(game: &GameInfo) -> Result<Vec<String>> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
 
-    let mut bytes = vec![];
-    stream.read_to_end(&mut bytes)?;
+    let published_file_ids: Vec<u64> = WorkshopperRequest::new("get-subscribed-items", steam_id).send()?;
+    Ok(published_file_ids.iter().map(|id| id.to_string()).collect())
+}
 
-    let array: [u8; 8] = bytes.try_into().map_err(|_| anyhow!("Error when trying to get the Steam User ID."))?;
+pub fn user_id(game: &GameInfo) -> Result<u64> {
+    let game_path = setting_path(game.key());
+    let steam_id = game.steam_id(&game_path)? as u32;
 
-    Ok(u64::from_le_bytes(array))
+    WorkshopperRequest::new("user-id", steam_id).send()
 }
 
 fn app_manifest_path(game: &GameInfo, game_path: &Path) -> Result<PathBuf> {
@@ -0,0 +1,77 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the online integrations.
+
+use std::fs::read_to_string;
+
+use super::*;
+
+#[test]
+fn test_workshop_item_id() {
+    assert_eq!(workshop_item_id("2789857593"), Some("2789857593".to_owned()));
+    assert_eq!(workshop_item_id("  2789857593\n"), Some("2789857593".to_owned()));
+    assert_eq!(workshop_item_id("https://steamcommunity.com/sharedfiles/filedetails/?id=2789857593"), Some("2789857593".to_owned()));
+    assert_eq!(workshop_item_id("https://steamcommunity.com/workshop/filedetails/?id=2789857593&searchtext=mixu"), Some("2789857593".to_owned()));
+    assert_eq!(workshop_item_id("https://steamcommunity.com/sharedfiles/filedetails/?id=2789857593#comments"), Some("2789857593".to_owned()));
+
+    assert_eq!(workshop_item_id(""), None);
+    assert_eq!(workshop_item_id("mixu_unlocker.pack"), None);
+    assert_eq!(workshop_item_id("https://steamcommunity.com/sharedfiles/filedetails/?id="), None);
+    assert_eq!(workshop_item_id("https://steamcommunity.com/sharedfiles/filedetails/?id=abc"), None);
+}
+
+#[test]
+fn test_split_command() {
+    assert_eq!(steam::split_command("steam -applaunch 1142710"), vec!["steam", "-applaunch", "1142710"]);
+    assert_eq!(steam::split_command("  \"/home/user/My Games/proton\"   run\t\"{exe}\" "), vec!["/home/user/My Games/proton", "run", "{exe}"]);
+    assert_eq!(steam::split_command("wine \"C:/Program Files/\"game.exe"), vec!["wine", "C:/Program Files/game.exe"]);
+
+    // Empty quotes are not passed as an empty argument.
+    assert_eq!(steam::split_command("steam \"\" -applaunch"), vec!["steam", "-applaunch"]);
+    assert!(steam::split_command("").is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_launch_command() {
+    let folder = tempfile::tempdir().unwrap();
+    let game_path = folder.path().join("My Game");
+    let exec_game = game_path.join("Warhammer3.exe");
+    let stub_path = folder.path().join("stub launcher.sh");
+    let args_path = folder.path().join("args.txt");
+    std::fs::create_dir(&game_path).unwrap();
+
+    // The stub records the folder it's launched from, and then each argument it receives in its own line.
+    std::fs::write(&stub_path, format!("{{ pwd -P; printf '%s\\n' \"$@\"; }} > \"{}\"\n", args_path.to_string_lossy())).unwrap();
+
+    let launcher_command = format!("sh \"{}\" --app {{app_id}} \"{{exe}}\" \"--path={{game_path}}\"", stub_path.to_string_lossy());
+    let args = vec![CUSTOM_MOD_LIST_FILE_NAME.to_owned(), "game_startup_mode".to_owned(), "campaign_load".to_owned(), "my save.save".to_owned()];
+
+    let mut command = steam::linux_launch_command(&launcher_command, 1142710, &exec_game, &game_path, &args).unwrap();
+    assert!(command.status().unwrap().success());
+
+    let recorded = read_to_string(&args_path).unwrap();
+    let game_path_arg = format!("--path={}", game_path.to_string_lossy());
+    let game_path_canonical = game_path.canonicalize().unwrap();
+    assert_eq!(recorded.lines().collect::<Vec<_>>(), vec![
+        game_path_canonical.to_str().unwrap(),
+        "--app",
+        "1142710",
+        exec_game.to_str().unwrap(),
+        game_path_arg.as_str(),
+        CUSTOM_MOD_LIST_FILE_NAME,
+        "game_startup_mode",
+        "campaign_load",
+        "my save.save",
+    ]);
+
+    assert!(steam::linux_launch_command("  ", 1142710, &exec_game, &game_path, &args).is_err());
+}
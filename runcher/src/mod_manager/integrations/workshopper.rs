@@ -0,0 +1,340 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module to talk with workshopper, the tool we use to access the Steam Api.
+//!
+//! Each request runs its own workshopper process, which reports back through a local socket we create for it.
+//! Messages are JSON envelopes, one per line: any amount of progress messages, followed by exactly one response or error.
+//! These types are a copy of the ones in workshopper's ipc module, so any incompatible change there needs a bump of [PROTOCOL_VERSION] here.
+//! Both sides test against the messages in workshopper's `test_files/ipc_messages.jsonl`, to catch the copies getting out of sync.
+
+use anyhow::{anyhow, Result};
+use crossbeam::channel::{unbounded, Receiver, RecvTimeoutError, Sender};
+use interprocess::local_socket::{prelude::*, GenericNamespaced, ListenerOptions};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Child, Command};
+use std::thread;
+use std::time::{Duration, Instant};
+#[cfg(target_os = "windows")]use std::os::windows::process::CommandExt;
+
+use rpfm_lib::integrations::log::info;
+
+#[cfg(target_os = "windows")]use super::{CREATE_NEW_CONSOLE, CREATE_NO_WINDOW, DETACHED_PROCESS};
+
+#[cfg(test)] mod test;
+
+/// Version of the protocol we understand.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Time we wait for a message from workshopper before giving up on it, unless the request says otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Time between checks of the workshopper process while we wait for messages.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[cfg(target_os = "windows")] const WORKSHOPPER_EXE: &str = "workshopper.exe";
+#[cfg(not(target_os = "windows"))] const WORKSHOPPER_EXE: &str = "workshopper";
+
+lazy_static::lazy_static! {
+    static ref WORKSHOPPER_PATH: PathBuf = if cfg!(debug_assertions) {
+        PathBuf::from(".").join("target").join("debug").join(WORKSHOPPER_EXE)
+    } else {
+
+        // Outside Windows the folder of the exe is not in the search path, so we need to point to it explicitly.
+        std::env::current_exe().ok()
+            .and_then(|path| path.parent().map(|folder| folder.join(WORKSHOPPER_EXE)))
+            .unwrap_or_else(|| PathBuf::from(WORKSHOPPER_EXE))
+    };
+}
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Request to workshopper, built like a [Command].
+#[derive(Debug)]
+pub struct WorkshopperRequest {
+    command: Command,
+
+    /// Max time without messages from workshopper. None for no limit.
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Envelope {
+    version: u32,
+    message: Message,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+enum Message {
+    Progress(Progress),
+    Response(Value),
+    Error(WorkshopperError),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Progress {
+    pub stage: Stage,
+    pub current: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Stage {
+    Started,
+    PreparingConfig,
+    PreparingContent,
+    UploadingContent,
+    UploadingPreview,
+    CommittingChanges,
+    Downloading,
+}
+
+/// Error reported by workshopper. Returned wrapped in an anyhow error, so it can be downcasted to check its kind.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkshopperError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ErrorKind {
+    SteamInit,
+    Workshop,
+    InvalidInput,
+    Io,
+    Other,
+}
+
+/// Events from the thread reading the socket.
+enum Event {
+    Connected,
+    Message(Envelope),
+    Closed,
+    Failed(anyhow::Error),
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Display for WorkshopperError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ErrorKind::SteamInit => write!(f, "Failed to connect to Steam: {}. Make sure Steam is running and you own the game.", self.message),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for WorkshopperError {}
+
+impl WorkshopperRequest {
+
+    /// This function creates a request for the provided workshopper command, for the game with the provided SteamId.
+    pub fn new(command: &str, steam_id: u32) -> Self {
+        let mut process = Command::new(&*WORKSHOPPER_PATH);
+        process.arg(command);
+        process.arg("-s");
+        process.arg(steam_id.to_string());
+
+        // In debug mode we want to see the terminal of workshopper.
+        #[cfg(target_os = "windows")] if cfg!(debug_assertions) {
+            process.creation_flags(DETACHED_PROCESS);
+        } else {
+            process.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        Self {
+            command: process,
+            timeout: Some(DEFAULT_TIMEOUT),
+        }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(mut self, arg: S) -> Self {
+        self.command.arg(arg);
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// This makes workshopper open its own terminal, so the user can follow long operations there.
+    pub fn with_console(mut self) -> Self {
+        #[cfg(target_os = "windows")]self.command.creation_flags(CREATE_NEW_CONSOLE);
+        self
+    }
+
+    /// This function runs the request and waits for its response.
+    pub fn send<T: DeserializeOwned>(self) -> Result<T> {
+        let response = self.run(false, None)?.unwrap_or(Value::Null);
+        serde_json::from_value(response).map_err(|error| anyhow!("Invalid response received from workshopper: {}", error))
+    }
+
+    /// This function runs the request and waits only until workshopper reports it as started, leaving it to finish on its own.
+    pub fn start(self) -> Result<()> {
+        self.run(true, None).map(|_| ())
+    }
+
+    /// This function runs the request and waits only until workshopper reports it as started.
+    ///
+    /// The provided callback is called from a background thread with the result of the request, once workshopper finishes it.
+    pub fn start_with<F: FnOnce(Result<()>) + Send + 'static>(self, on_finish: F) -> Result<()> {
+        self.run(true, Some(Box::new(on_finish))).map(|_| ())
+    }
+
+    fn run(mut self, until_started: bool, on_finish: Option<Box<dyn FnOnce(Result<()>) + Send>>) -> Result<Option<Value>> {
+        let ipc_channel = rand::random::<u64>().to_string();
+
+        // Create the socket before launching workshopper, so it's there when it tries to connect.
+        let listener = ListenerOptions::new().name(ipc_channel.to_ns_name::<GenericNamespaced>()?).create_sync()?;
+        let (sender, receiver) = unbounded();
+        thread::spawn(move || read_messages(listener, sender));
+
+        self.command.arg("-i");
+        self.command.arg(&ipc_channel);
+
+        let mut child = match self.command.spawn() {
+            Ok(child) => child,
+            Err(error) => {
+                wake_listener(&ipc_channel);
+                return Err(anyhow!("Error running workshopper ({}): {}", WORKSHOPPER_PATH.to_string_lossy(), error));
+            }
+        };
+
+        let mut connected = false;
+        let mut last_message = Instant::now();
+
+        loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(Event::Connected) => {
+                    connected = true;
+                    last_message = Instant::now();
+                },
+                Ok(Event::Message(envelope)) => {
+                    last_message = Instant::now();
+
+                    if envelope.version != PROTOCOL_VERSION {
+                        abort(&mut child, &ipc_channel);
+                        return Err(anyhow!("Workshopper uses version {} of the communication protocol, but Runcher expects version {}. Make sure both are from the same release.", envelope.version, PROTOCOL_VERSION));
+                    }
+
+                    match envelope.message {
+                        Message::Progress(progress) => {
+                            info!("Workshopper progress: {:?} ({}/{}).", progress.stage, progress.current, progress.total);
+
+                            if until_started && progress.stage == Stage::Started {
+                                if let Some(on_finish) = on_finish {
+                                    thread::spawn(move || on_finish(wait_for_result(receiver)));
+                                }
+
+                                return Ok(None);
+                            }
+                        },
+                        Message::Response(response) => return Ok(Some(response)),
+                        Message::Error(error) => return Err(From::from(error)),
+                    }
+                },
+                Ok(Event::Closed) => return Err(anyhow!("Workshopper closed the connection without answering. Check the workshopper logs for more info.")),
+                Ok(Event::Failed(error)) => {
+                    abort(&mut child, &ipc_channel);
+                    return Err(error);
+                },
+                Err(RecvTimeoutError::Timeout) => {
+
+                    // If it died before connecting, it's never going to answer.
+                    if !connected {
+                        if let Ok(Some(status)) = child.try_wait() {
+                            wake_listener(&ipc_channel);
+                            return Err(anyhow!("Workshopper exited ({}) without answering. Check the workshopper logs for more info.", status));
+                        }
+                    }
+
+                    if let Some(timeout) = self.timeout {
+                        if last_message.elapsed() >= timeout {
+                            abort(&mut child, &ipc_channel);
+                            return Err(anyhow!("Workshopper didn't answer in {} seconds. Check the workshopper logs for more info.", timeout.as_secs()));
+                        }
+                    }
+                },
+                Err(RecvTimeoutError::Disconnected) => return Err(anyhow!("Workshopper closed the connection without answering. Check the workshopper logs for more info.")),
+            }
+        }
+    }
+}
+
+/// This function waits for the response of a request already started, without any time limit.
+fn wait_for_result(receiver: Receiver<Event>) -> Result<()> {
+    loop {
+        match receiver.recv() {
+            Ok(Event::Connected) => {},
+            Ok(Event::Message(envelope)) => match envelope.message {
+                Message::Progress(progress) => info!("Workshopper progress: {:?} ({}/{}).", progress.stage, progress.current, progress.total),
+                Message::Response(_) => return Ok(()),
+                Message::Error(error) => return Err(From::from(error)),
+            },
+            Ok(Event::Failed(error)) => return Err(error),
+            Ok(Event::Closed) | Err(_) => return Err(anyhow!("Workshopper closed the connection without answering. Check the workshopper logs for more info.")),
+        }
+    }
+}
+
+/// This function waits for workshopper to connect, then reads its messages until it closes the connection.
+fn read_messages(listener: LocalSocketListener, sender: Sender<Event>) {
+    let stream = match listener.accept() {
+        Ok(stream) => stream,
+        Err(error) => {
+            let _ = sender.send(Event::Failed(From::from(error)));
+            return;
+        }
+    };
+
+    if sender.send(Event::Connected).is_err() {
+        return;
+    }
+
+    for line in BufReader::new(stream).lines() {
+        let event = match line {
+            Ok(line) => match serde_json::from_str(&line) {
+                Ok(envelope) => Event::Message(envelope),
+                Err(error) => Event::Failed(anyhow!("Invalid message received from workshopper: {}", error)),
+            },
+            Err(error) => Event::Failed(From::from(error)),
+        };
+
+        // If no one is listening, there's no point on reading the rest.
+        if sender.send(event).is_err() {
+            return;
+        }
+    }
+
+    let _ = sender.send(Event::Closed);
+}
+
+/// This function connects to our own socket, so the reading thread doesn't keep waiting for a workshopper that's never going to connect.
+fn wake_listener(ipc_channel: &str) {
+    if let Ok(name) = ipc_channel.to_ns_name::<GenericNamespaced>() {
+        let _ = LocalSocketStream::connect(name);
+    }
+}
+
+fn abort(child: &mut Child, ipc_channel: &str) {
+    let _ = child.kill();
+    wake_listener(ipc_channel);
+}
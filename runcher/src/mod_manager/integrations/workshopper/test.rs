@@ -0,0 +1,68 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for our copy of the workshopper protocol, against the messages workshopper generates.
+
+use std::fs::read_to_string;
+
+use super::*;
+use crate::mod_manager::integrations::PublishedCollection;
+
+#[test]
+fn test_decode_workshopper_messages() {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../workshopper/test_files/ipc_messages.jsonl");
+    let envelopes = read_to_string(path).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<Envelope>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert!(envelopes.iter().all(|envelope| envelope.version == PROTOCOL_VERSION));
+
+    let mut progress = vec![];
+    let mut responses = vec![];
+    let mut errors = vec![];
+    for envelope in envelopes {
+        match envelope.message {
+            Message::Progress(message) => progress.push((message.stage, message.current, message.total)),
+            Message::Response(response) => responses.push(response),
+            Message::Error(error) => errors.push(error),
+        }
+    }
+
+    assert_eq!(progress, vec![
+        (Stage::Started, 0, 0),
+        (Stage::PreparingConfig, 0, 0),
+        (Stage::PreparingContent, 0, 0),
+        (Stage::UploadingContent, 5, 10),
+        (Stage::UploadingPreview, 1, 2),
+        (Stage::CommittingChanges, 0, 0),
+        (Stage::Downloading, 3, 4),
+    ]);
+
+    // Responses are decoded by each request into its own type.
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0], Value::Null);
+
+    let collection: PublishedCollection = serde_json::from_value(responses[1].clone()).unwrap();
+    assert_eq!(collection.published_file_id, 123);
+    assert_eq!(collection.failed_items, vec![4]);
+    assert_eq!(collection.failed_removals, vec![5]);
+
+    assert_eq!(errors.iter().map(|error| error.kind).collect::<Vec<_>>(), vec![
+        ErrorKind::SteamInit,
+        ErrorKind::Workshop,
+        ErrorKind::InvalidInput,
+        ErrorKind::Io,
+        ErrorKind::Other,
+    ]);
+
+    assert_eq!(errors[0].to_string(), "Failed to connect to Steam: SteamAPI_Init failed. Make sure Steam is running and you own the game.");
+    assert_eq!(errors[4].to_string(), "Something else failed.");
+}
@@ -8,31 +8,44 @@
 // https://github.com/Frodo45127/runcher/blob/master/LICENSE.
 //---------------------------------------------------------------------------//
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use getset::*;
-use rayon::prelude::*;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
+use sha256::try_digest;
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::fs::{DirBuilder, File};
 use std::path::{Path, PathBuf};
 
-use rpfm_lib::files::pack::Pack;
-use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
+use rpfm_lib::binary::WriteBytes;
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType, supported_games::KEY_EMPIRE};
 use rpfm_lib::integrations::log::*;
 use rpfm_lib::utils::{path_to_absolute_path, path_to_absolute_string};
 
 use crate::mod_manager::SECONDARY_FOLDER_NAME;
+use crate::PACK_INDEX;
 use crate::settings_ui::game_config_path;
 
 use super::game_config::GameConfig;
+use super::mods::{Mod, ShareableMod};
+use super::pack_index::{DataFile, PackIndex, PackIndexCache, update_pack_index};
 use super::secondary_mods_path;
+use super::shared_load_order::{ca_launcher_mod_data_path, is_steam_id, SharedLoadOrder};
+
+#[cfg(test)] mod test;
 
 const FILE_NAME_START: &str = "last_load_order_";
 const FILE_NAME_END: &str = ".json";
 
+pub const CUSTOM_MOD_LIST_FILE_NAME: &str = "mod_list.txt";
+pub const USER_SCRIPT_FILE_NAME: &str = "user.script.txt";
+pub const USER_SCRIPT_EMPIRE_FILE_NAME: &str = "user.empire_script.txt";
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -50,15 +63,61 @@ pub struct LoadOrder {
     // Movie Packs. These are not reorderable, so we keep them in a separate list.
     movies: Vec<String>,
 
-    // List of Packs open for data checking. Not serialized.
+    // Indexes of the Packs in the load order, for data checking. Not serialized.
+    #[serde(skip_deserializing, skip_serializing)]
+    packs: HashMap<String, PackIndex>,
+
+    // Problems found while sorting the mods by their dependencies. Not serialized.
     #[serde(skip_deserializing, skip_serializing)]
-    packs: HashMap<String, Pack>,
+    dependency_issues: Vec<DependencyIssue>,
+}
+
+/// Problems that prevent a load order from being sorted correctly by dependencies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DependencyIssue {
+
+    /// A group of mods depends on each other, directly or not, so they cannot be sorted. Contains the affected mod ids.
+    Cycle(Vec<String>),
+
+    /// A mod depends, directly or not, on a group of mods that depend on each other, so it cannot be sorted either. Contains the mod id.
+    DependsOnCycle(String),
+}
+
+/// Dependency of an enabled mod that's not in the load order.
+#[derive(Clone, Debug, PartialEq, Eq, Getters)]
+#[getset(get = "pub")]
+pub struct MissingDependency {
+
+    // Id of the mod that requires the dependency.
+    mod_id: String,
+
+    // Pack name of the dependency.
+    pack_name: String,
+
+    // What we can do to get the dependency into the load order.
+    status: MissingDependencyStatus,
+}
+
+/// Reasons why a dependency may not be in the load order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MissingDependencyStatus {
+
+    /// The dependency is installed, but disabled. Contains the mod id of the dependency.
+    Disabled(String),
+
+    /// The dependency is a Workshop mod we know of, but it's not downloaded. Contains its Workshop id.
+    NotDownloaded(String),
+
+    /// We don't know where to get the dependency from.
+    NotFound,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ImportedLoadOrderMode {
     Runcher(String),
-    Modlist(String)
+    Modlist(String),
+    CALauncher(String),
+    Json(String),
 }
 
 //-------------------------------------------------------------------------------//
@@ -72,6 +131,26 @@ impl Default for LoadOrder {
             mods: vec![],
             movies: vec![],
             packs: HashMap::new(),
+            dependency_issues: vec![],
+        }
+    }
+}
+
+impl Display for DependencyIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cycle(mod_ids) => write!(f, "Mods {} depend on each other, so they cannot be sorted by their dependencies.", mod_ids.join(", ")),
+            Self::DependsOnCycle(mod_id) => write!(f, "Mod {} depends on mods that depend on each other, so it cannot be sorted by its dependencies.", mod_id),
+        }
+    }
+}
+
+impl Display for MissingDependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.status {
+            MissingDependencyStatus::Disabled(_) => write!(f, "Mod {} depends on {}, which is installed but disabled.", self.mod_id, self.pack_name),
+            MissingDependencyStatus::NotDownloaded(steam_id) => write!(f, "Mod {} depends on {}, which is not downloaded (Workshop id: {}).", self.mod_id, self.pack_name, steam_id),
+            MissingDependencyStatus::NotFound => write!(f, "Mod {} depends on {}, which is not installed.", self.mod_id, self.pack_name),
         }
     }
 }
@@ -106,6 +185,7 @@ impl LoadOrder {
 
     pub fn update(&mut self, game_config: &GameConfig, game_data_path: &Path) {
         self.movies.clear();
+        self.dependency_issues.clear();
 
         if self.automatic {
             self.build_automatic(game_config, game_data_path);
@@ -113,20 +193,35 @@ impl LoadOrder {
             self.build_manual(game_config, game_data_path);
         }
 
-        // After the order is built, reload the enabled packs.
-        self.packs.clear();
-        self.packs = self.mods.clone()
-            .into_par_iter()
-            .chain(self.movies.clone())
-            .filter_map(|mod_id| {
-                let modd = game_config.mods().get(&mod_id)?;
-                let path = modd.paths().first()?;
-                Some((mod_id.to_owned(), Pack::read_and_merge(&[path.to_path_buf()], true, false, false).ok()?))
-            })
-            .collect();
+        // After the order is built, reload the indexes of the enabled packs. Only the ones that changed are read again.
+        let paths = self.mods.iter()
+            .chain(self.movies.iter())
+            .filter_map(|mod_id| Some((mod_id, game_config.mods().get(mod_id)?.paths().first()?.to_path_buf())))
+            .collect::<Vec<_>>();
+
+        self.packs = {
+            update_pack_index(&paths.iter().map(|(_, path)| path.to_path_buf()).collect::<Vec<_>>(), false);
+
+            let pack_index = PACK_INDEX.read().unwrap();
+
+            paths.into_iter()
+                .filter_map(|(mod_id, path)| Some((mod_id.to_owned(), pack_index.get(&path)?.clone())))
+                .collect()
+        };
+
+        // Dependencies are read from the packs, so we can only sort by them once they're loaded.
+        if self.automatic {
+            self.sort_by_dependencies(game_config);
+
+            for issue in &self.dependency_issues {
+                warn!("{}", issue);
+            }
+        }
     }
 
     /// Automatic builds means the user input is ignored, and mods are sorted alphabetically.
+    ///
+    /// Once the packs are loaded, `update` sorts them again by their dependencies.
     pub fn build_automatic(&mut self, game_config: &GameConfig, game_data_path: &Path) {
         self.mods.clear();
 
@@ -159,8 +254,7 @@ impl LoadOrder {
             }
         });
 
-        // TODO: Automatically put parent mods above their children.
-        // TODO2: If it works how I think it works, the game loads parent mods twice:
+        // TODO: If it works how I think it works, the game loads parent mods twice:
         // - First, when the're loaded as a mod.
         // - Second, when a child mod tries to load.
         //
@@ -168,6 +262,210 @@ impl LoadOrder {
         // remove the parent mod from the final load order so it only loads once.
     }
 
+    /// This function sorts the mods so parent mods are above their children, using the current order to break ties.
+    ///
+    /// Requires the packs to be loaded. Dependency cycles are stored as issues. Dependencies not in the load order are ignored here, as [LoadOrder::missing_dependencies] reports them.
+    fn sort_by_dependencies(&mut self, game_config: &GameConfig) {
+
+        // Dependencies are declared by pack name, so we need to map them to their position in the current order.
+        let positions = self.mods.iter()
+            .enumerate()
+            .filter_map(|(index, mod_id)| {
+                let pack_name = game_config.mods().get(mod_id)?.paths().first()?.file_name()?.to_string_lossy().to_string();
+                Some((pack_name, index))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut children = vec![vec![]; self.mods.len()];
+        let mut parents_count = vec![0; self.mods.len()];
+        for (index, mod_id) in self.mods.iter().enumerate() {
+            if let Some(pack) = self.packs.get(mod_id) {
+                for (_, dependency) in pack.dependencies() {
+                    if let Some(parent) = positions.get(dependency) {
+                        if *parent != index {
+                            children[*parent].push(index);
+                            parents_count[index] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Always take the first mod in the current order with all its parents already sorted.
+        let mut available = (0..self.mods.len())
+            .filter(|index| parents_count[*index] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+
+        let mut sorted = Vec::with_capacity(self.mods.len());
+        while let Some(Reverse(index)) = available.pop() {
+            sorted.push(index);
+
+            for child in &children[index] {
+                parents_count[*child] -= 1;
+                if parents_count[*child] == 0 {
+                    available.push(Reverse(*child));
+                }
+            }
+        }
+
+        // Anything left is part of a cycle, or depends on one. Keep them at the end, in their current order.
+        if sorted.len() < self.mods.len() {
+            let left = (0..self.mods.len())
+                .filter(|index| parents_count[*index] > 0)
+                .collect::<Vec<_>>();
+
+            // Mods left can only have children left too, so we only need to check them to find which ones can reach themselves.
+            let reachable = left.iter()
+                .map(|index| {
+                    let mut visited = HashSet::new();
+                    let mut pending = children[*index].to_vec();
+                    while let Some(child) = pending.pop() {
+                        if visited.insert(child) {
+                            pending.extend_from_slice(&children[child]);
+                        }
+                    }
+
+                    (*index, visited)
+                })
+                .collect::<HashMap<_, _>>();
+
+            // Mods that can reach each other are part of the same cycle. The rest just depend on one.
+            let mut in_cycle = HashSet::new();
+            for index in &left {
+                if in_cycle.contains(index) || !reachable[index].contains(index) {
+                    continue;
+                }
+
+                let cycle = left.iter()
+                    .filter(|other| reachable[index].contains(*other) && reachable[*other].contains(index))
+                    .copied()
+                    .collect::<Vec<_>>();
+
+                self.dependency_issues.push(DependencyIssue::Cycle(cycle.iter().map(|index| self.mods[*index].to_owned()).collect()));
+                in_cycle.extend(cycle);
+            }
+
+            for index in &left {
+                if !in_cycle.contains(index) {
+                    self.dependency_issues.push(DependencyIssue::DependsOnCycle(self.mods[*index].to_owned()));
+                }
+            }
+
+            sorted.extend(left);
+        }
+
+        self.mods = sorted.iter().map(|index| self.mods[*index].to_owned()).collect();
+    }
+
+    /// This function returns the dependency issues affecting the provided mod.
+    pub fn dependency_issues_for_mod(&self, mod_id: &str) -> Vec<&DependencyIssue> {
+        self.dependency_issues.iter()
+            .filter(|issue| match issue {
+                DependencyIssue::Cycle(ids) => ids.iter().any(|id| id == mod_id),
+                DependencyIssue::DependsOnCycle(id) => id == mod_id,
+            })
+            .collect()
+    }
+
+    /// This function returns the dependencies of the mods in the load order that are not in the load order themselves, in load order.
+    ///
+    /// Requires the packs to be loaded.
+    pub fn missing_dependencies(&self, game_config: &GameConfig, game_data_path: &Path) -> Vec<MissingDependency> {
+
+        // Dependencies are declared by pack name, so we need to check both, the ids and the pack names of the loaded mods.
+        let loaded = self.mods.iter()
+            .chain(self.movies.iter())
+            .flat_map(|mod_id| {
+                let modd = game_config.mods().get(mod_id);
+                let pack_name = modd
+                    .and_then(|modd| modd.paths().first())
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().to_string());
+
+                vec![Some(mod_id.to_owned()), pack_name, modd.and_then(|modd| modd.steam_id().clone())]
+            })
+            .flatten()
+            .collect::<HashSet<_>>();
+
+        let mut missing = vec![];
+        for mod_id in &self.mods {
+            if let Some(pack) = self.packs.get(mod_id) {
+                for (_, dependency) in pack.dependencies() {
+                    if loaded.contains(dependency) {
+                        continue;
+                    }
+
+                    // Only mods we can actually enable count as disabled. Otherwise, enabling them would do nothing.
+                    let status = match Self::mod_by_pack_name(game_config, dependency) {
+                        Some(modd) if !modd.paths().is_empty() && !modd.enabled(game_data_path) && *modd.pack_type() == PFHFileType::Mod => MissingDependencyStatus::Disabled(modd.id().to_owned()),
+                        Some(modd) if modd.paths().is_empty() && modd.steam_id().is_some() => MissingDependencyStatus::NotDownloaded(modd.steam_id().clone().unwrap()),
+
+                        // Dependencies on Workshop items we don't know of yet can still be downloaded by their id.
+                        None if is_steam_id(dependency) => MissingDependencyStatus::NotDownloaded(dependency.to_owned()),
+                        _ => MissingDependencyStatus::NotFound,
+                    };
+
+                    missing.push(MissingDependency {
+                        mod_id: mod_id.to_owned(),
+                        pack_name: dependency.to_owned(),
+                        status,
+                    });
+                }
+            }
+        }
+
+        missing
+    }
+
+    /// This function enables all the installed dependencies missing from the load order, including the ones of the newly enabled mods.
+    ///
+    /// Returns the ids of the enabled mods. Remember to save both, the load order and the game config, after this.
+    pub fn enable_missing_dependencies(&mut self, game_config: &mut GameConfig, game_data_path: &Path) -> Vec<String> {
+        let mut enabled = vec![];
+
+        loop {
+            let mod_ids = self.missing_dependencies(game_config, game_data_path)
+                .into_iter()
+                .filter_map(|dependency| match dependency.status {
+                    MissingDependencyStatus::Disabled(mod_id) => Some(mod_id),
+                    _ => None,
+                })
+                .filter(|mod_id| !enabled.contains(mod_id))
+                .unique()
+                .collect::<Vec<_>>();
+
+            if mod_ids.is_empty() {
+                break;
+            }
+
+            for mod_id in &mod_ids {
+                if let Some(modd) = game_config.mods_mut().get_mut(mod_id) {
+                    modd.set_enabled(true);
+                }
+            }
+
+            // Reload the load order so the dependencies of the newly enabled mods get checked too.
+            enabled.extend(mod_ids);
+            self.update(game_config, game_data_path);
+        }
+
+        enabled
+    }
+
+    /// This function finds a mod by its pack name, falling back to the name of its pack file and its Workshop id if the id doesn't match.
+    fn mod_by_pack_name<'a>(game_config: &'a GameConfig, pack_name: &str) -> Option<&'a Mod> {
+        game_config.mods().get(pack_name).or_else(|| game_config.mods()
+            .values()
+            .find(|modd| modd.paths()
+                .first()
+                .and_then(|path| path.file_name())
+                .map(|name| name.to_string_lossy() == pack_name)
+                .unwrap_or(false) || modd.steam_id().as_deref() == Some(pack_name)
+            )
+        )
+    }
+
     /// Manual builds means keep the current order, remove deleted mods, and add new ones to the end.
     ///
     /// The user will take care of the rest of the re-ordering.
@@ -301,4 +599,210 @@ impl LoadOrder {
 
         folder_paths.push_str(&folder_paths_mods);
     }
+
+    /// This function returns the files of the vanilla packs and the packs of the load order, as the game sees them, by their lowercased path.
+    ///
+    /// Requires the packs to be loaded. Vanilla packs are indexed if needed.
+    pub fn data_files(&self, game_config: &GameConfig, game: &GameInfo, game_path: &Path) -> Result<HashMap<String, DataFile>> {
+
+        // Only load this if the game path is actually a path.
+        if game_path.exists() && game_path.is_dir() {
+
+            let vanilla_paths = game.ca_packs_paths(game_path)?;
+            update_pack_index(&vanilla_paths, true);
+
+            let pack_index = PACK_INDEX.read().unwrap();
+
+            // The first pack containing a file is the one the game uses.
+            let mut files = HashMap::new();
+            for (pack_name, pack) in self.packs_by_priority(game_config, &vanilla_paths, &pack_index) {
+                for file in pack.files() {
+                    files.entry(file.path().to_lowercase()).or_insert_with(|| DataFile::new(file.path(), &pack_name, *file.file_type()));
+                }
+            }
+
+            Ok(files)
+        } else {
+            Err(anyhow!("Game Path not found."))
+        }
+    }
+
+    /// This function returns the vanilla packs and the packs of the load order with their indexes, from higher to lower priority.
+    ///
+    /// Vanilla packs not found in the provided index are skipped. See [packs_by_priority] for the rules.
+    pub fn packs_by_priority<'a>(&'a self, game_config: &GameConfig, vanilla_paths: &[PathBuf], pack_index: &'a PackIndexCache) -> Vec<(String, &'a PackIndex)> {
+        let pack_name = |mod_id: &String| Some(game_config.mods().get(mod_id)?.paths().first()?.file_name()?.to_string_lossy().to_string());
+
+        let base_packs = vanilla_paths.iter()
+            .filter_map(|path| Some((path.file_name()?.to_string_lossy().to_string(), pack_index.get(path)?)))
+            .chain(self.movies.iter().filter_map(|mod_id| Some((pack_name(mod_id)?, self.packs.get(mod_id)?))))
+            .map(|(pack_name, pack)| (pack_name, *pack.pack_type(), pack))
+            .collect::<Vec<_>>();
+
+        let mods = self.mods.iter()
+            .filter_map(|mod_id| Some((pack_name(mod_id)?, self.packs.get(mod_id)?)))
+            .collect::<Vec<_>>();
+
+        packs_by_priority(base_packs, mods)
+    }
+
+    /// This function returns the paths of the packs in the load order, in the order the game loads them.
+    ///
+    /// Movie packs are always last.
+    pub fn pack_paths(&self, game_config: &GameConfig) -> Vec<PathBuf> {
+        self.mods().iter()
+            .chain(self.movies().iter())
+            .filter_map(|mod_id| game_config.mods().get(mod_id))
+            .filter_map(|modd| modd.paths().first())
+            .filter(|path| path.extension().map(|ext| ext == "pack").unwrap_or(false))
+            .cloned()
+            .collect()
+    }
+
+    /// This function returns the path of the file the game reads its mod list from.
+    ///
+    /// NOTE: On Empire and Napoleon we need to use the user_script, not the custom file, as it doesn't seem to work.
+    /// Older versions of shogun 2 also used the user_script, but the latest update enabled use of custom mod lists.
+    pub fn mod_list_file_path(game: &GameInfo, game_path: &Path) -> Result<PathBuf> {
+        if *game.raw_db_version() >= 1 {
+            Ok(game_path.join(CUSTOM_MOD_LIST_FILE_NAME))
+        } else {
+
+            // Games may fail to launch if we don't have this path created, which is done the first time we start the game.
+            let config_path = game.config_path(game_path).ok_or(anyhow!("Error getting the game's config path."))?;
+            let scripts_path = config_path.join("scripts");
+            DirBuilder::new().recursive(true).create(&scripts_path)?;
+
+            // Empire has its own user script.
+            if game.key() == KEY_EMPIRE {
+                Ok(scripts_path.join(USER_SCRIPT_EMPIRE_FILE_NAME))
+            } else {
+                Ok(scripts_path.join(USER_SCRIPT_FILE_NAME))
+            }
+        }
+    }
+
+    /// This function writes the provided folder and pack lists to the file the game reads its mod list from.
+    pub fn save_mod_list_file(game: &GameInfo, game_path: &Path, folder_list: &str, pack_list: &str) -> Result<()> {
+        let file_path = Self::mod_list_file_path(game, game_path)?;
+        Self::write_mod_list_file(game, &file_path, folder_list, pack_list)
+    }
+
+    /// This function writes the enabled mods of the load order to the mod data file of the official launcher, so the same mods are used when launching the game without Runcher.
+    ///
+    /// Returns the path of the file.
+    pub fn export_to_ca_launcher(&self, game_config: &GameConfig, game: &GameInfo, game_data_path: &Path) -> Result<PathBuf> {
+        let file_path = ca_launcher_mod_data_path(game)?;
+        let mod_data = if file_path.is_file() {
+            Some(std::fs::read_to_string(&file_path)?)
+        } else {
+            None
+        };
+
+        let shared = SharedLoadOrder::new(game, game_config, self, game_data_path, None);
+        let mod_data = shared.to_ca_launcher(game, game_config, mod_data.as_deref())?;
+
+        // Write it to a temp file first, so we don't leave the launcher with a broken file if something fails.
+        if let Some(parent_folder) = file_path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let temp_path = file_path.with_extension("dat.tmp");
+        let mut file = BufWriter::new(File::create(&temp_path)?);
+        file.write_all(mod_data.as_bytes())?;
+        file.flush()?;
+        drop(file);
+
+        std::fs::rename(&temp_path, &file_path)?;
+        Ok(file_path)
+    }
+
+    fn write_mod_list_file(game: &GameInfo, file_path: &Path, folder_list: &str, pack_list: &str) -> Result<()> {
+        let mut file = BufWriter::new(File::create(file_path)?);
+
+        // Napoleon, Empire and Shogun 2 require the user.script.txt or mod list file (for Shogun's latest update) to be in UTF-16 LE. What the actual fuck.
+        if *game.raw_db_version() < 2 {
+            file.write_string_u16(folder_list)?;
+            file.write_string_u16(pack_list)?;
+        } else {
+            file.write_all(folder_list.as_bytes())?;
+            file.write_all(pack_list.as_bytes())?;
+        }
+
+        file.flush()?;
+        Ok(())
+    }
+
+    /// This function replaces the current load order with the one from the provided list of mods.
+    ///
+    /// All mods not in the list are disabled, and the order is turned manual to respect the order of the list.
+    /// Returns the mods we couldn't find, and the ones we found but whose packs are different than the expected ones.
+    pub fn import_shareable_mod_list(&mut self, game_config: &mut GameConfig, game_data_path: &Path, shareable_mod_list: &[ShareableMod]) -> Result<(Vec<ShareableMod>, Vec<ShareableMod>)> {
+
+        // Before we begin, we need to set all mods to disable. Otherwise, new load orders would get mods mixed up.
+        game_config.mods_mut().iter_mut().for_each(|(_, modd)| { modd.set_enabled(false); });
+
+        let mut missing = vec![];
+        let mut wrong_hash = vec![];
+        let mut ids = vec![];
+
+        for modd in shareable_mod_list {
+
+            // Mods imported from other launchers may only be identifiable by their Workshop id.
+            let local_id = if game_config.mods().contains_key(modd.id()) {
+                Some(modd.id().to_owned())
+            } else {
+                modd.steam_id().as_ref().and_then(|steam_id| game_config.mods()
+                    .values()
+                    .find(|modd_local| modd_local.steam_id().as_ref() == Some(steam_id))
+                    .map(|modd_local| modd_local.id().to_owned()))
+            };
+
+            match local_id.and_then(|local_id| game_config.mods_mut().get_mut(&local_id)) {
+                Some(modd_local) => {
+                    if let Some(path) = modd_local.paths().first() {
+                        if !modd.hash().is_empty() {
+                            let current_hash = try_digest(path.as_path())?;
+                            if &current_hash != modd.hash() {
+                                wrong_hash.push(modd.clone());
+                            }
+                        }
+
+                        modd_local.set_enabled(true);
+                        ids.push(modd_local.id().to_owned());
+                    } else {
+
+                        // Mods we know of but are not downloaded cannot be loaded either.
+                        missing.push(modd.clone());
+                    }
+                },
+                None => missing.push(modd.clone()),
+            }
+        }
+
+        // Once we're done updating the game config, we need to update the load order.
+        //
+        // We need manual order to respect the provided load order, as it may not be automatic.
+        self.mods = ids;
+        self.automatic = false;
+        self.update(game_config, game_data_path);
+
+        Ok((missing, wrong_hash))
+    }
+}
+
+/// This function sorts the provided packs from higher to lower priority, the same way the game resolves a file present in more than one of them.
+///
+/// Base packs (vanilla and movie packs) are sorted by type, then by name, and the ones with the higher type or name win.
+/// Mods go after the movie packs and before the rest, and the mods at the top of the load order win over the ones below them.
+pub fn packs_by_priority<T>(mut base_packs: Vec<(String, PFHFileType, T)>, mods: Vec<(String, T)>) -> Vec<(String, T)> {
+    base_packs.sort_by(|(name_a, type_a, _), (name_b, type_b, _)| type_b.cmp(type_a).then_with(|| name_b.cmp(name_a)));
+
+    let movies_len = base_packs.iter().take_while(|(_, pack_type, _)| *pack_type == PFHFileType::Movie).count();
+    let mut base_packs = base_packs.into_iter().map(|(name, _, pack)| (name, pack)).collect::<Vec<_>>();
+    let rest = base_packs.split_off(movies_len);
+
+    base_packs.extend(mods);
+    base_packs.extend(rest);
+    base_packs
 }
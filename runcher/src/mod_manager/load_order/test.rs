@@ -0,0 +1,219 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the load order sorting and the priority between its packs.
+
+use super::*;
+
+/// This function builds a load order with the provided mods, in their current order, and the packs they depend on. Mod ids are their pack names.
+fn load_order_with_dependencies(mods: &[(&str, &[&str])]) -> (LoadOrder, GameConfig) {
+    let mut game_config = GameConfig::default();
+    let mut load_order = LoadOrder::default();
+
+    for (pack_name, dependencies) in mods {
+        let mut modd = Mod::default();
+        modd.set_id(pack_name.to_string());
+        modd.set_pack_type(PFHFileType::Mod);
+        modd.set_paths(vec![PathBuf::from("/mods").join(pack_name)]);
+        modd.set_enabled(true);
+
+        game_config.mods_mut().insert(pack_name.to_string(), modd);
+        load_order.mods_mut().push(pack_name.to_string());
+        load_order.packs_mut().insert(pack_name.to_string(), PackIndex::new_mod(dependencies));
+    }
+
+    (load_order, game_config)
+}
+
+fn ids(mod_ids: &[&str]) -> Vec<String> {
+    mod_ids.iter().map(|mod_id| mod_id.to_string()).collect()
+}
+
+fn names<T>(packs: &[(String, T)]) -> Vec<&str> {
+    packs.iter().map(|(name, _)| name.as_str()).collect()
+}
+
+#[test]
+fn test_packs_by_priority() {
+    let base_packs = vec![
+        ("data.pack".to_owned(), PFHFileType::Release, ()),
+        ("movies.pack".to_owned(), PFHFileType::Movie, ()),
+        ("patch.pack".to_owned(), PFHFileType::Patch, ()),
+        ("boot.pack".to_owned(), PFHFileType::Boot, ()),
+        ("data_2.pack".to_owned(), PFHFileType::Release, ()),
+        ("my_movie_mod.pack".to_owned(), PFHFileType::Movie, ()),
+    ];
+
+    let mods = vec![
+        ("z_top_mod.pack".to_owned(), ()),
+        ("a_bottom_mod.pack".to_owned(), ()),
+    ];
+
+    let packs = packs_by_priority(base_packs, mods);
+    assert_eq!(names(&packs), vec![
+        "my_movie_mod.pack",
+        "movies.pack",
+        "z_top_mod.pack",
+        "a_bottom_mod.pack",
+        "patch.pack",
+        "data_2.pack",
+        "data.pack",
+        "boot.pack",
+    ]);
+}
+
+#[test]
+fn test_packs_by_priority_without_movies() {
+    let base_packs = vec![("data.pack".to_owned(), PFHFileType::Release, ())];
+    let mods = vec![("my_mod.pack".to_owned(), ())];
+
+    let packs = packs_by_priority(base_packs, mods);
+    assert_eq!(names(&packs), vec!["my_mod.pack", "data.pack"]);
+}
+
+#[test]
+fn test_sort_by_dependencies_chain() {
+    let (mut load_order, game_config) = load_order_with_dependencies(&[
+        ("a.pack", &["b.pack"]),
+        ("b.pack", &["c.pack"]),
+        ("c.pack", &[]),
+        ("d.pack", &[]),
+    ]);
+
+    // Parents go first. Otherwise, the current order is kept.
+    load_order.sort_by_dependencies(&game_config);
+    assert_eq!(load_order.mods(), &ids(&["c.pack", "b.pack", "a.pack", "d.pack"]));
+    assert!(load_order.dependency_issues().is_empty());
+}
+
+#[test]
+fn test_sort_by_dependencies_cycle() {
+    let (mut load_order, game_config) = load_order_with_dependencies(&[
+        ("a.pack", &["b.pack"]),
+        ("b.pack", &["a.pack"]),
+        ("c.pack", &[]),
+    ]);
+
+    // Mods in a cycle go at the end, in their current order.
+    load_order.sort_by_dependencies(&game_config);
+    assert_eq!(load_order.mods(), &ids(&["c.pack", "a.pack", "b.pack"]));
+    assert_eq!(load_order.dependency_issues(), &vec![DependencyIssue::Cycle(ids(&["a.pack", "b.pack"]))]);
+}
+
+#[test]
+fn test_sort_by_dependencies_dependent_of_cycle() {
+    let (mut load_order, game_config) = load_order_with_dependencies(&[
+        ("a.pack", &["b.pack"]),
+        ("b.pack", &["c.pack"]),
+        ("c.pack", &["a.pack"]),
+        ("d.pack", &["c.pack"]),
+        ("e.pack", &["d.pack"]),
+        ("f.pack", &[]),
+    ]);
+
+    // Only the mods in the cycle are reported as part of it.
+    load_order.sort_by_dependencies(&game_config);
+    assert_eq!(load_order.mods(), &ids(&["f.pack", "a.pack", "b.pack", "c.pack", "d.pack", "e.pack"]));
+    assert_eq!(load_order.dependency_issues(), &vec![
+        DependencyIssue::Cycle(ids(&["a.pack", "b.pack", "c.pack"])),
+        DependencyIssue::DependsOnCycle("d.pack".to_owned()),
+        DependencyIssue::DependsOnCycle("e.pack".to_owned()),
+    ]);
+
+    assert_eq!(load_order.dependency_issues_for_mod("b.pack").len(), 1);
+    assert_eq!(load_order.dependency_issues_for_mod("e.pack").len(), 1);
+    assert!(load_order.dependency_issues_for_mod("f.pack").is_empty());
+}
+
+#[test]
+fn test_sort_by_dependencies_missing() {
+    let (mut load_order, game_config) = load_order_with_dependencies(&[
+        ("a.pack", &["missing.pack", "b.pack"]),
+        ("b.pack", &[]),
+    ]);
+
+    // Missing dependencies don't affect the sorting. They're reported apart.
+    load_order.sort_by_dependencies(&game_config);
+    assert_eq!(load_order.mods(), &ids(&["b.pack", "a.pack"]));
+    assert!(load_order.dependency_issues().is_empty());
+
+    assert_eq!(load_order.missing_dependencies(&game_config, Path::new("/game/data")), vec![MissingDependency {
+        mod_id: "a.pack".to_owned(),
+        pack_name: "missing.pack".to_owned(),
+        status: MissingDependencyStatus::NotFound,
+    }]);
+}
+
+#[test]
+fn test_import_shareable_mod_list() {
+    let (mut load_order, mut game_config) = load_order_with_dependencies(&[("a.pack", &[]), ("b.pack", &[])]);
+
+    let mut not_downloaded = Mod::default();
+    not_downloaded.set_id("c.pack".to_owned());
+    not_downloaded.set_pack_type(PFHFileType::Mod);
+    not_downloaded.set_steam_id(Some("1234".to_owned()));
+    game_config.mods_mut().insert("c.pack".to_owned(), not_downloaded);
+
+    let shareable = |id: &str, steam_id: Option<&str>| {
+        let mut modd = ShareableMod::default();
+        modd.set_id(id.to_owned());
+        modd.set_steam_id(steam_id.map(|steam_id| steam_id.to_owned()));
+        modd
+    };
+
+    // Mods we know of but are not downloaded are as missing as the ones we don't know of.
+    let (missing, wrong_hash) = load_order.import_shareable_mod_list(&mut game_config, Path::new("/game/data"), &[
+        shareable("1234", Some("1234")),
+        shareable("a.pack", None),
+        shareable("unknown.pack", None),
+    ]).unwrap();
+
+    assert_eq!(missing.iter().map(|modd| modd.id().as_str()).collect::<Vec<_>>(), vec!["1234", "unknown.pack"]);
+    assert!(wrong_hash.is_empty());
+    assert_eq!(load_order.mods(), &ids(&["a.pack"]));
+    assert!(!game_config.mods()["b.pack"].enabled(Path::new("/game/data")));
+    assert!(!*load_order.automatic());
+}
+
+#[test]
+fn test_missing_dependencies() {
+    let (load_order, mut game_config) = load_order_with_dependencies(&[
+        ("a.pack", &["disabled.pack", "not_downloaded.pack", "1234", "unknown.pack"]),
+        ("b.pack", &["a.pack", "5678"]),
+    ]);
+
+    let mut disabled = Mod::default();
+    disabled.set_id("disabled.pack".to_owned());
+    disabled.set_pack_type(PFHFileType::Mod);
+    disabled.set_paths(vec![PathBuf::from("/mods/disabled.pack")]);
+    game_config.mods_mut().insert("disabled.pack".to_owned(), disabled);
+
+    let mut not_downloaded = Mod::default();
+    not_downloaded.set_id("not_downloaded.pack".to_owned());
+    not_downloaded.set_pack_type(PFHFileType::Mod);
+    not_downloaded.set_steam_id(Some("9999".to_owned()));
+    game_config.mods_mut().insert("not_downloaded.pack".to_owned(), not_downloaded);
+
+    // Dependencies on loaded mods can also use their Workshop id.
+    game_config.mods_mut().get_mut("a.pack").unwrap().set_steam_id(Some("5678".to_owned()));
+
+    let missing = load_order.missing_dependencies(&game_config, Path::new("/game/data"));
+    let statuses = missing.iter()
+        .map(|dependency| (dependency.mod_id().as_str(), dependency.pack_name().as_str(), dependency.status().clone()))
+        .collect::<Vec<_>>();
+
+    // Workshop ids we don't know of can still be downloaded.
+    assert_eq!(statuses, vec![
+        ("a.pack", "disabled.pack", MissingDependencyStatus::Disabled("disabled.pack".to_owned())),
+        ("a.pack", "not_downloaded.pack", MissingDependencyStatus::NotDownloaded("9999".to_owned())),
+        ("a.pack", "1234", MissingDependencyStatus::NotDownloaded("1234".to_owned())),
+        ("a.pack", "unknown.pack", MissingDependencyStatus::NotFound),
+    ]);
+}
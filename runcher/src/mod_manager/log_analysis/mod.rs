@@ -0,0 +1,239 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the code to find script breaks in the game's script logs, and the mods that may have caused them.
+
+use anyhow::Result;
+use encoding_rs::WINDOWS_1252;
+use getset::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use rpfm_lib::games::GameInfo;
+use rpfm_lib::utils::files_from_subdir;
+
+use super::game_config::GameConfig;
+use super::pack_index::DataFile;
+
+#[cfg(test)] mod test;
+
+/// Start of a normal script error. It ends with a line of asterisks.
+const SCRIPT_ERROR: &str = "SCRIPT ERROR, timestamp";
+const SCRIPT_ERROR_END: &str = "********************";
+
+/// Start of an error reported by PJ's error wrapper. It ends in the third log line after the start.
+const BIG_FAT_SCRIPT_ERROR: &str = "BIG FAT SCRIPT ERROR";
+const LOG_LINE_START: &str = "[out]";
+
+/// Start of the errors of the mod loader. They end when the loader reports the mod as not loaded.
+const FAILED_TO_LOAD_MOD_FILE: &str = "Failed to load mod file";
+const FAILED_TO_EXECUTE_MOD_FILE: &str = "Failed to execute loaded mod file";
+const FAILED_TO_LOAD_MOD_END: &str = "Failed to load mod:";
+
+/// PJ's mods use requires that fail when the CA loader does its thing, so we need to ignore them.
+const IGNORED_FAILED_TO_LOAD_MOD_FILE: &str = "Failed to load mod file [script\\campaign\\mod\\pj_";
+
+/// Delimiters of the paths of the scripts in the stack traces.
+const SCRIPT_PATH_START: &str = "[string \"";
+const SCRIPT_PATH_END: &str = "\"]:";
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Script break found in a log.
+#[derive(Clone, Debug, Default, PartialEq, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct ScriptBreak {
+
+    // Type of error reported in the log.
+    kind: ScriptBreakKind,
+
+    // Part of the log with the error.
+    full_log: String,
+
+    // Paths of the scripts in the error, in the order they're in the log. Uses forward slashes.
+    script_paths: Vec<String>,
+
+    // Pack that may have caused the error. Empty if we couldn't find it.
+    posible_pack: String,
+
+    // Name of the mod of the pack that may have caused the error.
+    posible_pack_mod: String,
+
+    // Workshop link of the mod that may have caused the error.
+    posible_pack_link: Option<String>,
+}
+
+/// Types of errors we can find in a log.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScriptBreakKind {
+
+    /// Error caught by the game's event handlers.
+    #[default]
+    ScriptError,
+
+    /// Error caught by PJ's error wrapper.
+    BigFatScriptError,
+
+    /// Error while loading a mod script.
+    FailedToLoadModFile,
+
+    /// Error while executing a mod script after loading it.
+    FailedToExecuteModFile,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl ScriptBreak {
+
+    fn new(kind: ScriptBreakKind, full_log: &str) -> Self {
+        Self {
+            kind,
+            full_log: full_log.to_owned(),
+            script_paths: script_paths(full_log),
+            ..Default::default()
+        }
+    }
+
+    /// This function tries to find the mod that caused the break.
+    ///
+    /// We blame the pack of the first script of the break that's not vanilla. This only works if the pack is still in the load order.
+    pub fn blame(&mut self, data_files: &HashMap<String, DataFile>, vanilla_pack_names: &[String], game_config: &GameConfig) {
+        for path in &self.script_paths {
+            if let Some(file) = data_files.get(&path.to_lowercase()) {
+                let pack_name = file.pack_name();
+                if !pack_name.is_empty() && vanilla_pack_names.iter().all(|vanilla_pack_name| vanilla_pack_name != pack_name) {
+                    self.posible_pack = pack_name.to_owned();
+
+                    // This is only valid in newer games!!!
+                    let modd = game_config.mods().get(pack_name);
+                    self.posible_pack_mod = modd
+                        .map(|modd| modd.name().to_string())
+                        .unwrap_or_default();
+                    self.posible_pack_link = modd
+                        .and_then(|modd| modd.steam_id().clone())
+                        .map(|id| format!("https://steamcommunity.com/sharedfiles/filedetails/?id={}", id));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// This function returns the script breaks found in the provided log.
+pub fn parse_log(data: &str) -> Vec<ScriptBreak> {
+    let mut breaks = vec![];
+
+    for (start, _) in data.match_indices(SCRIPT_ERROR) {
+        if let Some(end) = data[start..].find(SCRIPT_ERROR_END) {
+            breaks.push(ScriptBreak::new(ScriptBreakKind::ScriptError, &data[start..start + end]));
+        }
+    }
+
+    for (start, _) in data.match_indices(BIG_FAT_SCRIPT_ERROR) {
+        if let Some(end) = nth_line_start(data, start, 3) {
+            breaks.push(ScriptBreak::new(ScriptBreakKind::BigFatScriptError, &data[start..end]));
+        }
+    }
+
+    for (marker, kind) in [(FAILED_TO_LOAD_MOD_FILE, ScriptBreakKind::FailedToLoadModFile), (FAILED_TO_EXECUTE_MOD_FILE, ScriptBreakKind::FailedToExecuteModFile)] {
+        for (start, _) in data.match_indices(marker) {
+            if let Some(end) = data[start..].find(FAILED_TO_LOAD_MOD_END) {
+                let message = &data[start..start + end];
+                if message.contains(IGNORED_FAILED_TO_LOAD_MOD_FILE) {
+                    continue;
+                }
+
+                breaks.push(ScriptBreak::new(kind, message));
+            }
+        }
+    }
+
+    breaks
+}
+
+/// This function reads a log, falling back to windows-1252 if it's not valid UTF-8, as some mods write their logs with it.
+pub fn read_log(path: &Path) -> Result<String> {
+    let mut data = vec![];
+    let mut file = BufReader::new(File::open(path)?);
+    file.read_to_end(&mut data)?;
+
+    Ok(decode_log(data))
+}
+
+/// This function turns the bytes of a log into a string, falling back to windows-1252 if they're not valid UTF-8.
+pub fn decode_log(data: Vec<u8>) -> String {
+    match String::from_utf8(data) {
+        Ok(data) => data,
+        Err(error) => WINDOWS_1252.decode_without_bom_handling(error.as_bytes()).0.into_owned(),
+    }
+}
+
+/// This function returns the logs in the game folder modified after the provided date.
+pub fn logs_modified_since(game_path: &Path, start_date: &SystemTime) -> Result<Vec<PathBuf>> {
+    Ok(files_from_subdir(game_path, false)?
+        .into_iter()
+        .filter(|path| path.extension().map(|extension| extension == "txt").unwrap_or(false))
+        .filter(|path| path.metadata().and_then(|metadata| metadata.modified()).map(|modified| modified > *start_date).unwrap_or(false))
+        .collect())
+}
+
+/// This function returns the names of the vanilla packs of the game, as we shouldn't blame them for any error.
+pub fn vanilla_pack_names(game: &GameInfo, game_path: &Path) -> Result<Vec<String>> {
+    Ok(game.ca_packs_paths(game_path)?
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect())
+}
+
+/// This function parses the provided logs, and tries to find the mods responsible for each break found in them.
+pub fn analyze_logs(paths: &[PathBuf], data_files: &HashMap<String, DataFile>, vanilla_pack_names: &[String], game_config: &GameConfig) -> Result<Vec<ScriptBreak>> {
+    let mut breaks = vec![];
+    for path in paths {
+        let mut log_breaks = parse_log(&read_log(path)?);
+        log_breaks.iter_mut().for_each(|script_break| script_break.blame(data_files, vanilla_pack_names, game_config));
+        breaks.append(&mut log_breaks);
+    }
+
+    Ok(breaks)
+}
+
+/// This function returns the position where the nth log line after the provided position starts.
+fn nth_line_start(data: &str, start: usize, nth: usize) -> Option<usize> {
+    let mut position = start;
+    for index in 0..nth {
+
+        // Skip the marker of the previous line, so we don't find it again.
+        let offset = if index == 0 { 0 } else { LOG_LINE_START.len() };
+        position += offset + data[position + offset..].find(LOG_LINE_START)?;
+    }
+
+    Some(position)
+}
+
+/// This function returns the paths of all the scripts in an error, with forward slashes.
+fn script_paths(message: &str) -> Vec<String> {
+    message.match_indices(SCRIPT_PATH_START)
+        .filter_map(|(start, _)| {
+            let start = start + SCRIPT_PATH_START.len();
+            let end = message[start..].find(SCRIPT_PATH_END)?;
+            Some(message[start..start + end].replace('\\', "/"))
+        })
+        .collect()
+}
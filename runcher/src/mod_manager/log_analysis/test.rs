@@ -0,0 +1,132 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the log analysis module.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use rpfm_lib::files::FileType;
+
+use super::*;
+
+fn log_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_files/logs").join(name)
+}
+
+#[test]
+fn test_parse_script_error() {
+    let data = read_log(&log_path("script_error.txt")).unwrap();
+    let breaks = parse_log(&data);
+
+    assert_eq!(breaks.len(), 1);
+    assert_eq!(breaks[0].kind(), &ScriptBreakKind::ScriptError);
+    assert!(breaks[0].full_log().starts_with("SCRIPT ERROR, timestamp <375.0s>"));
+    assert!(breaks[0].full_log().trim_end().ends_with("in function 'event_protected_callback'"));
+    assert!(!breaks[0].full_log().contains("has ended its turn"));
+
+    assert_eq!(breaks[0].script_paths().len(), 6);
+    assert_eq!(breaks[0].script_paths()[0], "script/campaign/dynamic_disasters/disaster_the_great_bastion_improved.lua");
+    assert_eq!(breaks[0].script_paths()[3], "script/_lib/lib_core.lua");
+}
+
+#[test]
+fn test_parse_big_fat_script_error() {
+    let data = read_log(&log_path("big_fat_script_error.txt")).unwrap();
+    let breaks = parse_log(&data);
+
+    assert_eq!(breaks.len(), 1);
+    assert_eq!(breaks[0].kind(), &ScriptBreakKind::BigFatScriptError);
+    assert!(breaks[0].full_log().starts_with("BIG FAT SCRIPT ERROR"));
+    assert!(breaks[0].full_log().contains("in function 'xpcall'"));
+    assert!(!breaks[0].full_log().contains("Removing effect bundle"));
+
+    assert_eq!(breaks[0].script_paths(), &vec![
+        "script/campaign/mod/meh_blightwing_duchy_campaign_features.lua".to_owned(),
+        "script/_lib/mod/pj_error_wrapping.lua".to_owned(),
+        "script/_lib/lib_core.lua".to_owned(),
+        "script/_lib/lib_core.lua".to_owned(),
+    ]);
+}
+
+#[test]
+fn test_parse_failed_to_load_mod() {
+    let data = read_log(&log_path("failed_to_load_mod.txt")).unwrap();
+    let breaks = parse_log(&data);
+
+    // The error of PJ's loader must be ignored.
+    assert_eq!(breaks.len(), 2);
+    assert!(breaks.iter().all(|script_break| !script_break.full_log().contains("pj_loadorder")));
+
+    assert_eq!(breaks[0].kind(), &ScriptBreakKind::FailedToLoadModFile);
+    assert_eq!(breaks[0].script_paths(), &vec!["script/campaign/mod/test_errors_1.lua".to_owned()]);
+
+    assert_eq!(breaks[1].kind(), &ScriptBreakKind::FailedToExecuteModFile);
+    assert_eq!(breaks[1].script_paths(), &vec!["script/campaign/mod/test_error_3.lua".to_owned()]);
+}
+
+#[test]
+fn test_parse_windows_1252() {
+    let data = read_log(&log_path("windows_1252.txt")).unwrap();
+    assert!(data.contains("Café du Château"));
+
+    let breaks = parse_log(&data);
+    assert_eq!(breaks.len(), 1);
+    assert_eq!(breaks[0].script_paths(), &vec!["script/campaign/mod/clockwork_régiment.lua".to_owned()]);
+}
+
+#[test]
+fn test_decode_log() {
+    assert_eq!(decode_log("Château".as_bytes().to_vec()), "Château");
+    assert_eq!(decode_log(vec![0x43, 0x68, 0xe2, 0x74, 0x65, 0x61, 0x75]), "Château");
+}
+
+#[test]
+fn test_parse_empty_log() {
+    assert!(parse_log("").is_empty());
+    assert!(parse_log("[out] <0.1s>  Nothing to see here.\n").is_empty());
+
+    // Unterminated errors are ignored, as the game may still be writing them.
+    assert!(parse_log("SCRIPT ERROR, timestamp <1.0s>\n[string \"script\\a.lua\"]:1: error").is_empty());
+}
+
+#[test]
+fn test_blame_without_packs() {
+    let data = read_log(&log_path("script_error.txt")).unwrap();
+    let mut breaks = parse_log(&data);
+    breaks[0].blame(&HashMap::new(), &[], &GameConfig::default());
+
+    assert!(breaks[0].posible_pack().is_empty());
+    assert!(breaks[0].posible_pack_mod().is_empty());
+    assert!(breaks[0].posible_pack_link().is_none());
+}
+
+#[test]
+fn test_blame() {
+    let data = read_log(&log_path("script_error.txt")).unwrap();
+    let mut breaks = parse_log(&data);
+
+    let mut data_files = HashMap::new();
+    for (path, pack_name) in [
+        ("script/_lib/lib_core.lua", "data.pack"),
+        ("script/campaign/dynamic_disasters/disaster_the_great_bastion_improved.lua", "dynamic_disasters.pack"),
+    ] {
+        data_files.insert(path.to_owned(), DataFile::new(path, pack_name, FileType::Text));
+    }
+
+    breaks[0].blame(&data_files, &["data.pack".to_owned()], &GameConfig::default());
+    assert_eq!(breaks[0].posible_pack(), "dynamic_disasters.pack");
+    assert!(breaks[0].posible_pack_link().is_none());
+
+    // Vanilla packs are never blamed, even if their scripts are in the error.
+    let mut breaks = parse_log(&data);
+    breaks[0].blame(&data_files, &["data.pack".to_owned(), "dynamic_disasters.pack".to_owned()], &GameConfig::default());
+    assert!(breaks[0].posible_pack().is_empty());
+}
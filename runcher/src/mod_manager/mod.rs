@@ -16,9 +16,10 @@ use anyhow::{anyhow, Result};
 
 use std::fs::{DirBuilder, File};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use rpfm_lib::games::GameInfo;
+use rpfm_lib::files::pack::Pack;
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
 use rpfm_lib::utils::{files_from_subdir, path_to_absolute_path, path_to_absolute_string};
 
 use rpfm_ui_common::ASSETS_PATH;
@@ -28,12 +29,22 @@ use crate::SUPPORTED_GAMES;
 
 use self::game_config::GameConfig;
 
+pub mod conflicts;
 pub mod game_config;
+pub mod history;
 pub mod integrations;
 pub mod load_order;
+pub mod log_analysis;
+pub mod modpack;
 pub mod mods;
+pub mod pack_index;
+pub mod pack_watcher;
+pub mod preflight;
 pub mod profiles;
 pub mod saves;
+pub mod shared_load_order;
+pub mod translations;
+pub mod upload_history;
 
 pub const SECONDARY_FOLDER_NAME: &str = "masks";
 
@@ -183,6 +194,38 @@ pub fn secondary_mods_path(game: &str) -> Result<PathBuf> {
     Ok(game_path)
 }
 
+/// This function masks all disabled movie packs in the secondary folder, if the secondary folder is in use by the folder list.
+///
+/// Masking is done by creating empty movie packs with the same name in the masks folder, which is loaded before the secondary folder.
+pub fn mask_disabled_secondary_movie_packs(game_config: &GameConfig, game: &GameInfo, data_path: &Path, folder_list: &str) -> Result<()> {
+    let secondary_mods_path = secondary_mods_path(game.key()).unwrap_or_else(|_| PathBuf::new());
+    if secondary_mods_path.is_dir() && folder_list.contains(&secondary_mods_path.to_string_lossy().to_string()) {
+        let masks_path = secondary_mods_path.join(SECONDARY_FOLDER_NAME);
+
+        // Remove all files in it so previous maskings do not interfere.
+        if masks_path.is_dir() {
+            std::fs::remove_dir_all(&masks_path)?;
+        }
+
+        DirBuilder::new().recursive(true).create(&masks_path)?;
+
+        let mut mask_pack = Pack::new_with_version(game.pfh_version_by_file_type(PFHFileType::Movie));
+        mask_pack.set_pfh_file_type(PFHFileType::Movie);
+
+        for path in std::fs::read_dir(secondary_mods_path)? {
+            let file_name = path?.file_name().to_string_lossy().to_string();
+
+            if let Some(modd) = game_config.mods().get(&file_name) {
+                if modd.pack_type() == &PFHFileType::Movie && !modd.enabled(data_path) {
+                    mask_pack.save(Some(&masks_path.join(file_name)), game, &None)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn secondary_mods_packs_paths(game: &str) -> Option<Vec<PathBuf>> {
     let path = secondary_mods_path(game).ok()?;
     let mut paths = vec![];
@@ -209,3 +252,27 @@ pub unsafe fn icon_data(icon_file_name: &str) -> Result<Vec<u8>> {
 
     Ok(data)
 }
+
+/// This function returns the folders in the Workshop content folder of the game that belong to items the user is no longer subscribed to.
+///
+/// Steam doesn't always delete the files of unsubscribed items, and their packs keep showing up in the mod list.
+pub fn orphaned_content_folders(game: &GameInfo, game_path: &Path, subscribed_items: &[String]) -> Result<Vec<PathBuf>> {
+    let content_path = game.content_path(game_path)?;
+    let mut folders = vec![];
+
+    if content_path.is_dir() {
+        for entry in std::fs::read_dir(&content_path)? {
+            let path = entry?.path();
+            let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+
+            // Only consider folders named like Workshop ids, so we never touch anything else.
+            if path.is_dir() && !name.is_empty() && name.chars().all(|char| char.is_ascii_digit()) && !subscribed_items.contains(&name) {
+                folders.push(path);
+            }
+        }
+    }
+
+    folders.sort();
+
+    Ok(folders)
+}
@@ -0,0 +1,199 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the modpacks: archives with the packs of a load order that are not in the Workshop,
+//! and the list of the ones that are, so a load order can be passed to people without access to our mod folders.
+
+use anyhow::{anyhow, Result};
+use getset::*;
+use serde::{Deserialize, Serialize};
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use std::ffi::OsStr;
+use std::fs::{remove_file, rename, File};
+use std::io::{copy, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use rpfm_lib::games::GameInfo;
+
+use crate::games::launch_options::LaunchOptions;
+
+use super::game_config::GameConfig;
+use super::load_order::LoadOrder;
+use super::mods::ShareableMod;
+use super::secondary_mods_path;
+use super::shared_load_order::SharedLoadOrder;
+
+#[cfg(test)] mod test;
+
+pub const MODPACK_EXTENSION: &str = "zip";
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+const PACKS_FOLDER: &str = "packs/";
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Manifest of a modpack, describing its load order and where to get each of its mods.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct ModpackManifest {
+
+    // Load order of the modpack, including the bundled and the Workshop mods.
+    load_order: SharedLoadOrder,
+
+    // Mods that have to be downloaded from the Workshop.
+    workshop_mods: Vec<ShareableMod>,
+
+    // File names of the packs bundled in the archive.
+    bundled_packs: Vec<String>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl ModpackManifest {
+
+    /// This function writes a modpack with the enabled mods of the provided load order to the provided path.
+    ///
+    /// Mods from the Workshop are only listed in the manifest. The rest have their packs bundled in the archive.
+    pub fn export(path: &Path, game: &GameInfo, game_config: &GameConfig, load_order: &LoadOrder, game_data_path: &Path, launch_options: Option<LaunchOptions>) -> Result<Self> {
+        let mut manifest = Self {
+            load_order: SharedLoadOrder::new(game, game_config, load_order, game_data_path, launch_options),
+            ..Default::default()
+        };
+
+        let mut pack_paths = vec![];
+        for modd in manifest.load_order.mods().iter().chain(manifest.load_order.movies().iter()) {
+            if modd.steam_id().is_some() {
+                manifest.workshop_mods.push(modd.clone());
+                continue;
+            }
+
+            let pack_path = match game_config.mods().get(modd.id()).and_then(|modd| modd.paths().first()) {
+                Some(pack_path) => pack_path,
+                None => continue,
+            };
+
+            let pack_name = pack_path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow!("Invalid pack path: {}.", pack_path.to_string_lossy()))?;
+
+            manifest.bundled_packs.push(pack_name);
+            pack_paths.push(pack_path.to_path_buf());
+        }
+
+        manifest.write(path, &pack_paths)?;
+        Ok(manifest)
+    }
+
+    /// This function extracts the packs of the modpack in the provided path, and returns its manifest and the paths of the extracted packs.
+    ///
+    /// Packs are extracted to the secondary folder if it's configured, or to /data otherwise. Packs already there are not overwritten.
+    /// The load order of the modpack is not applied, as the mod list needs to be updated with the new packs first.
+    pub fn import(path: &Path, game: &GameInfo, game_path: &Path) -> Result<(Self, Vec<PathBuf>)> {
+        let (manifest, mut archive) = Self::open(path)?;
+        manifest.load_order.check_game(game)?;
+
+        let destination = match secondary_mods_path(game.key()) {
+            Ok(path) => path,
+            Err(_) => game.data_path(game_path)?,
+        };
+
+        let extracted = manifest.extract_packs(&mut archive, &destination)?;
+        Ok((manifest, extracted))
+    }
+
+    /// This function writes the modpack with the provided packs, in the same order as the bundled pack names.
+    ///
+    /// The archive is written to a temp file first, so a failed export doesn't leave a broken modpack behind.
+    fn write(&self, path: &Path, pack_paths: &[PathBuf]) -> Result<()> {
+        let temp_path = path.with_extension(format!("{MODPACK_EXTENSION}.tmp"));
+        match self.write_archive(&temp_path, pack_paths) {
+            Ok(()) => Ok(rename(&temp_path, path)?),
+            Err(error) => {
+                let _ = remove_file(&temp_path);
+                Err(error)
+            }
+        }
+    }
+
+    fn write_archive(&self, path: &Path, pack_paths: &[PathBuf]) -> Result<()> {
+        let mut archive = ZipWriter::new(BufWriter::new(File::create(path)?));
+        for (pack_name, pack_path) in self.bundled_packs.iter().zip(pack_paths.iter()) {
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .large_file(pack_path.metadata()?.len() >= u32::MAX as u64);
+
+            archive.start_file(format!("{PACKS_FOLDER}{pack_name}"), options)?;
+            copy(&mut BufReader::new(File::open(pack_path)?), &mut archive)?;
+        }
+
+        archive.start_file(MANIFEST_FILE_NAME, SimpleFileOptions::default())?;
+        archive.write_all(&serde_json::to_vec_pretty(&self)?)?;
+        archive.finish()?.flush()?;
+        Ok(())
+    }
+
+    /// This function opens the modpack in the provided path, returning its manifest and the archive to extract its packs from.
+    fn open(path: &Path) -> Result<(Self, ZipArchive<BufReader<File>>)> {
+        let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+
+        let manifest: Self = {
+            let mut file = archive.by_name(MANIFEST_FILE_NAME).map_err(|_| anyhow!("This file is not a Runcher modpack: it doesn't contain a manifest."))?;
+            let mut data = vec![];
+            file.read_to_end(&mut data)?;
+            serde_json::from_slice(&data)?
+        };
+
+        Ok((manifest, archive))
+    }
+
+    /// This function extracts the bundled packs of the modpack to the provided folder, skipping the ones already there.
+    fn extract_packs(&self, archive: &mut ZipArchive<BufReader<File>>, destination: &Path) -> Result<Vec<PathBuf>> {
+        let mut extracted = vec![];
+        for pack_name in &self.bundled_packs {
+
+            // Only accept plain file names, so the archive cannot write outside the destination folder.
+            if Path::new(pack_name).file_name() != Some(OsStr::new(pack_name)) {
+                return Err(anyhow!("Invalid pack name in the modpack: {}.", pack_name));
+            }
+
+            let pack_path = destination.join(pack_name);
+            if pack_path.exists() {
+                continue;
+            }
+
+            // Same as with exports, extract to a temp file first so a failed extraction doesn't leave a broken pack in the mod folders.
+            let temp_path = destination.join(format!("{pack_name}.tmp"));
+            let result = archive.by_name(&format!("{PACKS_FOLDER}{pack_name}"))
+                .map_err(|_| anyhow!("Pack {} not found in the modpack.", pack_name))
+                .and_then(|mut file| {
+                    let mut pack_file = BufWriter::new(File::create(&temp_path)?);
+                    copy(&mut file, &mut pack_file)?;
+                    pack_file.flush()?;
+                    Ok(())
+                });
+
+            if let Err(error) = result {
+                let _ = remove_file(&temp_path);
+                return Err(error);
+            }
+
+            rename(&temp_path, &pack_path)?;
+            extracted.push(pack_path);
+        }
+
+        Ok(extracted)
+    }
+}
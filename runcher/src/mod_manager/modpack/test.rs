@@ -0,0 +1,81 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for writing and extracting modpacks.
+
+use tempfile::tempdir;
+
+use std::fs::{read, write};
+
+use super::*;
+
+fn manifest(bundled_packs: &[&str]) -> ModpackManifest {
+    ModpackManifest {
+        bundled_packs: bundled_packs.iter().map(|pack| pack.to_string()).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_modpack_round_trip() {
+    let source = tempdir().unwrap();
+    let destination = tempdir().unwrap();
+
+    let pack_a = source.path().join("my_mod.pack");
+    let pack_b = source.path().join("my_other_mod.pack");
+    write(&pack_a, b"PFH5 my mod").unwrap();
+    write(&pack_b, b"PFH5 my other mod").unwrap();
+
+    let path = source.path().join("modpack.zip");
+    manifest(&["my_mod.pack", "my_other_mod.pack"]).write(&path, &[pack_a, pack_b]).unwrap();
+    assert!(!path.with_extension("zip.tmp").exists());
+
+    // Packs already in the destination folder are not overwritten.
+    write(destination.path().join("my_other_mod.pack"), b"PFH5 local version").unwrap();
+
+    let (manifest, mut archive) = ModpackManifest::open(&path).unwrap();
+    assert_eq!(manifest.bundled_packs(), &vec!["my_mod.pack".to_owned(), "my_other_mod.pack".to_owned()]);
+
+    let extracted = manifest.extract_packs(&mut archive, destination.path()).unwrap();
+    assert_eq!(extracted, vec![destination.path().join("my_mod.pack")]);
+    assert_eq!(read(destination.path().join("my_mod.pack")).unwrap(), b"PFH5 my mod");
+    assert_eq!(read(destination.path().join("my_other_mod.pack")).unwrap(), b"PFH5 local version");
+}
+
+#[test]
+fn test_modpack_path_traversal() {
+    let source = tempdir().unwrap();
+    let destination = tempdir().unwrap();
+
+    let pack = source.path().join("evil.pack");
+    write(&pack, b"PFH5 evil").unwrap();
+
+    for pack_name in ["../evil.pack", "subfolder/evil.pack", "/evil.pack"] {
+        let path = source.path().join("modpack.zip");
+        manifest(&[pack_name]).write(&path, &[pack.to_path_buf()]).unwrap();
+
+        let (manifest, mut archive) = ModpackManifest::open(&path).unwrap();
+        assert!(manifest.extract_packs(&mut archive, destination.path()).is_err());
+    }
+
+    assert!(!destination.path().parent().unwrap().join("evil.pack").exists());
+    assert_eq!(destination.path().read_dir().unwrap().count(), 0);
+}
+
+#[test]
+fn test_modpack_failed_export() {
+    let source = tempdir().unwrap();
+    let path = source.path().join("modpack.zip");
+
+    // A pack that doesn't exist makes the export fail midway. Nothing should be left behind.
+    assert!(manifest(&["missing.pack"]).write(&path, &[source.path().join("missing.pack")]).is_err());
+    assert!(!path.exists());
+    assert!(!path.with_extension("zip.tmp").exists());
+}
@@ -0,0 +1,262 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the persistent index of the packs we've seen, so we don't need to open them again unless they change.
+
+use anyhow::{anyhow, Result};
+use getset::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use rpfm_lib::files::{FileType, pack::Pack};
+use rpfm_lib::games::pfh_file_type::PFHFileType;
+
+use crate::PACK_INDEX;
+use crate::settings_ui::game_config_path;
+
+#[cfg(test)] mod test;
+
+const PACK_INDEX_FILE_NAME: &str = "pack_index.json";
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Index of all the packs we've seen, by path. Shared between games, as paths are unique.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct PackIndexCache {
+    packs: HashMap<PathBuf, PackIndex>,
+
+    // Index of the vanilla packs. Not serialized, as they're huge and only the conflicts and data views need them,
+    // so they're indexed the first time they're needed on each session.
+    #[serde(skip)]
+    vanilla_packs: HashMap<PathBuf, PackIndex>,
+
+    // If the index changed since it was loaded or saved. Not serialized.
+    #[serde(skip)]
+    changed: bool,
+}
+
+/// Data we need from a pack. Enough to build the mod list, sort the load order and build the data view without opening it.
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct PackIndex {
+
+    // Size and last modification date (in milliseconds since the unix epoch) of the pack when we indexed it.
+    size: u64,
+    modified: u64,
+
+    pack_type: PFHFileType,
+
+    // Dependencies of the pack, as declared in its header.
+    dependencies: Vec<(bool, String)>,
+
+    // Files in the pack, sorted by path.
+    files: Vec<IndexedFile>,
+}
+
+#[derive(Clone, Debug, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct IndexedFile {
+    path: String,
+    file_type: FileType,
+}
+
+/// File as the game sees it, once all the packs are merged.
+#[derive(Clone, Debug, Getters)]
+#[getset(get = "pub")]
+pub struct DataFile {
+    path: String,
+    pack_name: String,
+    file_type: FileType,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl PackIndexCache {
+
+    pub fn load() -> Result<Self> {
+        let path = game_config_path()?.join(PACK_INDEX_FILE_NAME);
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let cache: Self = serde_json::from_slice(&data)?;
+        Ok(cache)
+    }
+
+    /// This function saves the index to disk. Does nothing if no pack was indexed or removed since the last save.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        let path = game_config_path()?.join(PACK_INDEX_FILE_NAME);
+
+        // Make sure the path exists to avoid problems with updating schemas.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&serde_json::to_vec(&self)?)?;
+
+        self.changed = false;
+        Ok(())
+    }
+
+    /// This function returns the provided packs that are not indexed, or whose size or modification date changed since they were indexed.
+    pub fn outdated(&self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        paths.iter()
+            .filter(|path| match (self.get(path), file_stamp(path)) {
+                (Some(index), Some((size, modified))) => index.size != size || index.modified != modified,
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// This function stores the provided indexes, as returned by [read_pack_indexes]. Packs that failed to be read are removed from the index.
+    ///
+    /// Vanilla indexes are kept in memory only, so they're not saved to disk.
+    pub fn insert(&mut self, indexes: Vec<(PathBuf, Result<PackIndex>)>, vanilla: bool) {
+        for (path, index) in indexes {
+            if vanilla {
+                match index {
+                    Ok(index) => { self.vanilla_packs.insert(path, index); },
+                    Err(_) => { self.vanilla_packs.remove(&path); },
+                }
+            } else {
+                match index {
+                    Ok(index) => { self.packs.insert(path, index); },
+                    Err(_) => { self.packs.remove(&path); },
+                }
+
+                self.changed = true;
+            }
+        }
+    }
+
+    /// This function returns the index of the provided pack, if it's indexed. Remember to update the index before using this.
+    pub fn get(&self, path: &Path) -> Option<&PackIndex> {
+        self.packs.get(path).or_else(|| self.vanilla_packs.get(path))
+    }
+
+    /// This function removes from the index the packs that no longer exist.
+    pub fn prune(&mut self) {
+        let len = self.packs.len();
+        self.packs.retain(|path, _| path.is_file());
+
+        if len != self.packs.len() {
+            self.changed = true;
+        }
+    }
+}
+
+impl PackIndex {
+
+    /// This function reads the index of the pack at the provided path.
+    fn read(path: &Path) -> Result<Self> {
+        let (size, modified) = file_stamp(path).ok_or_else(|| anyhow!("Error reading the metadata of {}.", path.to_string_lossy()))?;
+        let pack = Pack::read_and_merge(&[path.to_path_buf()], true, false, false)?;
+
+        let mut files = pack.files()
+            .values()
+            .map(|file| IndexedFile {
+                path: file.path_in_container_raw().to_owned(),
+                file_type: file.file_type(),
+            })
+            .collect::<Vec<_>>();
+
+        files.sort_by(|file_a, file_b| file_a.path.cmp(&file_b.path));
+
+        Ok(Self {
+            size,
+            modified,
+            pack_type: pack.pfh_file_type(),
+            dependencies: pack.dependencies().to_vec(),
+            files,
+        })
+    }
+}
+
+#[cfg(test)]
+impl PackIndex {
+
+    /// This function creates the index of a mod pack with the provided dependencies, without reading any pack.
+    pub fn new_mod(dependencies: &[&str]) -> Self {
+        Self {
+            size: 0,
+            modified: 0,
+            pack_type: PFHFileType::Mod,
+            dependencies: dependencies.iter().map(|dependency| (true, dependency.to_string())).collect(),
+            files: vec![],
+        }
+    }
+}
+
+impl DataFile {
+    pub fn new(path: &str, pack_name: &str, file_type: FileType) -> Self {
+        Self {
+            path: path.to_owned(),
+            pack_name: pack_name.to_owned(),
+            file_type,
+        }
+    }
+}
+
+/// This function reads the indexes of the provided packs, in parallel.
+pub fn read_pack_indexes(paths: &[PathBuf]) -> Vec<(PathBuf, Result<PackIndex>)> {
+    paths.par_iter()
+        .map(|path| (path.to_path_buf(), PackIndex::read(path)))
+        .collect()
+}
+
+/// This function indexes the provided packs, if they're not indexed or changed since they were indexed.
+///
+/// The packs are read without locking the index, so it can still be used while big packs are being read.
+pub fn update_pack_index(paths: &[PathBuf], vanilla: bool) {
+    let outdated = PACK_INDEX.read().unwrap().outdated(paths);
+    if outdated.is_empty() {
+        return;
+    }
+
+    let indexes = read_pack_indexes(&outdated);
+    PACK_INDEX.write().unwrap().insert(indexes, vanilla);
+}
+
+/// This function indexes the provided packs if needed, and returns their types, in the same order as the paths.
+///
+/// Packs that cannot be read as packs return None.
+pub fn index_pack_types(paths: &[PathBuf]) -> Vec<Option<PFHFileType>> {
+    update_pack_index(paths, false);
+
+    let pack_index = PACK_INDEX.read().unwrap();
+    paths.iter()
+        .map(|path| pack_index.get(path).map(|index| *index.pack_type()))
+        .collect()
+}
+
+/// This function returns the size and last modification date of a file, in milliseconds since the unix epoch.
+pub fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = path.metadata().ok()?;
+    let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_millis() as u64;
+    Some((metadata.len(), modified))
+}
@@ -0,0 +1,71 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the invalidation of the pack index.
+
+use super::*;
+
+fn index_with_stamp(size: u64, modified: u64) -> PackIndex {
+    PackIndex {
+        size,
+        modified,
+        pack_type: PFHFileType::Mod,
+        dependencies: vec![],
+        files: vec![],
+    }
+}
+
+#[test]
+fn test_outdated() {
+    let folder = tempfile::tempdir().unwrap();
+    let path = folder.path().join("my_mod.pack");
+    let moved_path = folder.path().join("my_moved_mod.pack");
+    std::fs::write(&path, b"pack").unwrap();
+    std::fs::write(&moved_path, b"pack").unwrap();
+
+    let (size, modified) = file_stamp(&path).unwrap();
+    let mut cache = PackIndexCache::default();
+    cache.insert(vec![(path.to_path_buf(), Ok(index_with_stamp(size, modified)))], false);
+
+    // Same path, size and date means the index is still valid. A different path is not indexed yet.
+    assert!(cache.outdated(&[path.to_path_buf()]).is_empty());
+    assert_eq!(cache.outdated(&[path.to_path_buf(), moved_path.to_path_buf()]), vec![moved_path.to_path_buf()]);
+
+    // A different date or size invalidates it.
+    cache.insert(vec![(path.to_path_buf(), Ok(index_with_stamp(size, modified - 1)))], false);
+    assert_eq!(cache.outdated(&[path.to_path_buf()]), vec![path.to_path_buf()]);
+
+    cache.insert(vec![(path.to_path_buf(), Ok(index_with_stamp(size, modified)))], false);
+    std::fs::write(&path, b"bigger pack").unwrap();
+    assert_eq!(cache.outdated(&[path.to_path_buf()]), vec![path.to_path_buf()]);
+
+    // Packs that no longer exist are always outdated, and get removed from the index when they fail to be read.
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(cache.outdated(&[path.to_path_buf()]), vec![path.to_path_buf()]);
+
+    cache.insert(read_pack_indexes(&[path.to_path_buf()]), false);
+    assert!(cache.get(&path).is_none());
+}
+
+#[test]
+fn test_vanilla_packs_are_not_saved() {
+    let path = PathBuf::from("/game/data/data.pack");
+
+    // Vanilla packs are only kept in memory, so indexing them doesn't need a save.
+    let mut cache = PackIndexCache::default();
+    cache.insert(vec![(path.to_path_buf(), Ok(index_with_stamp(1, 1)))], true);
+
+    assert!(cache.packs().is_empty());
+    assert!(!cache.changed);
+    assert_eq!(*cache.get(&path).unwrap().size(), 1);
+
+    let saved: PackIndexCache = serde_json::from_slice(&serde_json::to_vec(&cache).unwrap()).unwrap();
+    assert!(saved.get(&path).is_none());
+}
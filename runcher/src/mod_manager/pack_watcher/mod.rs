@@ -0,0 +1,227 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the watcher that detects packs added, changed or removed from the mod folders of a game.
+
+use crossbeam::channel::{unbounded, Receiver, Sender};
+use getset::*;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rpfm_lib::games::GameInfo;
+
+use crate::games::{RESERVED_PACK_NAME, RESERVED_PACK_NAME_ALTERNATIVE};
+
+use super::pack_index::file_stamp;
+use super::secondary_mods_packs_paths;
+
+#[cfg(test)] mod test;
+
+/// Time between scans of the mod folders.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time without new changes we wait before reporting them. Steam downloads write packs in batches, and we don't want to reload the UI for each one.
+const DEBOUNCE: Duration = Duration::from_secs(3);
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Watcher over the data, content and secondary folders of a game. It stops when dropped.
+#[derive(Debug)]
+pub struct PackWatcher {
+    receiver: Receiver<PackChanges>,
+    stop: Arc<AtomicBool>,
+}
+
+/// Packs that changed in the watched folders since the last report.
+#[derive(Clone, Debug, Default, Getters)]
+#[getset(get = "pub")]
+pub struct PackChanges {
+    added: Vec<PathBuf>,
+    changed: Vec<PathBuf>,
+    removed: Vec<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl PackWatcher {
+
+    /// This function starts watching the mod folders of the provided game in a background thread.
+    pub fn new(game: &GameInfo, game_path: &Path) -> Self {
+        let (sender, receiver) = unbounded();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let game = game.clone();
+        let game_path = game_path.to_path_buf();
+        let stop_thread = stop.clone();
+        thread::spawn(move || watch(&game, &game_path, &sender, &stop_thread));
+
+        Self {
+            receiver,
+            stop,
+        }
+    }
+
+    /// This function returns all the changes reported since the last call, if any.
+    pub fn changes(&self) -> Option<PackChanges> {
+        let mut changes: Option<PackChanges> = None;
+        for new_changes in self.receiver.try_iter() {
+            match changes {
+                Some(ref mut changes) => changes.merge(new_changes),
+                None => changes = Some(new_changes),
+            }
+        }
+
+        changes
+    }
+}
+
+impl Drop for PackWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl PackChanges {
+
+    /// This function returns if there are no changes.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// This function returns all the paths affected by the changes.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.added.iter().chain(self.changed.iter()).chain(self.removed.iter())
+    }
+
+    /// This function adds the changes of a later report to this one.
+    fn merge(&mut self, other: Self) {
+        let mut changes = self.by_path();
+        for (path, kind) in other.by_path() {
+            add_change(&mut changes, path, kind);
+        }
+
+        *self = Self::from_changes(changes);
+    }
+
+    fn by_path(&self) -> HashMap<PathBuf, ChangeKind> {
+        self.added.iter().map(|path| (path.to_path_buf(), ChangeKind::Added))
+            .chain(self.changed.iter().map(|path| (path.to_path_buf(), ChangeKind::Changed)))
+            .chain(self.removed.iter().map(|path| (path.to_path_buf(), ChangeKind::Removed)))
+            .collect()
+    }
+
+    fn from_changes(changes: HashMap<PathBuf, ChangeKind>) -> Self {
+        let mut report = Self::default();
+        for (path, kind) in changes {
+            match kind {
+                ChangeKind::Added => report.added.push(path),
+                ChangeKind::Changed => report.changed.push(path),
+                ChangeKind::Removed => report.removed.push(path),
+            }
+        }
+
+        report.added.sort();
+        report.changed.sort();
+        report.removed.sort();
+        report
+    }
+}
+
+/// This function scans the mod folders of the game until told to stop, reporting the changes once they settle down.
+fn watch(game: &GameInfo, game_path: &Path, sender: &Sender<PackChanges>, stop: &AtomicBool) {
+    let mut stamps = scan(game, game_path);
+    let mut pending = HashMap::new();
+    let mut last_change = Instant::now();
+
+    while !stop.load(Ordering::Relaxed) {
+        thread::sleep(POLL_INTERVAL);
+
+        let new_stamps = scan(game, game_path);
+        let mut changed = false;
+
+        for (path, stamp) in &new_stamps {
+            match stamps.get(path) {
+                Some(old_stamp) if old_stamp == stamp => {},
+                Some(_) => { add_change(&mut pending, path.to_path_buf(), ChangeKind::Changed); changed = true; },
+                None => { add_change(&mut pending, path.to_path_buf(), ChangeKind::Added); changed = true; },
+            }
+        }
+
+        for path in stamps.keys() {
+            if !new_stamps.contains_key(path) {
+                add_change(&mut pending, path.to_path_buf(), ChangeKind::Removed);
+                changed = true;
+            }
+        }
+
+        stamps = new_stamps;
+        if changed {
+            last_change = Instant::now();
+        }
+
+        // Only report once nothing changed for a while. If the receiver is gone, there's no one to report to.
+        if !pending.is_empty() && last_change.elapsed() >= DEBOUNCE {
+            let changes = PackChanges::from_changes(std::mem::take(&mut pending));
+            if sender.send(changes).is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// This function returns the size and modification date of all the packs in the mod folders of the game.
+fn scan(game: &GameInfo, game_path: &Path) -> HashMap<PathBuf, (u64, u64)> {
+    game.data_packs_paths(game_path).unwrap_or_default()
+        .into_iter()
+        .chain(game.content_packs_paths(game_path).unwrap_or_default())
+        .chain(secondary_mods_packs_paths(game.key()).unwrap_or_default())
+
+        // Ignore the packs we generate on launch, as they're not mods.
+        .filter(|path| path.file_name()
+            .map(|name| name != RESERVED_PACK_NAME && name != RESERVED_PACK_NAME_ALTERNATIVE)
+            .unwrap_or(false))
+        .filter_map(|path| {
+            let stamp = file_stamp(&path)?;
+            Some((path, stamp))
+        })
+        .collect()
+}
+
+/// This function adds a change to a list of pending changes, collapsing it with the previous change of the same path.
+fn add_change(changes: &mut HashMap<PathBuf, ChangeKind>, path: PathBuf, kind: ChangeKind) {
+    match (changes.get(&path).copied(), kind) {
+
+        // A pack that appeared and then disappeared was never there.
+        (Some(ChangeKind::Added), ChangeKind::Removed) => { changes.remove(&path); },
+
+        // A pack that appeared and then changed is still a new pack.
+        (Some(ChangeKind::Added), ChangeKind::Changed) => {},
+
+        // A pack that disappeared and then appeared again is just a changed pack.
+        (Some(ChangeKind::Removed), ChangeKind::Added) => { changes.insert(path, ChangeKind::Changed); },
+        _ => { changes.insert(path, kind); },
+    }
+}
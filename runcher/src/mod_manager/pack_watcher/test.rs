@@ -0,0 +1,62 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for how the watcher collapses consecutive changes of the same pack.
+
+use super::*;
+
+fn changes_after(kinds: &[ChangeKind]) -> HashMap<PathBuf, ChangeKind> {
+    let mut changes = HashMap::new();
+    for kind in kinds {
+        add_change(&mut changes, PathBuf::from("/data/my_mod.pack"), *kind);
+    }
+
+    changes
+}
+
+fn report(added: &[&str], changed: &[&str], removed: &[&str]) -> PackChanges {
+    let paths = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+    PackChanges {
+        added: paths(added),
+        changed: paths(changed),
+        removed: paths(removed),
+    }
+}
+
+#[test]
+fn test_add_change() {
+    use ChangeKind::*;
+
+    let kind_after = |kinds: &[ChangeKind]| changes_after(kinds).get(Path::new("/data/my_mod.pack")).copied();
+
+    assert_eq!(kind_after(&[Added, Changed, Removed]), None);
+    assert_eq!(kind_after(&[Added, Changed, Changed]), Some(Added));
+    assert_eq!(kind_after(&[Changed, Removed]), Some(Removed));
+    assert_eq!(kind_after(&[Changed, Changed]), Some(Changed));
+    assert_eq!(kind_after(&[Removed, Added]), Some(Changed));
+    assert_eq!(kind_after(&[Removed, Added, Removed]), Some(Removed));
+    assert_eq!(kind_after(&[Added, Removed, Added]), Some(Added));
+}
+
+#[test]
+fn test_merge() {
+    let mut changes = report(&["/data/a.pack", "/data/d.pack"], &["/data/b.pack"], &["/data/c.pack"]);
+    changes.merge(report(&["/data/c.pack", "/data/e.pack"], &["/data/d.pack"], &["/data/a.pack", "/data/b.pack"]));
+
+    // Added then removed is nothing, removed then added is changed, and added then changed is still added.
+    assert_eq!(changes.added(), &vec![PathBuf::from("/data/d.pack"), PathBuf::from("/data/e.pack")]);
+    assert_eq!(changes.changed(), &vec![PathBuf::from("/data/c.pack")]);
+    assert_eq!(changes.removed(), &vec![PathBuf::from("/data/b.pack")]);
+
+    // Reports that cancel each other out leave nothing to report.
+    let mut changes = report(&["/data/a.pack"], &[], &[]);
+    changes.merge(report(&[], &[], &["/data/a.pack"]));
+    assert!(changes.is_empty());
+}
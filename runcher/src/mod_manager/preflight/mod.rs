@@ -0,0 +1,312 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the checks done to a pack before uploading it to the Workshop.
+
+use anyhow::Result;
+use getset::*;
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use rpfm_extensions::dependencies::Dependencies;
+use rpfm_extensions::optimizer::Optimizable;
+
+use rpfm_lib::files::{Container, DecodeableExtraData, FileType, pack::Pack, RFileDecoded};
+use rpfm_lib::games::{GameInfo, pfh_file_type::PFHFileType};
+use rpfm_lib::schema::Schema;
+
+use crate::SCHEMA;
+
+#[cfg(test)] mod test;
+
+/// Max size of the preview image accepted by the Workshop.
+pub const MAX_PREVIEW_SIZE: u64 = 1024 * 1024;
+
+/// Extension of the preview image. By default, it's next to the pack, with the same name.
+const PREVIEW_EXTENSION: &str = "png";
+
+/// First bytes of any png image.
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Extension of the files RPFM adds to packs to store its own data. The game ignores them.
+const RPFM_RESERVED_EXTENSION: &str = ".rpfm_reserved";
+
+/// Root folders of the packs any of the supported games reads files from.
+const KNOWN_FOLDERS: [&str; 23] = [
+    "ai",
+    "animations",
+    "audio",
+    "battleterrain",
+    "campaign_maps",
+    "campaigns",
+    "composite_scene",
+    "db",
+    "fonts",
+    "materials",
+    "models",
+    "movies",
+    "particles",
+    "prefabs",
+    "rigidmodels",
+    "script",
+    "shaders",
+    "terrain",
+    "text",
+    "ui",
+    "variantmeshes",
+    "vfx",
+    "warscape",
+];
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Report of the problems found in a pack before uploading it.
+#[derive(Clone, Debug, Default, Getters)]
+#[getset(get = "pub")]
+pub struct PreflightReport {
+
+    // Problems found, with the blocking ones first.
+    issues: Vec<PreflightIssue>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreflightIssue {
+
+    /// The pack is not a mod pack. Contains the type of the pack.
+    WrongPackType(PFHFileType),
+
+    /// There's no preview image at the chosen path. Contains the path.
+    MissingPreview(PathBuf),
+
+    /// The preview image is not a png image. Contains its path.
+    WrongPreviewFormat(PathBuf),
+
+    /// The preview image is too big for the Workshop. Contains its size in bytes.
+    OversizedPreview(u64),
+
+    /// A table uses an older version than the one of the vanilla table. Contains the path of the table, its version and the one of the vanilla table.
+    OutdatedTable(String, i32, i32),
+
+    /// A table cannot be decoded with the schema of the game. Contains the path of the table.
+    UndecodableTable(String),
+
+    /// A file is outside the folders the game reads. Contains the path of the file.
+    UnknownFolder(String),
+
+    /// A table contains rows that are duplicated, or equal to the vanilla ones. Contains the path of the table and the amount of rows.
+    NotOptimized(String, usize),
+
+    /// There's no schema for the game, so tables cannot be checked.
+    NoSchema,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl Display for PreflightIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongPackType(pack_type) => write!(f, "The pack is of type {}. Only Mod packs can be uploaded to the Workshop.", pack_type),
+            Self::MissingPreview(path) => write!(f, "There's no preview image at {}.", path.to_string_lossy()),
+            Self::WrongPreviewFormat(path) => write!(f, "The preview image at {} is not a png image.", path.to_string_lossy()),
+            Self::OversizedPreview(size) => write!(f, "The preview image is {} KB. The Workshop only accepts images up to {} KB.", size / 1024, MAX_PREVIEW_SIZE / 1024),
+            Self::OutdatedTable(path, version, game_version) => write!(f, "Table {} is outdated: it's version {}, but the game uses version {}.", path, version, game_version),
+            Self::UndecodableTable(path) => write!(f, "Table {} cannot be decoded with the current schema. It may be outdated.", path),
+            Self::UnknownFolder(path) => write!(f, "File {} is outside the folders the game reads files from.", path),
+            Self::NotOptimized(path, rows) => write!(f, "Table {} has {} rows that are duplicated or equal to the vanilla ones. Optimize the pack to remove them.", path, rows),
+            Self::NoSchema => write!(f, "There is no schema loaded for this game, so the tables of the pack cannot be checked. Make sure the schemas are downloaded."),
+        }
+    }
+}
+
+impl PreflightIssue {
+
+    /// This function returns if the issue has to be fixed before uploading the pack.
+    pub fn is_blocking(&self) -> bool {
+        matches!(self,
+            Self::WrongPackType(_) |
+            Self::MissingPreview(_) |
+            Self::WrongPreviewFormat(_) |
+            Self::OversizedPreview(_) |
+            Self::OutdatedTable(..)
+        )
+    }
+
+    /// This function returns if the issue is about the preview image, instead of the pack.
+    pub fn is_preview_issue(&self) -> bool {
+        matches!(self,
+            Self::MissingPreview(_) |
+            Self::WrongPreviewFormat(_) |
+            Self::OversizedPreview(_)
+        )
+    }
+}
+
+impl PreflightReport {
+
+    /// This function checks the pack at the provided path, and the preview image that's going to be uploaded with it.
+    ///
+    /// Tables are only checked if we have a schema. Checking if they're optimized requires reading the vanilla packs, so it may take a while.
+    pub fn generate(game: &GameInfo, game_path: &Path, pack_path: &Path, preview_path: &Path, schema: Option<&Schema>) -> Result<Self> {
+        let mut issues = preview_issues(preview_path);
+
+        let mut pack = Pack::read_and_merge(&[pack_path.to_path_buf()], true, false, false)?;
+        if pack.pfh_file_type() != PFHFileType::Mod {
+            issues.push(PreflightIssue::WrongPackType(pack.pfh_file_type()));
+        }
+
+        let paths = pack.files().keys().cloned().collect::<Vec<_>>();
+        issues.extend(unknown_folder_paths(&paths).into_iter().map(PreflightIssue::UnknownFolder));
+
+        match schema {
+            Some(schema) => issues.append(&mut check_tables(game, game_path, &mut pack, schema)?),
+            None => issues.push(PreflightIssue::NoSchema),
+        }
+
+        let mut report = Self {
+            issues
+        };
+
+        report.sort();
+        Ok(report)
+    }
+
+    /// This function checks the pack at the provided path using the currently loaded schema.
+    ///
+    /// The schema is copied, so it's not locked while the check runs.
+    pub fn generate_with_loaded_schema(game: &GameInfo, game_path: &Path, pack_path: &Path, preview_path: &Path) -> Result<Self> {
+        let schema = SCHEMA.read().unwrap().clone();
+        Self::generate(game, game_path, pack_path, preview_path, schema.as_ref())
+    }
+
+    /// This function replaces the issues of the preview image with the ones of the image at the provided path, without checking the pack again.
+    pub fn set_preview(&mut self, preview_path: &Path) {
+        self.issues.retain(|issue| !issue.is_preview_issue());
+        self.issues.append(&mut preview_issues(preview_path));
+        self.sort();
+    }
+
+    /// This function returns if any of the issues found has to be fixed before uploading the pack.
+    pub fn is_blocking(&self) -> bool {
+        self.issues.iter().any(|issue| issue.is_blocking())
+    }
+
+    fn sort(&mut self) {
+
+        // Stable sort, so within each group issues keep the order in which they were found.
+        self.issues.sort_by_key(|issue| !issue.is_blocking());
+    }
+}
+
+/// This function returns the path where the launcher expects the preview image of a pack: next to it, with its same name.
+pub fn default_preview_path(pack_path: &Path) -> PathBuf {
+    pack_path.with_extension(PREVIEW_EXTENSION)
+}
+
+/// This function checks if the image at the provided path can be used as preview image in the Workshop.
+///
+/// Images are checked by their signature, not by their extension, as renamed images are not accepted either.
+pub fn preview_issues(preview_path: &Path) -> Vec<PreflightIssue> {
+    let is_png = File::open(preview_path)
+        .and_then(|mut file| {
+            let mut signature = [0; 8];
+            file.read_exact(&mut signature).map(|_| signature == PNG_SIGNATURE)
+        })
+        .unwrap_or(false);
+
+    match preview_path.metadata() {
+        Ok(metadata) if metadata.is_file() => {
+            let mut issues = vec![];
+            if !is_png {
+                issues.push(PreflightIssue::WrongPreviewFormat(preview_path.to_path_buf()));
+            }
+
+            if metadata.len() > MAX_PREVIEW_SIZE {
+                issues.push(PreflightIssue::OversizedPreview(metadata.len()));
+            }
+
+            issues
+        },
+        _ => vec![PreflightIssue::MissingPreview(preview_path.to_path_buf())],
+    }
+}
+
+/// This function returns the paths, sorted, that are not within the folders the game reads files from.
+///
+/// Files from RPFM are ignored, as they're expected to be in the root of the pack.
+pub fn unknown_folder_paths(paths: &[String]) -> Vec<String> {
+    let mut unknown = paths.iter()
+        .filter(|path| !path.to_lowercase().ends_with(RPFM_RESERVED_EXTENSION))
+        .filter(|path| match path.split_once('/') {
+            Some((folder, _)) => !KNOWN_FOLDERS.contains(&&*folder.to_lowercase()),
+            None => true,
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    unknown.sort();
+    unknown
+}
+
+/// This function checks the versions of the tables of the pack, and if they contain rows the optimizer would remove.
+fn check_tables(game: &GameInfo, game_path: &Path, pack: &mut Pack, schema: &Schema) -> Result<Vec<PreflightIssue>> {
+    let mut issues = vec![];
+
+    let mut dec_extra_data = DecodeableExtraData::default();
+    dec_extra_data.set_schema(Some(schema));
+    let dec_extra_data = Some(dec_extra_data);
+
+    let mut tables = pack.files_by_type_mut(&[FileType::DB, FileType::Loc]);
+    if tables.is_empty() {
+        return Ok(issues);
+    }
+
+    tables.sort_by(|table_a, table_b| table_a.path_in_container_raw().cmp(table_b.path_in_container_raw()));
+
+    // Only build the dependencies once, as they're the slowest part of the check.
+    let mut dependencies = Dependencies::generate_dependencies_cache(&Some(schema.clone()), game, game_path, &None, false)?;
+
+    for table in tables {
+        let path = table.path_in_container_raw().to_owned();
+        match table.decode(&dec_extra_data, false, true) {
+            Ok(Some(RFileDecoded::DB(mut db))) => {
+
+                // Compare against the version of the vanilla table, as the schema may have newer versions than the ones the installed game uses.
+                if let Some(game_version) = dependencies.db_version(db.table_name()) {
+                    if *db.definition().version() < game_version {
+                        issues.push(PreflightIssue::OutdatedTable(path.to_owned(), *db.definition().version(), game_version));
+                    }
+                }
+
+                let rows = db.data().len();
+                db.optimize(&mut dependencies);
+                if db.data().len() < rows {
+                    issues.push(PreflightIssue::NotOptimized(path, rows - db.data().len()));
+                }
+            },
+            Ok(Some(RFileDecoded::Loc(mut loc))) => {
+                let rows = loc.data().len();
+                loc.optimize(&mut dependencies);
+                if loc.data().len() < rows {
+                    issues.push(PreflightIssue::NotOptimized(path, rows - loc.data().len()));
+                }
+            },
+            _ => issues.push(PreflightIssue::UndecodableTable(path)),
+        }
+    }
+
+    Ok(issues)
+}
@@ -0,0 +1,64 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the pre-upload checks.
+
+use super::*;
+
+#[test]
+fn test_unknown_folder_paths() {
+    let paths = vec![
+        "db/units_tables/my_mod".to_owned(),
+        "Text/db/my_mod.loc".to_owned(),
+        "script/campaign/mod/my_mod.lua".to_owned(),
+        "my_mod/db/units_tables/my_mod".to_owned(),
+        "readme.txt".to_owned(),
+        "settings.rpfm_reserved".to_owned(),
+        "dbs/units_tables/my_mod".to_owned(),
+        "campaigns/main_warhammer/campaign_variables.xml".to_owned(),
+        "rigidmodels/buildings/my_building.rigid_model_v2".to_owned(),
+    ];
+
+    assert_eq!(unknown_folder_paths(&paths), vec![
+        "dbs/units_tables/my_mod".to_owned(),
+        "my_mod/db/units_tables/my_mod".to_owned(),
+        "readme.txt".to_owned(),
+    ]);
+}
+
+#[test]
+fn test_blocking_issues() {
+    let mut report = PreflightReport::default();
+    assert!(!report.is_blocking());
+
+    report.issues.push(PreflightIssue::UnknownFolder("readme.txt".to_owned()));
+    report.issues.push(PreflightIssue::NotOptimized("text/db/my_mod.loc".to_owned(), 3));
+    assert!(!report.is_blocking());
+
+    report.issues.push(PreflightIssue::OversizedPreview(MAX_PREVIEW_SIZE + 1));
+    assert!(report.is_blocking());
+}
+
+#[test]
+fn test_preview_issues() {
+    let folder = tempfile::tempdir().unwrap();
+
+    let png_path = folder.path().join("my_mod.png");
+    std::fs::write(&png_path, [&PNG_SIGNATURE[..], b"image data"].concat()).unwrap();
+    assert!(preview_issues(&png_path).is_empty());
+
+    // A jpg renamed to png is still a jpg.
+    let renamed_path = folder.path().join("renamed.png");
+    std::fs::write(&renamed_path, [0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F']).unwrap();
+    assert_eq!(preview_issues(&renamed_path), vec![PreflightIssue::WrongPreviewFormat(renamed_path.to_path_buf())]);
+
+    let missing_path = folder.path().join("missing.png");
+    assert_eq!(preview_issues(&missing_path), vec![PreflightIssue::MissingPreview(missing_path.to_path_buf())]);
+}
@@ -18,10 +18,12 @@ use serde_json::to_string_pretty;
 use std::collections::HashMap;
 use std::fs::{DirBuilder, File};
 use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
 
 use rpfm_lib::games::GameInfo;
 use rpfm_lib::utils::*;
 
+use crate::games::launch_options::LaunchOptions;
 use crate::mod_manager::game_config::GameConfig;
 use crate::settings_ui::*;
 
@@ -48,6 +50,15 @@ pub struct Profile {
 
     // Load order of this profile.
     load_order: LoadOrder,
+
+    // Launch options of this profile.
+    launch_options: LaunchOptions,
+
+    // Name of the save to load when launching the game with this profile, if any.
+    save: Option<String>,
+
+    // Workshop collection this profile was published as, if any.
+    workshop_collection: Option<String>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -107,10 +118,45 @@ impl Profile {
     #[allow(dead_code)]
     pub fn update(game_config: &GameConfig, game_info: &GameInfo) -> Result<()> {
         let _ = versions::v0::ProfileV0::update(game_config, game_info);
+        let _ = versions::v1::ProfileV1::update(game_info);
 
         Ok(())
     }
 
+    /// This function applies the profile to the provided game config and load order.
+    ///
+    /// All mods not in the profile are disabled, and the load order is replaced with the one from the profile.
+    pub fn apply(&self, game_config: &mut GameConfig, load_order: &mut LoadOrder, game_data_path: &Path) {
+        game_config.mods_mut().values_mut().for_each(|modd| { modd.set_enabled(false); });
+
+        for mod_id in self.load_order().mods() {
+            if let Some(ref mut modd) = game_config.mods_mut().get_mut(mod_id) {
+                modd.set_enabled(true);
+            }
+        }
+
+        // Replace the current load order with the one from the profile, and update it.
+        *load_order = self.load_order().clone();
+        load_order.update(game_config, game_data_path);
+    }
+
+    /// This function returns the Workshop ids of the mods of the profile, in load order, and the mods that are not in the Workshop.
+    ///
+    /// Mods not in the provided game config are considered not in the Workshop, as we cannot know their ids.
+    pub fn workshop_items(&self, game_config: &GameConfig) -> (Vec<String>, Vec<String>) {
+        let mut steam_ids = vec![];
+        let mut local_only = vec![];
+
+        for mod_id in self.load_order().mods().iter().chain(self.load_order().movies().iter()) {
+            match game_config.mods().get(mod_id).and_then(|modd| modd.steam_id().clone()) {
+                Some(steam_id) => steam_ids.push(steam_id),
+                None => local_only.push(mod_id.to_owned()),
+            }
+        }
+
+        (steam_ids, local_only)
+    }
+
     pub fn delete(&self, game: &GameInfo) -> Result<()> {
         let path = profiles_path()?.join(format!("{FILE_NAME_START}{}_{}{FILE_NAME_END}", game.key(), self.id()));
         if path.is_file() {
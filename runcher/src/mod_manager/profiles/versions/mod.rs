@@ -8,7 +8,8 @@
 // https://github.com/Frodo45127/runcher/blob/master/LICENSE.
 //---------------------------------------------------------------------------//
 
-use super::Profile as ProfileV1;
+use super::Profile as ProfileV2;
 
 #[allow(dead_code)] pub mod v0;
+#[allow(dead_code)] pub mod v1;
 
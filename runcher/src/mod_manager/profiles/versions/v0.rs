@@ -22,7 +22,7 @@ use rpfm_lib::utils::files_from_subdir;
 use crate::mod_manager::{game_config::GameConfig, load_order::LoadOrder};
 use crate::profiles_path;
 
-use super::ProfileV1;
+use super::v1::ProfileV1;
 
 const PROFILE_FILE_NAME_START: &str = "profile_";
 const PROFILE_FILE_NAME_END: &str = ".json";
@@ -59,7 +59,7 @@ impl ProfileV0 {
                     profile.load_order_mut().mods_mut().retain(|mod_id| !movies.contains(mod_id));
                     *profile.load_order_mut().movies_mut() = movies;
 
-                    let profile_name = profile.id.to_owned();
+                    let profile_name = profile.id().to_owned();
                     profile.save(game, &profile_name)?;
                 }
             }
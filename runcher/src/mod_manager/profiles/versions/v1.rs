@@ -0,0 +1,108 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+use getset::*;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::to_string_pretty;
+
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use rpfm_lib::games::GameInfo;
+use rpfm_lib::utils::files_from_subdir;
+
+use crate::games::launch_options::LaunchOptions;
+use crate::mod_manager::load_order::LoadOrder;
+use crate::profiles_path;
+
+use super::ProfileV2;
+
+const PROFILE_FILE_NAME_START: &str = "profile_";
+const PROFILE_FILE_NAME_END: &str = ".json";
+
+#[derive(Clone, Debug, Default, Getters, MutGetters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", get_mut = "pub", set = "pub")]
+pub struct ProfileV1 {
+    pub id: String,
+    pub game: String,
+    pub load_order: LoadOrder,
+}
+
+impl ProfileV1 {
+    pub fn update(game: &GameInfo) -> Result<()> {
+        let path = profiles_path()?;
+        let file_name_start = format!("{PROFILE_FILE_NAME_START}{}_", game.key());
+
+        let files = files_from_subdir(&path, false)?;
+        for file in files {
+            let file_name = file.file_name().unwrap().to_string_lossy();
+            if file_name.starts_with(&file_name_start) && file_name.ends_with(PROFILE_FILE_NAME_END) {
+                let file_name_no_end = file.file_stem().unwrap().to_string_lossy().strip_prefix(&file_name_start).unwrap().to_string();
+
+                // Check that it fails with v2, because v2 files are readable with v1.
+                if ProfileV2::load(game, &file_name_no_end, false).is_err() {
+                    if let Ok(profile) = Self::load(game, &file_name_no_end, false) {
+
+                        // Old profiles didn't have launch options, so we use the ones the game was using.
+                        let mut profile = ProfileV2::from(&profile);
+                        profile.set_launch_options(LaunchOptions::from_settings(game));
+                        profile.save(game, &file_name_no_end)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn load(game: &GameInfo, profile: &str, new_if_missing: bool) -> Result<Self> {
+        let path = profiles_path()?.join(format!("{PROFILE_FILE_NAME_START}{}_{}{PROFILE_FILE_NAME_END}", game.key(), profile));
+        if !path.is_file() && new_if_missing {
+            return Ok(Self {
+                id: profile.to_string(),
+                ..Default::default()
+            });
+        }
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let profile: Self = serde_json::from_slice(&data)?;
+        Ok(profile)
+    }
+
+    pub fn save(&mut self, game: &GameInfo, profile: &str) -> Result<()> {
+        let path = profiles_path()?.join(format!("{PROFILE_FILE_NAME_START}{}_{}{PROFILE_FILE_NAME_END}", game.key(), profile));
+
+        // Make sure the path exists to avoid problems with updating schemas.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(to_string_pretty(&self)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl From<&ProfileV1> for ProfileV2 {
+    fn from(value: &ProfileV1) -> Self {
+        Self {
+            id: value.id.to_owned(),
+            game: value.game.to_owned(),
+            load_order: value.load_order.clone(),
+            launch_options: LaunchOptions::default(),
+            save: None,
+            workshop_collection: None,
+        }
+    }
+}
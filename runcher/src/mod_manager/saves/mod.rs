@@ -8,10 +8,34 @@
 // https://github.com/Frodo45127/runcher/blob/master/LICENSE.
 //---------------------------------------------------------------------------//
 
+use anyhow::{anyhow, Result};
 use getset::*;
 use serde::{Deserialize, Serialize};
 
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use rpfm_lib::files::{esf::NodeType, RFile, RFileDecoded};
+use rpfm_lib::games::GameInfo;
+use rpfm_lib::integrations::log::*;
+use rpfm_lib::utils::files_from_subdir;
+
+use crate::settings_ui::game_config_path;
+
+use super::game_config::GameConfig;
+use super::load_order::LoadOrder;
+use super::mods::ShareableMod;
+
+#[cfg(test)] mod test;
+
+const CACHE_FILE_NAME_START: &str = "saves_cache_";
+const CACHE_FILE_NAME_END: &str = ".json";
+
+/// Path of records we need to follow from the root of the save to get to the list of mods used.
+const MOD_HISTORY_PATH: [&str; 3] = ["CAMPAIGN_SAVE_GAME", "SAVE_GAME_HEADER", "mod_history_block_name"];
 
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
@@ -22,9 +46,244 @@ use std::path::PathBuf;
 pub struct Save {
     path: PathBuf,
     name: String,
+
+    /// Last modification date of the save, in seconds since the unix epoch.
+    modified: u64,
+
+    /// Pack names of the mods the save was made with, in load order.
+    mods: Vec<String>,
+
+    /// If we know the mods of the save, either from the cache or from reading the save.
+    mods_read: bool,
+
+    /// Error we got reading the mods of the save, if any. We cannot know which mods were used in saves with errors.
+    read_error: Option<String>,
+}
+
+/// Mods of each save, so we don't need to parse saves that didn't change since the last time we read them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct SavesCache {
+    saves: HashMap<PathBuf, CachedSave>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct CachedSave {
+
+    // Last modification date of the save when we parsed it, in seconds since the unix epoch.
+    modified: u64,
     mods: Vec<String>,
 }
 
 //-------------------------------------------------------------------------------//
 //                             Implementations
 //-------------------------------------------------------------------------------//
+
+impl Save {
+
+    /// This function returns the saves of the provided game, sorted by date, with the most recent first.
+    ///
+    /// Saves are not parsed here, as that's slow. The mods of saves that didn't change since the last time we parsed them are taken from the cache.
+    /// For the rest, use [Save::read_mods_if_needed] when they're needed.
+    pub fn saves_for_game(game: &GameInfo, game_path: &Path) -> Result<Vec<Self>> {
+        let config_path = match game.config_path(game_path) {
+            Some(config_path) => config_path,
+            None => return Ok(vec![]),
+        };
+
+        let saves_paths = match files_from_subdir(&config_path.join("save_games"), false) {
+            Ok(saves_paths) => saves_paths,
+            Err(_) => return Ok(vec![]),
+        };
+
+        let mut saves_paths = saves_paths.into_iter()
+            .filter_map(|path| {
+                let modified = path.metadata().ok()?.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_secs();
+                Some((path, modified))
+            })
+            .collect::<Vec<_>>();
+
+        // Sort them by date, then reverse, so the most recent one is first.
+        saves_paths.sort_by_key(|(_, modified)| *modified);
+        saves_paths.reverse();
+
+        let mut cache = SavesCache::load(game).unwrap_or_default();
+        let saves = saves_paths.into_iter()
+            .map(|(path, modified)| {
+                let mut save = Self {
+                    name: path.file_name().unwrap().to_string_lossy().to_string(),
+                    path,
+                    modified,
+                    ..Default::default()
+                };
+
+                if let Some(cached) = cache.saves.get(&save.path) {
+                    if cached.modified == modified {
+                        save.mods = cached.mods.to_vec();
+                        save.mods_read = true;
+                    }
+                }
+
+                save
+            })
+            .collect::<Vec<_>>();
+
+        // Remove deleted and changed saves from the cache.
+        let cached_count = cache.saves.len();
+        cache.saves.retain(|path, cached| saves.iter().any(|save| save.mods_read && &save.path == path && save.modified == cached.modified));
+
+        if cache.saves.len() != cached_count {
+            if let Err(error) = cache.save(game) {
+                warn!("Error saving the saves cache: {}", error);
+            }
+        }
+
+        Ok(saves)
+    }
+
+    /// This function reads the mods of the save, unless we already know them or already failed to read them, and adds them to the cache.
+    pub fn read_mods_if_needed(&mut self, game: &GameInfo) {
+        if self.mods_read || self.read_error.is_some() {
+            return;
+        }
+
+        match Self::read_mods(&self.path) {
+            Ok(mods) => {
+                self.mods = mods;
+                self.mods_read = true;
+
+                let mut cache = SavesCache::load(game).unwrap_or_default();
+                cache.saves.insert(self.path.to_path_buf(), CachedSave { modified: self.modified, mods: self.mods.to_vec() });
+
+                if let Err(error) = cache.save(game) {
+                    warn!("Error saving the saves cache: {}", error);
+                }
+            },
+
+            // Saves we couldn't read are not cached, so we try again the next time the saves are loaded.
+            Err(error) => {
+                warn!("Error reading the mods of save {}: {}", self.path.to_string_lossy(), error);
+                self.read_error = Some(error.to_string());
+            },
+        }
+    }
+
+    /// This function reads the pack names of the mods used in the provided save.
+    fn read_mods(path: &Path) -> Result<Vec<String>> {
+        let mut file = RFile::new_from_file_path(path)?;
+        file.guess_file_type()?;
+
+        match file.decode(&None, false, true)? {
+            Some(RFileDecoded::ESF(esf)) => Self::mods_from_esf(esf.root_node()).ok_or_else(|| anyhow!("The list of mods used was not found in the save.")),
+            _ => Err(anyhow!("The save is not an ESF file.")),
+        }
+    }
+
+    /// This function returns the pack names of the mods in the mod history of a save, if it has one.
+    fn mods_from_esf(root_node: &NodeType) -> Option<Vec<String>> {
+        match Self::find_record(root_node, &MOD_HISTORY_PATH) {
+            Some(NodeType::Record(mod_history)) => {
+
+                // Each entry of the mod history contains the pack name, then the folder it was loaded from.
+                Some(mod_history.children()
+                    .iter()
+                    .filter_map(|children| match children.first() {
+                        Some(NodeType::Ascii(pack_name)) => Some(pack_name.to_owned()),
+                        _ => None,
+                    })
+                    .collect())
+            },
+            _ => None,
+        }
+    }
+
+    /// This function follows the provided path of record names from the provided node, returning the last record of the path.
+    fn find_record<'a>(node: &'a NodeType, path: &[&str]) -> Option<&'a NodeType> {
+        match node {
+            NodeType::Record(record) if record.name() == path[0] => {
+                if path.len() == 1 {
+                    Some(node)
+                } else {
+                    record.children()
+                        .iter()
+                        .flatten()
+                        .find_map(|child| Self::find_record(child, &path[1..]))
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// This function returns the differences between the mods of the save and the current load order.
+    ///
+    /// The first list contains the mods of the save missing from the load order. The second one, the mods in the load order not used by the save.
+    /// Movie packs are included, as saves also list them. Fails if we couldn't read the mods of the save.
+    pub fn load_order_differences(&self, game_config: &GameConfig, load_order: &LoadOrder) -> Result<(Vec<String>, Vec<String>)> {
+        if let Some(error) = &self.read_error {
+            return Err(anyhow!("The mods used by the save {} could not be read: {}", self.name, error));
+        }
+
+        if !self.mods_read {
+            return Err(anyhow!("The mods used by the save {} have not been read yet.", self.name));
+        }
+
+        let pack_names = load_order.mods()
+            .iter()
+            .chain(load_order.movies().iter())
+            .filter_map(|mod_id| game_config.mods().get(mod_id))
+            .filter_map(|modd| modd.paths().first())
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+
+        let missing = self.mods.iter()
+            .filter(|pack_name| !pack_names.contains(pack_name))
+            .cloned()
+            .collect();
+
+        let extra = pack_names.iter()
+            .filter(|pack_name| !self.mods.contains(pack_name))
+            .cloned()
+            .collect();
+
+        Ok((missing, extra))
+    }
+
+    /// This function returns the mods of the save as a shareable mod list, so they can be imported as a load order.
+    pub fn shareable_mod_list(&self) -> Vec<ShareableMod> {
+        self.mods.iter()
+            .map(|pack_name| {
+                let mut modd = ShareableMod::default();
+                modd.set_id(pack_name.to_owned());
+                modd.set_name(pack_name.to_owned());
+                modd
+            })
+            .collect()
+    }
+}
+
+impl SavesCache {
+
+    fn load(game: &GameInfo) -> Result<Self> {
+        let path = game_config_path()?.join(format!("{CACHE_FILE_NAME_START}{}{CACHE_FILE_NAME_END}", game.key()));
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let cache: Self = serde_json::from_slice(&data)?;
+        Ok(cache)
+    }
+
+    fn save(&self, game: &GameInfo) -> Result<()> {
+        let path = game_config_path()?.join(format!("{CACHE_FILE_NAME_START}{}{CACHE_FILE_NAME_END}", game.key()));
+
+        // Make sure the path exists to avoid problems with updating schemas.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&serde_json::to_vec(&self)?)?;
+        Ok(())
+    }
+}
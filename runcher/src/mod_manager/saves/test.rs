@@ -0,0 +1,100 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the mods used by saves.
+
+use rpfm_lib::files::esf::RecordNode;
+use rpfm_lib::games::pfh_file_type::PFHFileType;
+
+use super::*;
+use crate::mod_manager::mods::Mod;
+
+fn record(name: &str, children: Vec<Vec<NodeType>>) -> NodeType {
+    let mut record = RecordNode::default();
+    record.set_name(name.to_owned());
+    record.set_children(children);
+    NodeType::Record(record.into())
+}
+
+fn mod_history_entry(pack_name: &str) -> Vec<NodeType> {
+    vec![NodeType::Ascii(pack_name.to_owned()), NodeType::Ascii("data".to_owned())]
+}
+
+fn save(mods: &[&str]) -> Save {
+    Save {
+        name: "autosave.save".to_owned(),
+        mods: mods.iter().map(|mod_id| mod_id.to_string()).collect(),
+        mods_read: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_mods_from_esf() {
+    let root = record("CAMPAIGN_SAVE_GAME", vec![vec![
+        record("CAMPAIGN_ENV", vec![]),
+        record("SAVE_GAME_HEADER", vec![vec![
+            record("mod_history_block_name", vec![
+                mod_history_entry("b.pack"),
+                mod_history_entry("a.pack"),
+                vec![],
+            ]),
+        ]]),
+    ]]);
+
+    // Mods are returned in the order the save has them.
+    assert_eq!(Save::mods_from_esf(&root), Some(vec!["b.pack".to_owned(), "a.pack".to_owned()]));
+
+    // Saves without mod history are not the same as saves without mods.
+    let root = record("CAMPAIGN_SAVE_GAME", vec![vec![record("SAVE_GAME_HEADER", vec![])]]);
+    assert_eq!(Save::mods_from_esf(&root), None);
+
+    let root = record("CAMPAIGN_SAVE_GAME", vec![vec![record("SAVE_GAME_HEADER", vec![vec![record("mod_history_block_name", vec![])]])]]);
+    assert_eq!(Save::mods_from_esf(&root), Some(vec![]));
+}
+
+#[test]
+fn test_load_order_differences() {
+    let mut game_config = GameConfig::default();
+    for (id, pack_type) in [("a.pack", PFHFileType::Mod), ("b.pack", PFHFileType::Mod), ("movies.pack", PFHFileType::Movie)] {
+        let mut modd = Mod::default();
+        modd.set_id(id.to_owned());
+        modd.set_pack_type(pack_type);
+        modd.set_paths(vec![PathBuf::from("/mods").join(id)]);
+        game_config.mods_mut().insert(id.to_owned(), modd);
+    }
+
+    let mut load_order = LoadOrder::default();
+    load_order.set_mods(vec!["a.pack".to_owned(), "b.pack".to_owned()]);
+    load_order.set_movies(vec!["movies.pack".to_owned()]);
+
+    let (missing, extra) = save(&["a.pack", "c.pack", "movies.pack"]).load_order_differences(&game_config, &load_order).unwrap();
+    assert_eq!(missing, vec!["c.pack".to_owned()]);
+    assert_eq!(extra, vec!["b.pack".to_owned()]);
+
+    // Movie packs count too.
+    let (missing, extra) = save(&["a.pack", "b.pack"]).load_order_differences(&game_config, &load_order).unwrap();
+    assert!(missing.is_empty());
+    assert_eq!(extra, vec!["movies.pack".to_owned()]);
+
+    // Saves made without mods are not skipped.
+    let (missing, extra) = save(&[]).load_order_differences(&game_config, &load_order).unwrap();
+    assert!(missing.is_empty());
+    assert_eq!(extra.len(), 3);
+
+    // Saves we didn't read yet or couldn't read cannot be compared.
+    let mut unread = save(&[]);
+    unread.set_mods_read(false);
+    assert!(unread.load_order_differences(&game_config, &load_order).is_err());
+
+    let mut unreadable = save(&[]);
+    unreadable.set_read_error(Some("Unsupported ESF format.".to_owned()));
+    assert!(unreadable.load_order_differences(&game_config, &load_order).is_err());
+}
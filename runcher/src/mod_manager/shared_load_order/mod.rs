@@ -0,0 +1,501 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the format of the load order strings users can share between Runcher instances.
+
+use anyhow::{anyhow, Result};
+use base64::{Engine as _, engine::general_purpose};
+use directories::BaseDirs;
+use getset::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use zstd::stream::*;
+
+use std::path::{Path, PathBuf};
+
+use rpfm_lib::games::{GameInfo, supported_games::*};
+
+use crate::games::launch_options::LaunchOptions;
+use crate::SUPPORTED_GAMES;
+
+use super::game_config::{GameConfig, DEFAULT_CATEGORY};
+use super::load_order::LoadOrder;
+use super::mods::{Mod, ShareableMod};
+
+#[cfg(test)] mod test;
+
+/// Version of the format we generate. Strings generated before the format was versioned are version 0.
+pub const SHARED_LOAD_ORDER_VERSION: u32 = 1;
+
+/// Keys other launchers use for the lists of mods in their exports, when the list is not the root of the file.
+const JSON_LIST_KEYS: [&str; 7] = ["mods", "modList", "mod_list", "loadOrder", "load_order", "packs", "items"];
+
+/// Keys other launchers use for each value of a mod in their exports. The first one found is used.
+const JSON_PACK_KEYS: [&str; 10] = ["packfile", "packFile", "pack_file", "pack", "packName", "pack_name", "fileName", "file_name", "filename", "id"];
+const JSON_STEAM_ID_KEYS: [&str; 7] = ["uuid", "workshopId", "workshop_id", "steamId", "steam_id", "publishedFileId", "published_file_id"];
+const JSON_NAME_KEYS: [&str; 4] = ["name", "title", "displayName", "humanName"];
+const JSON_ENABLED_KEYS: [&str; 5] = ["active", "enabled", "isEnabled", "is_enabled", "checked"];
+const JSON_ORDER_KEYS: [&str; 5] = ["order", "loadOrder", "load_order", "index", "position"];
+const JSON_GAME_KEYS: [&str; 2] = ["game", "gameId"];
+
+/// File where the official launcher keeps the mods of all the games it manages, and which ones are enabled.
+const CA_LAUNCHER_MOD_DATA_FILE_NAME: &str = "20190104-moddata.dat";
+
+/// Games whose mods are managed through the mod data file of the official launcher.
+const CA_LAUNCHER_GAMES: [&str; 6] = [KEY_PHARAOH_DYNASTIES, KEY_PHARAOH, KEY_WARHAMMER_3, KEY_TROY, KEY_THREE_KINGDOMS, KEY_WARHAMMER_2];
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Load order in a format that can be shared between Runcher instances.
+#[derive(Clone, Debug, Default, Getters, Setters, Serialize, Deserialize)]
+#[getset(get = "pub", set = "pub")]
+#[serde(default)]
+pub struct SharedLoadOrder {
+
+    // Version of the format.
+    version: u32,
+
+    // Key of the game the load order is for. Empty if we don't know it, like with version 0 strings or mod lists.
+    game: String,
+
+    // Enabled mods, in load order.
+    mods: Vec<ShareableMod>,
+
+    // Enabled movie packs. Their order is always automatic.
+    movies: Vec<ShareableMod>,
+
+    // Categories of the shared mods, in order.
+    categories: Vec<SharedCategory>,
+
+    // Launch options used with the load order, if the user chose to share them.
+    launch_options: Option<LaunchOptions>,
+}
+
+/// Category of a shared load order, with the ids of its mods in order.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct SharedCategory {
+    name: String,
+    mods: Vec<String>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl SharedLoadOrder {
+
+    /// This function builds a shared load order from the enabled mods of the provided load order.
+    pub fn new(game: &GameInfo, game_config: &GameConfig, load_order: &LoadOrder, game_data_path: &Path, launch_options: Option<LaunchOptions>) -> Self {
+        let shareable = |ids: &[String]| ids.par_iter()
+            .filter_map(|mod_id| game_config.mods().get(mod_id))
+            .filter(|modd| modd.enabled(game_data_path) && !modd.paths().is_empty())
+            .map(ShareableMod::from)
+            .collect::<Vec<_>>();
+
+        let mods = shareable(load_order.mods());
+        let movies = shareable(load_order.movies());
+
+        // Only share the categories of the mods we share, as the rest mean nothing to whoever imports this.
+        let categories = game_config.categories_order()
+            .iter()
+            .filter_map(|name| {
+                let mods = game_config.categories().get(name)?
+                    .iter()
+                    .filter(|mod_id| mods.iter().chain(movies.iter()).any(|modd| modd.id() == *mod_id))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if mods.is_empty() {
+                    None
+                } else {
+                    Some(SharedCategory {
+                        name: name.to_owned(),
+                        mods,
+                    })
+                }
+            })
+            .collect();
+
+        Self {
+            version: SHARED_LOAD_ORDER_VERSION,
+            game: game.key().to_owned(),
+            mods,
+            movies,
+            categories,
+            launch_options,
+        }
+    }
+
+    /// This function builds a shared load order for an unknown game from a list of mods, like the ones of a save or a mod list file.
+    pub fn from_mods(mods: Vec<ShareableMod>) -> Self {
+        Self {
+            version: SHARED_LOAD_ORDER_VERSION,
+            mods,
+            ..Default::default()
+        }
+    }
+
+    /// This function builds a shared load order from the mod list file written by the official launcher, for the provided game.
+    ///
+    /// Both the json mod data file of the current launcher and the `mod "x.pack";` lines of the used mods file are supported.
+    pub fn from_ca_launcher(string: &str, game: &GameInfo) -> Result<Self> {
+        match serde_json::from_str::<Value>(string) {
+            Ok(value) => Ok(Self::from_mods(mods_from_json(&value, Some(game))?)),
+            Err(_) => {
+                let mods = mods_from_mod_list(string);
+                if mods.is_empty() {
+                    Err(anyhow!("No mods found. Make sure you pasted the contents of the mod data file of the official launcher, or of its used mods file."))
+                } else {
+                    Ok(Self::from_mods(mods))
+                }
+            }
+        }
+    }
+
+    /// This function returns the mod data file of the official launcher, with the mods of this load order as the only enabled mods for the provided game.
+    ///
+    /// Entries of other games are kept as they are, and entries of this game not in the load order are kept disabled,
+    /// so the launcher doesn't lose the data it has about them. Mods not in the launcher yet are added with the data the launcher needs.
+    pub fn to_ca_launcher(&self, game: &GameInfo, game_config: &GameConfig, mod_data: Option<&str>) -> Result<String> {
+        let mut entries = match mod_data {
+            Some(mod_data) if !mod_data.trim().is_empty() => match serde_json::from_str::<Value>(mod_data)? {
+                Value::Array(entries) => entries,
+                _ => return Err(anyhow!("The mod data file of the official launcher is not a list of mods.")),
+            },
+            _ => vec![],
+        };
+
+        let is_this_game = |entry: &Value| entry.get("game").and_then(|game_key| game_key.as_str()).map(|game_key| same_game(game, game_key)).unwrap_or(false);
+
+        // The launcher doesn't use our game keys, so reuse the one it already uses if we can.
+        let launcher_game_key = entries.iter()
+            .filter(|entry| is_this_game(entry))
+            .find_map(|entry| entry.get("game").and_then(|game_key| game_key.as_str()).map(|game_key| game_key.to_owned()))
+            .unwrap_or_else(|| game.key().replace('_', ""));
+
+        for entry in entries.iter_mut().filter(|entry| is_this_game(entry)) {
+            if let Value::Object(object) = entry {
+                object.insert("active".to_owned(), Value::Bool(false));
+            }
+        }
+
+        let mut order = 0;
+        for modd in self.mods.iter().chain(self.movies.iter()) {
+            let pack_path = match game_config.mods().get(modd.id()).and_then(|modd| modd.paths().first()) {
+                Some(pack_path) => pack_path,
+                None => continue,
+            };
+
+            order += 1;
+            let existing = entries.iter_mut()
+                .filter(|entry| is_this_game(entry))
+                .find(|entry| json_string(entry.as_object().unwrap_or(&Map::new()), &JSON_PACK_KEYS).map(|pack| pack_file_name(&pack) == *modd.id()).unwrap_or(false));
+
+            match existing {
+                Some(Value::Object(object)) => {
+                    object.insert("active".to_owned(), Value::Bool(true));
+                    object.insert("order".to_owned(), json!(order));
+                },
+                _ => {
+                    let packfile = pack_path.to_string_lossy().replace('\\', "/");
+                    let human_name = if modd.name().is_empty() { modd.id() } else { modd.name() };
+                    let short = modd.id().strip_suffix(".pack").unwrap_or(modd.id());
+                    let uuid = modd.steam_id().clone().unwrap_or_else(|| modd.id().to_owned());
+
+                    entries.push(json!({
+                        "game": launcher_game_key,
+                        "packfile": packfile,
+                        "name": modd.id(),
+                        "humanName": human_name,
+                        "short": short,
+                        "category": "",
+                        "owned": true,
+                        "active": true,
+                        "uuid": uuid,
+                        "order": order,
+                    }));
+                },
+            }
+        }
+
+        // Disabled mods go after the enabled ones, in the order they're in the file.
+        for entry in entries.iter_mut().filter(|entry| is_this_game(entry) && entry.get("active") == Some(&Value::Bool(false))) {
+            order += 1;
+            if let Value::Object(object) = entry {
+                object.insert("order".to_owned(), json!(order));
+            }
+        }
+
+        Ok(serde_json::to_string(&entries)?)
+    }
+
+    /// This function builds a shared load order from the json export of another mod manager.
+    ///
+    /// As each mod manager uses its own format, this looks for a list of mods with the usual keys for the pack name, Workshop id, name, state and order of each mod.
+    pub fn from_json_export(string: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(string).map_err(|error| anyhow!("The text is not valid json: {}.", error))?;
+        Ok(Self::from_mods(mods_from_json(&value, None)?))
+    }
+
+    /// This function builds a shared load order from the mods of a Workshop collection, in the order they're in the collection.
+    pub fn from_workshop_collection(items: &[Mod]) -> Self {
+        let mods = items.iter()
+            .map(|item| {
+                let mut modd = ShareableMod::default();
+                modd.set_name(item.name().to_owned());
+                modd.set_steam_id(item.steam_id().clone());
+
+                // The pack name is only known if the item has been uploaded with it. If not, the Workshop id will have to do.
+                if item.file_name().is_empty() {
+                    modd.set_id(item.steam_id().clone().unwrap_or_default());
+                } else if let Some(alt_name) = item.alt_name() {
+                    modd.set_id(alt_name);
+                } else {
+                    modd.set_id(pack_file_name(item.file_name()));
+                }
+
+                modd
+            })
+            .collect();
+
+        Self::from_mods(mods)
+    }
+
+    /// This function encodes the shared load order into a string users can copy.
+    pub fn encode(&self) -> Result<String> {
+        let data = serde_json::to_vec(self)?;
+        let mut compressed = vec![];
+        copy_encode(data.as_slice(), &mut compressed, 3)?;
+
+        Ok(general_purpose::STANDARD_NO_PAD.encode(compressed))
+    }
+
+    /// This function decodes a shared load order from a string generated by any version of Runcher.
+    pub fn decode(string: &str) -> Result<Self> {
+        let debased = general_purpose::STANDARD_NO_PAD.decode(string.trim().as_bytes())?;
+        let mut decompressed = vec![];
+        copy_decode(debased.as_slice(), &mut decompressed)?;
+
+        // Version 0 strings are just the list of mods.
+        if decompressed.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'[') {
+            let mods: Vec<ShareableMod> = serde_json::from_slice(&decompressed)?;
+            let mut shared = Self::from_mods(mods);
+            shared.version = 0;
+            return Ok(shared);
+        }
+
+        let shared: Self = serde_json::from_slice(&decompressed)?;
+        if shared.version > SHARED_LOAD_ORDER_VERSION {
+            return Err(anyhow!("This load order was generated by a newer version of Runcher (format version {}, while this version only supports up to {}). Update Runcher to import it.", shared.version, SHARED_LOAD_ORDER_VERSION));
+        }
+
+        Ok(shared)
+    }
+
+    /// This function checks if the shared load order can be imported into the provided game.
+    pub fn check_game(&self, game: &GameInfo) -> Result<()> {
+        if self.game.is_empty() || self.game == game.key() {
+            return Ok(());
+        }
+
+        let game_name = match SUPPORTED_GAMES.game(&self.game) {
+            Some(game) => game.display_name().to_owned(),
+            None => self.game.to_owned(),
+        };
+
+        Err(anyhow!("This load order is for {}, but the game selected is {}. Select {} and try again.", game_name, game.display_name(), game_name))
+    }
+
+    /// This function replaces the current load order with this one, and moves the shared mods into their shared categories.
+    ///
+    /// Launch options are not applied, as the user may not want them. Returns the mods we couldn't find, and the ones we found but whose packs are different than the expected ones.
+    pub fn apply(&self, game_config: &mut GameConfig, load_order: &mut LoadOrder, game_data_path: &Path) -> Result<(Vec<ShareableMod>, Vec<ShareableMod>)> {
+        let mods = self.mods.iter().chain(self.movies.iter()).cloned().collect::<Vec<_>>();
+        let result = load_order.import_shareable_mod_list(game_config, game_data_path, &mods)?;
+
+        for category in &self.categories {
+            let mod_ids = category.mods.iter()
+                .filter(|mod_id| game_config.mods().get(*mod_id).map(|modd| !modd.paths().is_empty()).unwrap_or(false))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if mod_ids.is_empty() {
+                continue;
+            }
+
+            if !game_config.categories().contains_key(category.name()) {
+                if category.name() == DEFAULT_CATEGORY {
+                    game_config.categories_mut().insert(DEFAULT_CATEGORY.to_owned(), vec![]);
+                    game_config.categories_order_mut().push(DEFAULT_CATEGORY.to_owned());
+                } else {
+                    game_config.create_category(category.name());
+                }
+            }
+
+            for mods in game_config.categories_mut().values_mut() {
+                mods.retain(|mod_id| !mod_ids.contains(mod_id));
+            }
+
+            if let Some(mods) = game_config.categories_mut().get_mut(category.name()) {
+                mods.extend(mod_ids);
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// This function returns the path of the mod data file of the official launcher, if the provided game uses it.
+pub fn ca_launcher_mod_data_path(game: &GameInfo) -> Result<PathBuf> {
+    if !CA_LAUNCHER_GAMES.contains(&game.key()) {
+        return Err(anyhow!("This game ({}) is not managed by the official launcher, so its mods cannot be exported to it.", game.display_name()));
+    }
+
+    BaseDirs::new()
+        .map(|base_dirs| base_dirs.config_dir().join("The Creative Assembly").join("Launcher").join(CA_LAUNCHER_MOD_DATA_FILE_NAME))
+        .ok_or_else(|| anyhow!("Error getting the config folder of the official launcher."))
+}
+
+/// This function returns the mods in a list of `mod "x.pack";` lines.
+pub fn mods_from_mod_list(string: &str) -> Vec<ShareableMod> {
+    let mut mods = vec![];
+    for line in string.lines() {
+        if let Some(start) = line.find("mod \"") {
+            if let Some(mod_id) = line.get(start + 5 ..).and_then(|rest| rest.split('"').next()) {
+                if !mod_id.is_empty() {
+                    let mut modd = ShareableMod::default();
+                    modd.set_id(mod_id.to_owned());
+
+                    mods.push(modd);
+                }
+            }
+        }
+    }
+
+    mods
+}
+
+/// This function returns the enabled mods in a json list of mods, in order.
+///
+/// If a game is provided and the mods say which game they're for, only the ones for that game are returned.
+fn mods_from_json(value: &Value, game: Option<&GameInfo>) -> Result<Vec<ShareableMod>> {
+    let list = match value {
+        Value::Array(list) => list,
+        Value::Object(object) => JSON_LIST_KEYS.iter()
+            .find_map(|key| object.get(*key).and_then(|value| value.as_array()))
+            .ok_or_else(|| anyhow!("No list of mods found in the json."))?,
+        _ => return Err(anyhow!("No list of mods found in the json.")),
+    };
+
+    let mut entries = vec![];
+    let mut other_games = false;
+    for (index, entry) in list.iter().enumerate() {
+        let mut modd = ShareableMod::default();
+        let mut order = index as f64;
+
+        match entry {
+
+            // Plain lists contain either pack names or Workshop ids.
+            Value::String(string) => {
+                if is_steam_id(string) {
+                    modd.set_steam_id(Some(string.to_owned()));
+                } else {
+                    modd.set_id(pack_file_name(string));
+                }
+            },
+            Value::Object(object) => {
+                if let (Some(game), Some(mod_game)) = (game, json_string(object, &JSON_GAME_KEYS)) {
+                    if !same_game(game, &mod_game) {
+                        other_games = true;
+                        continue;
+                    }
+                }
+
+                if let Some(Value::Bool(false)) = JSON_ENABLED_KEYS.iter().find_map(|key| object.get(*key)) {
+                    continue;
+                }
+
+                modd.set_steam_id(json_string(object, &JSON_STEAM_ID_KEYS).filter(|steam_id| is_steam_id(steam_id)));
+
+                // Some exports use the Workshop id as id of the mod.
+                if let Some(pack) = json_string(object, &JSON_PACK_KEYS) {
+                    if !is_steam_id(&pack) {
+                        modd.set_id(pack_file_name(&pack));
+                    } else if modd.steam_id().is_none() {
+                        modd.set_steam_id(Some(pack));
+                    }
+                }
+
+                modd.set_name(json_string(object, &JSON_NAME_KEYS).unwrap_or_default());
+
+                if let Some(value) = JSON_ORDER_KEYS.iter().find_map(|key| object.get(*key).and_then(|value| value.as_f64())) {
+                    order = value;
+                }
+            },
+            _ => continue,
+        }
+
+        // Entries we cannot identify by pack are still kept, so they get reported as missing.
+        if modd.id().is_empty() {
+            let id = match modd.steam_id() {
+                Some(steam_id) => steam_id.to_owned(),
+                None => modd.name().to_owned(),
+            };
+
+            if id.is_empty() {
+                continue;
+            }
+
+            modd.set_id(id);
+        }
+
+        entries.push((order, modd));
+    }
+
+    if entries.is_empty() && other_games {
+        if let Some(game) = game {
+            return Err(anyhow!("The mod list doesn't contain mods for {}.", game.display_name()));
+        }
+    }
+
+    entries.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    Ok(entries.into_iter().map(|(_, modd)| modd).collect())
+}
+
+/// This function returns the first value of the provided keys as a string, if any.
+fn json_string(object: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter().find_map(|key| match object.get(*key)? {
+        Value::String(string) if !string.is_empty() => Some(string.to_owned()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    })
+}
+
+/// This function checks if the provided string is a Workshop id.
+pub fn is_steam_id(string: &str) -> bool {
+    !string.is_empty() && string.chars().all(|char| char.is_ascii_digit())
+}
+
+/// This function returns the file name of a pack, as some launchers store full paths.
+fn pack_file_name(pack: &str) -> String {
+    pack.rsplit(['/', '\\']).next().unwrap_or(pack).to_owned()
+}
+
+/// This function checks if a game name from another launcher refers to the provided game.
+///
+/// Other launchers don't use our keys, but they're the same once we remove separators (warhammer3 vs warhammer_3).
+fn same_game(game: &GameInfo, other: &str) -> bool {
+    let normalize = |key: &str| key.chars().filter(|char| char.is_ascii_alphanumeric()).collect::<String>().to_lowercase();
+    normalize(game.key()) == normalize(other)
+}
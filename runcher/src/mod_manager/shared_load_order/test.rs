@@ -0,0 +1,212 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the formats load orders can be shared in.
+
+use std::fs::read_to_string;
+
+use super::*;
+
+fn load_order_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_files/load_orders").join(name)
+}
+
+fn shareable_mod(id: &str, steam_id: Option<&str>) -> ShareableMod {
+    let mut modd = ShareableMod::default();
+    modd.set_id(id.to_owned());
+    modd.set_steam_id(steam_id.map(|steam_id| steam_id.to_owned()));
+    modd
+}
+
+fn ids(mods: &[ShareableMod]) -> Vec<&str> {
+    mods.iter().map(|modd| modd.id().as_str()).collect()
+}
+
+#[test]
+fn test_ca_launcher_export() {
+    let game = SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap();
+    let mod_data = read_to_string(load_order_path("ca_launcher_moddata.dat")).unwrap();
+
+    let mut game_config = GameConfig::default();
+    for (id, path) in [
+        ("my_local_mod.pack", "C:/Games/Total War WARHAMMER III/data/my_local_mod.pack"),
+        ("mixu_unlocker.pack", "C:/Steam/steamapps/workshop/content/1142710/2789857593/mixu_unlocker.pack"),
+        ("new_mod.pack", "C:/Games/Total War WARHAMMER III/data/new_mod.pack"),
+    ] {
+        let mut modd = Mod::default();
+        modd.set_id(id.to_owned());
+        modd.set_paths(vec![PathBuf::from(path)]);
+        game_config.mods_mut().insert(id.to_owned(), modd);
+    }
+
+    let shared = SharedLoadOrder::from_mods(vec![
+        shareable_mod("my_local_mod.pack", None),
+        shareable_mod("new_mod.pack", None),
+        shareable_mod("mixu_unlocker.pack", Some("2789857593")),
+    ]);
+
+    let exported = shared.to_ca_launcher(game, &game_config, Some(&mod_data)).unwrap();
+
+    // What the launcher enables is exactly our load order, in our order.
+    let imported = SharedLoadOrder::from_ca_launcher(&exported, game).unwrap();
+    assert_eq!(ids(imported.mods()), vec!["my_local_mod.pack", "new_mod.pack", "mixu_unlocker.pack"]);
+
+    // Mods we don't use are kept, but disabled, and mods of other games are not touched.
+    let entries: Vec<Value> = serde_json::from_str(&exported).unwrap();
+    assert_eq!(entries.len(), 5);
+
+    let entry = |name: &str| entries.iter().find(|entry| entry["name"] == name).unwrap();
+    assert_eq!(entry("!!!!!!Mixus_Tabletop_Caps.pack")["active"], false);
+    assert_eq!(entry("!!!!!!Mixus_Tabletop_Caps.pack")["order"], 4);
+    assert_eq!(entry("sfo_grimhammer_2.pack")["active"], true);
+    assert_eq!(entry("sfo_grimhammer_2.pack")["order"], 1);
+
+    // New mods use the game key of the launcher, and keep the launcher data of the existing ones.
+    assert_eq!(entry("new_mod.pack")["game"], "warhammer3");
+    assert_eq!(entry("new_mod.pack")["packfile"], "C:/Games/Total War WARHAMMER III/data/new_mod.pack");
+    assert_eq!(entry("mixu_unlocker.pack")["category"], "Units");
+
+    // Runcher-only folders never end up in the launcher's data.
+    assert!(!exported.contains("add_working_directory"));
+}
+
+#[test]
+fn test_ca_launcher_export_without_mod_data() {
+    let game = SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap();
+
+    let mut game_config = GameConfig::default();
+    let mut modd = Mod::default();
+    modd.set_id("my_mod.pack".to_owned());
+    modd.set_paths(vec![PathBuf::from("C:\\Games\\data\\my_mod.pack")]);
+    game_config.mods_mut().insert("my_mod.pack".to_owned(), modd);
+
+    let shared = SharedLoadOrder::from_mods(vec![shareable_mod("my_mod.pack", None)]);
+    let exported = shared.to_ca_launcher(game, &game_config, None).unwrap();
+
+    let entries: Vec<Value> = serde_json::from_str(&exported).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["packfile"], "C:/Games/data/my_mod.pack");
+    assert_eq!(entries[0]["active"], true);
+    assert_eq!(entries[0]["order"], 1);
+
+    // Games the launcher doesn't manage cannot be exported.
+    assert!(ca_launcher_mod_data_path(SUPPORTED_GAMES.game(KEY_EMPIRE).unwrap()).is_err());
+}
+
+#[test]
+fn test_ca_launcher_import() {
+    let mod_data = read_to_string(load_order_path("ca_launcher_moddata.dat")).unwrap();
+
+    // Only the enabled mods of the game are imported, in the launcher's order.
+    let imported = SharedLoadOrder::from_ca_launcher(&mod_data, SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap()).unwrap();
+    assert_eq!(ids(imported.mods()), vec!["!!!!!!Mixus_Tabletop_Caps.pack", "mixu_unlocker.pack"]);
+    assert_eq!(imported.mods()[0].steam_id(), &Some("2792731173".to_owned()));
+    assert_eq!(imported.mods()[1].name(), "mixu_unlocker.pack");
+
+    let imported = SharedLoadOrder::from_ca_launcher(&mod_data, SUPPORTED_GAMES.game(KEY_WARHAMMER_2).unwrap()).unwrap();
+    assert_eq!(ids(imported.mods()), vec!["sfo_grimhammer_2.pack"]);
+
+    // Games without mods in the file are an error, not an empty load order.
+    assert!(SharedLoadOrder::from_ca_launcher(&mod_data, SUPPORTED_GAMES.game(KEY_TROY).unwrap()).is_err());
+}
+
+#[test]
+fn test_ca_launcher_used_mods_import() {
+    let used_mods = read_to_string(load_order_path("used_mods.txt")).unwrap();
+
+    let imported = SharedLoadOrder::from_ca_launcher(&used_mods, SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap()).unwrap();
+    assert_eq!(ids(imported.mods()), vec!["!!!!!!Mixus_Tabletop_Caps.pack", "mixu_unlocker.pack"]);
+    assert!(imported.mods().iter().all(|modd| modd.steam_id().is_none()));
+
+    assert!(SharedLoadOrder::from_ca_launcher("not a mod list", SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap()).is_err());
+}
+
+#[test]
+fn test_json_export_import() {
+    let export = read_to_string(load_order_path("mod_manager_export.json")).unwrap();
+
+    // Disabled mods are skipped, full paths are reduced to the pack name, and mods without pack use their Workshop id.
+    let imported = SharedLoadOrder::from_json_export(&export).unwrap();
+    assert_eq!(ids(imported.mods()), vec!["mixu_unlocker.pack", "2792731173", "my_local_mod.pack"]);
+    assert_eq!(imported.mods()[0].steam_id(), &Some("2789857593".to_owned()));
+    assert_eq!(imported.mods()[1].steam_id(), &Some("2792731173".to_owned()));
+    assert_eq!(imported.mods()[2].steam_id(), &None);
+    assert_eq!(imported.mods()[2].name(), "My Local Mod");
+
+    assert!(SharedLoadOrder::from_json_export("{ \"mods\": ").is_err());
+    assert!(SharedLoadOrder::from_json_export("{ \"name\": \"No mods here\" }").is_err());
+}
+
+#[test]
+fn test_mods_from_json_plain_list() {
+    let value = serde_json::from_str::<Value>(r#"["a.pack", "2789857593", "data/b.pack", 5, ""]"#).unwrap();
+    let mods = mods_from_json(&value, None).unwrap();
+
+    assert_eq!(ids(&mods), vec!["a.pack", "2789857593", "b.pack"]);
+    assert_eq!(mods[1].steam_id(), &Some("2789857593".to_owned()));
+}
+
+#[test]
+fn test_encode_decode() {
+    let mut shared = SharedLoadOrder::from_mods(vec![
+        shareable_mod("a.pack", Some("1234")),
+        shareable_mod("b.pack", None),
+    ]);
+    shared.set_game(KEY_WARHAMMER_3.to_owned());
+    shared.set_movies(vec![shareable_mod("movies.pack", None)]);
+
+    let decoded = SharedLoadOrder::decode(&shared.encode().unwrap()).unwrap();
+    assert_eq!(*decoded.version(), SHARED_LOAD_ORDER_VERSION);
+    assert_eq!(decoded.game(), KEY_WARHAMMER_3);
+    assert_eq!(ids(decoded.mods()), vec!["a.pack", "b.pack"]);
+    assert_eq!(decoded.mods()[0].steam_id(), &Some("1234".to_owned()));
+    assert_eq!(ids(decoded.movies()), vec!["movies.pack"]);
+}
+
+#[test]
+fn test_decode_version_0() {
+
+    // Version 0 strings are just the compressed list of mods.
+    let mods = vec![shareable_mod("a.pack", Some("1234")), shareable_mod("b.pack", None)];
+    let data = serde_json::to_vec(&mods).unwrap();
+    let mut compressed = vec![];
+    copy_encode(data.as_slice(), &mut compressed, 3).unwrap();
+    let string = general_purpose::STANDARD_NO_PAD.encode(compressed);
+
+    let decoded = SharedLoadOrder::decode(&string).unwrap();
+    assert_eq!(*decoded.version(), 0);
+    assert!(decoded.game().is_empty());
+    assert_eq!(ids(decoded.mods()), vec!["a.pack", "b.pack"]);
+
+    // Without game, they can be imported into any game.
+    assert!(decoded.check_game(SUPPORTED_GAMES.game(KEY_WARHAMMER_2).unwrap()).is_ok());
+}
+
+#[test]
+fn test_decode_newer_version() {
+    let mut shared = SharedLoadOrder::from_mods(vec![shareable_mod("a.pack", None)]);
+    shared.version = SHARED_LOAD_ORDER_VERSION + 1;
+
+    assert!(SharedLoadOrder::decode(&shared.encode().unwrap()).is_err());
+    assert!(SharedLoadOrder::decode("not a load order").is_err());
+}
+
+#[test]
+fn test_check_game() {
+    let mut shared = SharedLoadOrder::from_mods(vec![shareable_mod("a.pack", None)]);
+    shared.set_game(KEY_WARHAMMER_3.to_owned());
+
+    assert!(shared.check_game(SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap()).is_ok());
+
+    let error = shared.check_game(SUPPORTED_GAMES.game(KEY_WARHAMMER_2).unwrap()).unwrap_err().to_string();
+    let wh3 = SUPPORTED_GAMES.game(KEY_WARHAMMER_3).unwrap().display_name();
+    let wh2 = SUPPORTED_GAMES.game(KEY_WARHAMMER_2).unwrap().display_name();
+    assert_eq!(error, format!("This load order is for {}, but the game selected is {}. Select {} and try again.", wh3, wh2, wh3));
+}
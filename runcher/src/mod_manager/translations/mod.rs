@@ -0,0 +1,322 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the code to check how much of the load order is translated to a language, and to edit local translations.
+
+use anyhow::{anyhow, Result};
+use getset::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use rpfm_extensions::translator::{PackTranslation, Translation};
+
+use rpfm_lib::files::{Container, FileType, pack::Pack, RFileDecoded};
+use rpfm_lib::games::GameInfo;
+
+use crate::games::translation_paths;
+use crate::settings_ui::{translations_local_path, translations_remote_path};
+
+use super::game_config::GameConfig;
+use super::load_order::LoadOrder;
+
+#[cfg(test)] mod test;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Report of the translation status of the packs of a load order for a specific language.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct TranslationCoverageReport {
+
+    // Language the report was generated for.
+    language: String,
+
+    // Packs of the load order with loc files, in load order.
+    packs: Vec<PackTranslationCoverage>,
+}
+
+/// Translation status of the loc keys of a pack.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct PackTranslationCoverage {
+
+    // Name of the pack.
+    pack: String,
+
+    // If there is a translation for this pack in the translation folders.
+    has_translation: bool,
+
+    // Amount of loc keys translated and up to date.
+    translated: usize,
+
+    // Keys without translation, sorted. These are shown in english ingame.
+    untranslated: Vec<String>,
+
+    // Keys whose translation is marked as outdated, sorted. These are shown in english ingame.
+    needs_retranslation: Vec<String>,
+}
+
+/// Loc key of a pack, with its translation from the translation hub and its local translation, if any.
+#[derive(Clone, Debug, Default, Getters, Setters)]
+#[getset(get = "pub", set = "pub")]
+pub struct TranslationOverride {
+
+    // Key of the loc entry.
+    key: String,
+
+    // Current english text of the key, from the pack.
+    original: String,
+
+    // Translation from the translation hub. Empty if there is none.
+    hub: String,
+
+    // If the translation from the hub is marked as outdated, so it's not used ingame.
+    hub_outdated: bool,
+
+    // Local translation, overriding the one from the hub. Empty if there is none.
+    local: String,
+}
+
+/// Translation of a loc key, as found in a pack translation.
+#[derive(Clone, Debug, Default)]
+struct TranslationEntry<'a> {
+    translated: &'a str,
+    needs_retranslation: bool,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl TranslationCoverageReport {
+
+    /// This function generates the translation coverage report of the provided load order for the provided language.
+    ///
+    /// It uses the same translations the launch uses: local ones first, then the ones from the translation hub.
+    /// Requires the packs of the load order to be loaded. Only the packs with loc files are opened.
+    pub fn generate(game_config: &GameConfig, game: &GameInfo, load_order: &LoadOrder, language: &str) -> Result<Self> {
+        let translation_paths = translation_paths();
+        if translation_paths.is_empty() {
+            return Err(anyhow!("There are no translation folders available. Make sure the translations are downloaded."));
+        }
+
+        let pack_paths = load_order.movies().iter()
+            .chain(load_order.mods().iter())
+            .filter(|mod_id| match load_order.packs().get(*mod_id) {
+                Some(pack) => pack.files().iter().any(|file| *file.file_type() == FileType::Loc),
+                None => false,
+            })
+            .filter_map(|mod_id| game_config.mods().get(mod_id).and_then(|modd| modd.paths().first()).cloned())
+            .collect::<Vec<_>>();
+
+        let packs = pack_paths.par_iter()
+            .map(|path| PackTranslationCoverage::generate(game, path, &translation_paths, language))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(Self {
+            language: language.to_owned(),
+            packs,
+        })
+    }
+
+    /// This function returns the amount of loc keys in the load order, and how many of them are translated.
+    pub fn totals(&self) -> (usize, usize) {
+        self.packs.iter().fold((0, 0), |(total, translated), pack| (total + pack.total(), translated + pack.translated))
+    }
+}
+
+impl PackTranslationCoverage {
+
+    /// This function checks the loc keys of the pack at the provided path against its translation, if any.
+    ///
+    /// Returns None if the pack has nothing to translate.
+    pub fn generate(game: &GameInfo, pack_path: &Path, translation_paths: &[PathBuf], language: &str) -> Result<Option<Self>> {
+        let pack_name = pack_name(pack_path)?;
+        let values = loc_values(pack_path)?;
+        if values.is_empty() {
+            return Ok(None);
+        }
+
+        let coverage = match load_pack_translation(translation_paths, &pack_name, game.key(), language) {
+            Ok(translation) => {
+                let entries = translation.translations().values()
+                    .map(|tr| (tr.key().as_str(), TranslationEntry {
+                        translated: tr.value_translated(),
+                        needs_retranslation: *tr.needs_retranslation(),
+                    }))
+                    .collect::<HashMap<_, _>>();
+
+                Self::from_entries(&pack_name, &values, Some(&entries))
+            }
+            Err(_) => Self::from_entries(&pack_name, &values, None),
+        };
+
+        Ok(Some(coverage))
+    }
+
+    /// This function classifies the loc values of a pack by their translation status.
+    ///
+    /// Only translations marked as needing retranslation count as outdated. Empty ones count as untranslated.
+    fn from_entries(pack_name: &str, values: &BTreeMap<String, String>, entries: Option<&HashMap<&str, TranslationEntry>>) -> Self {
+        let mut coverage = Self {
+            pack: pack_name.to_owned(),
+            has_translation: entries.is_some(),
+            ..Default::default()
+        };
+
+        // Same rules as the launch: translations are used unless they're empty or marked as outdated.
+        for key in values.keys() {
+            match entries.and_then(|entries| entries.get(key.as_str())) {
+                Some(entry) if entry.needs_retranslation => coverage.needs_retranslation.push(key.to_owned()),
+                Some(entry) if !entry.translated.is_empty() => coverage.translated += 1,
+                _ => coverage.untranslated.push(key.to_owned()),
+            }
+        }
+
+        coverage
+    }
+
+    /// This function returns the amount of loc keys of the pack that need translation.
+    pub fn total(&self) -> usize {
+        self.translated + self.untranslated.len() + self.needs_retranslation.len()
+    }
+
+    /// This function returns the percentage of loc keys of the pack translated and up to date.
+    pub fn percentage(&self) -> f64 {
+        match self.total() {
+            0 => 100.0,
+            total => self.translated as f64 * 100.0 / total as f64,
+        }
+    }
+}
+
+impl TranslationOverride {
+
+    /// This function returns the loc keys of the pack at the provided path, with their translations to the provided language.
+    ///
+    /// Local translations equal to the ones from the hub are not returned, as they don't override anything.
+    pub fn load(game: &GameInfo, pack_path: &Path, language: &str) -> Result<Vec<Self>> {
+        let pack_name = pack_name(pack_path)?;
+        let values = loc_values(pack_path)?;
+
+        let hub = translations_remote_path().ok().and_then(|path| PackTranslation::load(&[path], &pack_name, game.key(), language).ok());
+        let local = translations_local_path().ok().and_then(|path| PackTranslation::load(&[path], &pack_name, game.key(), language).ok());
+
+        Ok(values.into_iter()
+            .map(|(key, original)| {
+                let mut entry = Self::default();
+
+                if let Some(tr) = hub.as_ref().and_then(|hub| hub.translations().get(&key)) {
+                    entry.hub = tr.value_translated().to_owned();
+                    entry.hub_outdated = *tr.needs_retranslation();
+                }
+
+                if let Some(tr) = local.as_ref().and_then(|local| local.translations().get(&key)) {
+                    if *tr.value_translated() != entry.hub {
+                        entry.local = tr.value_translated().to_owned();
+                    }
+                }
+
+                entry.key = key;
+                entry.original = original;
+                entry
+            })
+            .collect())
+    }
+
+    /// This function saves the local translations of the provided entries as the local translation of a pack.
+    ///
+    /// Only the keys with a local translation are saved. The rest keep using the translation from the hub on launch,
+    /// so fixes made in the hub after this still reach them.
+    pub fn save(game: &GameInfo, pack_name: &str, language: &str, entries: &[Self]) -> Result<()> {
+        let mut translation = PackTranslation::default();
+        translation.set_language(language.to_owned());
+        translation.set_pack_name(pack_name.to_owned());
+
+        for translation_local in entries.iter().filter_map(|entry| entry.to_local_translation()) {
+            translation.translations_mut().insert(translation_local.key().to_owned(), translation_local);
+        }
+
+        translation.save(&translations_local_path()?, game.key())
+    }
+
+    /// This function returns the local translation of this key, if any.
+    fn to_local_translation(&self) -> Option<Translation> {
+        if self.local.is_empty() {
+            return None;
+        }
+
+        let mut translation = Translation::default();
+        translation.set_key(self.key.to_owned());
+        translation.set_value_original(self.original.to_owned());
+        translation.set_value_translated(self.local.to_owned());
+        Some(translation)
+    }
+}
+
+/// This function loads the translation of a pack from the provided translation folders, merging them key by key.
+///
+/// Folders go from higher to lower priority, and keys found in more than one folder use the translation from the first one.
+/// This allows local translations to only contain the keys they change, leaving the rest to the translation hub.
+pub fn load_pack_translation(paths: &[PathBuf], pack_name: &str, game_key: &str, language: &str) -> Result<PackTranslation> {
+    let mut merged: Option<PackTranslation> = None;
+
+    for path in paths.iter().rev() {
+        if let Ok(translation) = PackTranslation::load(&[path.to_path_buf()], pack_name, game_key, language) {
+            match merged {
+                Some(ref mut merged) => merged.translations_mut().extend(translation.translations().iter().map(|(key, tr)| (key.to_owned(), tr.clone()))),
+                None => merged = Some(translation),
+            }
+        }
+    }
+
+    merged.ok_or_else(|| anyhow!("There is no translation to {} for {}.", language, pack_name))
+}
+
+/// This function returns the name of the pack at the provided path.
+fn pack_name(pack_path: &Path) -> Result<String> {
+    pack_path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| anyhow!("Invalid pack path: {}.", pack_path.to_string_lossy()))
+}
+
+/// This function returns the english text of all the non-empty loc keys of the pack at the provided path, sorted by key.
+///
+/// Same filter as the launch: only locs in text/ are translated, and the first file defining a key wins.
+fn loc_values(pack_path: &Path) -> Result<BTreeMap<String, String>> {
+    let mut pack = Pack::read_and_merge(&[pack_path.to_path_buf()], true, false, false)?;
+
+    let mut locs = pack.files_by_type_mut(&[FileType::Loc]);
+    locs.retain(|loc| loc.path_in_container_raw().starts_with("text/"));
+    locs.sort_by(|a, b| a.path_in_container_raw().cmp(b.path_in_container_raw()));
+
+    let mut values = BTreeMap::new();
+    for loc in locs {
+        if let Ok(Some(RFileDecoded::Loc(loc))) = loc.decode(&None, false, true) {
+            for row in loc.data().iter() {
+                if row.len() > 1 {
+                    values.entry(row[0].data_to_string().to_string()).or_insert_with(|| row[1].data_to_string().to_string());
+                }
+            }
+        }
+    }
+
+    // Empty values do not need translation.
+    values.retain(|_, value| !value.is_empty());
+    Ok(values)
+}
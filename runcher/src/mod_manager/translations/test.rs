@@ -0,0 +1,107 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the translation coverage reports and the local translations.
+
+use super::*;
+
+fn entry(translated: &str, needs_retranslation: bool) -> TranslationEntry<'_> {
+    TranslationEntry {
+        translated,
+        needs_retranslation,
+    }
+}
+
+#[test]
+fn test_coverage_from_entries() {
+    let values = [
+        ("key_changed", "New text"),
+        ("key_flagged", "Text"),
+        ("key_missing", "Text"),
+        ("key_translated", "Text"),
+        ("key_untranslated", "Text"),
+    ].iter().map(|(key, value)| (key.to_string(), value.to_string())).collect::<BTreeMap<_, _>>();
+
+    let entries = HashMap::from([
+        ("key_changed", entry("Texto viejo", false)),
+        ("key_flagged", entry("Texto", true)),
+        ("key_translated", entry("Texto", false)),
+        ("key_untranslated", entry("", false)),
+    ]);
+
+    // Like in the launch, only the outdated flag makes a translation not to be used, even if the english text changed.
+    let coverage = PackTranslationCoverage::from_entries("my_mod.pack", &values, Some(&entries));
+    assert!(*coverage.has_translation());
+    assert_eq!(*coverage.translated(), 2);
+    assert_eq!(coverage.untranslated(), &vec!["key_missing".to_owned(), "key_untranslated".to_owned()]);
+    assert_eq!(coverage.needs_retranslation(), &vec!["key_flagged".to_owned()]);
+    assert_eq!(coverage.total(), 5);
+    assert_eq!(coverage.percentage(), 40.0);
+
+    let coverage = PackTranslationCoverage::from_entries("my_mod.pack", &values, None);
+    assert!(!*coverage.has_translation());
+    assert_eq!(*coverage.translated(), 0);
+    assert_eq!(coverage.untranslated().len(), 5);
+}
+
+#[test]
+fn test_override_to_local_translation() {
+    let mut entry = TranslationOverride {
+        key: "key".to_owned(),
+        original: "New text".to_owned(),
+        hub: "Texto viejo".to_owned(),
+        hub_outdated: true,
+        local: String::new(),
+    };
+
+    // Without local translation, there's nothing to save, so the hub one keeps being used.
+    assert!(entry.to_local_translation().is_none());
+
+    // Local translations are made for the current text, so they're always up to date.
+    entry.set_local("Texto nuevo".to_owned());
+    let translation = entry.to_local_translation().unwrap();
+    assert_eq!(translation.key(), "key");
+    assert_eq!(translation.value_original(), "New text");
+    assert_eq!(translation.value_translated(), "Texto nuevo");
+    assert!(!*translation.needs_retranslation());
+}
+
+#[test]
+fn test_load_pack_translation_merges_by_key() {
+    let local_path = tempfile::tempdir().unwrap();
+    let hub_path = tempfile::tempdir().unwrap();
+
+    let translation = |entries: &[(&str, &str)]| {
+        let mut translation = PackTranslation::default();
+        translation.set_language("es".to_owned());
+        translation.set_pack_name("my_mod.pack".to_owned());
+
+        for (key, value) in entries {
+            let mut tr = Translation::default();
+            tr.set_key(key.to_string());
+            tr.set_value_original("Text".to_owned());
+            tr.set_value_translated(value.to_string());
+            translation.translations_mut().insert(key.to_string(), tr);
+        }
+
+        translation
+    };
+
+    translation(&[("key_fixed", "Texto arreglado"), ("key_overridden", "Texto del hub")]).save(hub_path.path(), "warhammer_3").unwrap();
+    translation(&[("key_overridden", "Texto local")]).save(local_path.path(), "warhammer_3").unwrap();
+
+    let paths = [local_path.path().to_path_buf(), hub_path.path().to_path_buf()];
+    let merged = load_pack_translation(&paths, "my_mod.pack", "warhammer_3", "es").unwrap();
+    assert_eq!(merged.translations().len(), 2);
+    assert_eq!(merged.translations().get("key_fixed").unwrap().value_translated(), "Texto arreglado");
+    assert_eq!(merged.translations().get("key_overridden").unwrap().value_translated(), "Texto local");
+
+    assert!(load_pack_translation(&paths, "other_mod.pack", "warhammer_3", "es").is_err());
+}
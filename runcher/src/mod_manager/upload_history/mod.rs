@@ -0,0 +1,176 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Module containing the record of what we uploaded of each mod, so we can tell what changed in the next upload.
+
+use anyhow::{anyhow, Result};
+use getset::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha256::digest;
+
+use std::collections::BTreeMap;
+use std::fs::{DirBuilder, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use rpfm_lib::files::{Container, pack::Pack};
+use rpfm_lib::games::GameInfo;
+
+use crate::settings_ui::upload_history_path;
+
+use super::pack_index::file_stamp;
+
+#[cfg(test)] mod test;
+
+const FILE_NAME_END: &str = ".json";
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Contents of a pack when it was uploaded to the Workshop.
+#[derive(Clone, Debug, Default, Getters, Serialize, Deserialize)]
+#[getset(get = "pub")]
+pub struct UploadSnapshot {
+
+    // Size and last modification date of the pack when it was uploaded, to skip checking its files if it didn't change.
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    modified: u64,
+
+    // Hashes of the files of the pack, by path.
+    files: BTreeMap<String, String>,
+}
+
+/// Files that changed between two snapshots of a pack, sorted by path.
+#[derive(Clone, Debug, Default, Getters)]
+#[getset(get = "pub")]
+pub struct PackDiff {
+    added: Vec<String>,
+    changed: Vec<String>,
+    removed: Vec<String>,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl UploadSnapshot {
+
+    /// This function builds a snapshot of the pack at the provided path.
+    pub fn from_pack(pack_path: &Path) -> Result<Self> {
+        let (size, modified) = file_stamp(pack_path).ok_or_else(|| anyhow!("Error reading the metadata of {}.", pack_path.to_string_lossy()))?;
+        let mut pack = Pack::read_and_merge(&[pack_path.to_path_buf()], true, false, false)?;
+
+        let files = pack.files_mut()
+            .par_iter_mut()
+            .map(|(path, file)| {
+                let data = file.encode(&None, false, false, true)?.ok_or_else(|| anyhow!("Error reading {} from {}.", path, pack_path.to_string_lossy()))?;
+                Ok((path.to_owned(), digest(data)))
+            })
+            .collect::<Result<BTreeMap<_, _>>>()?;
+
+        Ok(Self {
+            size,
+            modified,
+            files
+        })
+    }
+
+    /// This function returns if the pack at the provided path is still the one this snapshot was made from, judging by its size and modification date.
+    pub fn is_same_pack(&self, pack_path: &Path) -> bool {
+        file_stamp(pack_path) == Some((self.size, self.modified))
+    }
+
+    /// This function loads the snapshot of the last upload of the provided pack, if there is one.
+    pub fn load(game: &GameInfo, pack_name: &str) -> Result<Option<Self>> {
+        let path = Self::path(game, pack_name)?;
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let mut file = BufReader::new(File::open(path)?);
+        let mut data = Vec::with_capacity(file.get_ref().metadata()?.len() as usize);
+        file.read_to_end(&mut data)?;
+
+        let snapshot: Self = serde_json::from_slice(&data)?;
+        Ok(Some(snapshot))
+    }
+
+    pub fn save(&self, game: &GameInfo, pack_name: &str) -> Result<()> {
+        let path = Self::path(game, pack_name)?;
+
+        // Make sure the path exists to avoid problems with updating schemas.
+        if let Some(parent_folder) = path.parent() {
+            DirBuilder::new().recursive(true).create(parent_folder)?;
+        }
+
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(&serde_json::to_vec(&self)?)?;
+        Ok(())
+    }
+
+    /// This function returns the files that changed from this snapshot to the provided one.
+    pub fn diff(&self, current: &Self) -> PackDiff {
+        let mut diff = PackDiff::default();
+
+        for (path, hash) in &current.files {
+            match self.files.get(path) {
+                Some(old_hash) if old_hash != hash => diff.changed.push(path.to_owned()),
+                Some(_) => {},
+                None => diff.added.push(path.to_owned()),
+            }
+        }
+
+        diff.removed = self.files.keys()
+            .filter(|path| !current.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    fn path(game: &GameInfo, pack_name: &str) -> Result<PathBuf> {
+        Ok(upload_history_path()?.join(game.key()).join(format!("{pack_name}{FILE_NAME_END}")))
+    }
+}
+
+impl PackDiff {
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// This function returns a changelog template listing the changed files, meant to be completed by the author.
+    pub fn changelog(&self) -> String {
+        [("Added", &self.added), ("Changed", &self.changed), ("Removed", &self.removed)].iter()
+            .filter(|(_, paths)| !paths.is_empty())
+            .map(|(title, paths)| format!("{}:\n{}", title, paths.iter().map(|path| format!("- {path}")).collect::<Vec<_>>().join("\n")))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// This function returns a changelog template with the changes of the pack since its last upload.
+///
+/// If we don't know what was uploaded last time, or nothing changed, it returns None. The files are only checked if the pack changed since the upload.
+pub fn changelog_since_last_upload(game: &GameInfo, pack_path: &Path) -> Result<Option<String>> {
+    let pack_name = pack_path.file_name().ok_or_else(|| anyhow!("Invalid pack path: {}.", pack_path.to_string_lossy()))?.to_string_lossy();
+    match UploadSnapshot::load(game, &pack_name)? {
+        Some(last_upload) if last_upload.is_same_pack(pack_path) => Ok(None),
+        Some(last_upload) => {
+            let diff = last_upload.diff(&UploadSnapshot::from_pack(pack_path)?);
+            Ok(if diff.is_empty() { None } else { Some(diff.changelog()) })
+        },
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,60 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/runcher.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/runcher/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the changelogs generated from upload snapshots.
+
+use super::*;
+
+fn snapshot(files: &[(&str, &str)]) -> UploadSnapshot {
+    UploadSnapshot {
+        files: files.iter().map(|(path, hash)| (path.to_string(), hash.to_string())).collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_diff_changelog() {
+    let last_upload = snapshot(&[
+        ("db/units_tables/my_mod", "a"),
+        ("script/campaign/mod/my_mod.lua", "b"),
+        ("text/db/my_mod.loc", "c"),
+    ]);
+
+    let current = snapshot(&[
+        ("db/units_tables/my_mod", "a"),
+        ("db/unit_stats_land_tables/my_mod", "d"),
+        ("text/db/my_mod.loc", "e"),
+    ]);
+
+    assert!(last_upload.diff(&last_upload).is_empty());
+
+    let diff = last_upload.diff(&current);
+    assert_eq!(diff.changelog(), "Added:\n- db/unit_stats_land_tables/my_mod\n\nChanged:\n- text/db/my_mod.loc\n\nRemoved:\n- script/campaign/mod/my_mod.lua");
+}
+
+#[test]
+fn test_is_same_pack() {
+    let folder = tempfile::tempdir().unwrap();
+    let pack_path = folder.path().join("my_mod.pack");
+    std::fs::write(&pack_path, b"PFH5").unwrap();
+
+    let (size, modified) = file_stamp(&pack_path).unwrap();
+    let last_upload = UploadSnapshot {
+        size,
+        modified,
+        ..Default::default()
+    };
+
+    assert!(last_upload.is_same_pack(&pack_path));
+
+    std::fs::write(&pack_path, b"PFH5 with more data").unwrap();
+    assert!(!last_upload.is_same_pack(&pack_path));
+    assert!(!last_upload.is_same_pack(&folder.path().join("missing.pack")));
+}
@@ -19,7 +19,7 @@ use rpfm_ui_common::settings::error_path;
 use crate::CENTRAL_COMMAND;
 use crate::communications::*;
 use crate::games::{TRANSLATIONS_REPO, TRANSLATIONS_REMOTE, TRANSLATIONS_BRANCH};
-use crate::mod_manager::integrations::request_mods_data;
+use crate::mod_manager::integrations::{request_collection_mods, request_mods_data};
 use crate::settings_ui::{schemas_path, translations_remote_path};
 use crate::updater_ui::check_updates_main_program;
 
@@ -88,6 +88,13 @@ pub fn network_loop() {
                 }
             }
 
+            Command::RequestCollectionMods(game, collection_id) => {
+                match request_collection_mods(&game, &collection_id) {
+                    Ok(mods_data) => CentralCommand::send_back(&sender, Response::VecMod(mods_data)),
+                    Err(error) => CentralCommand::send_back(&sender, Response::Error(error)),
+                }
+            }
+
             // If you hit this, you fucked it up somewhere else.
             _ => panic!("{THREADS_COMMUNICATION_ERROR}{response:?}"),
         }
@@ -11,10 +11,14 @@
 use qt_widgets::QGridLayout;
 use qt_widgets::q_header_view::ResizeMode;
 use qt_widgets::QLineEdit;
+use qt_widgets::QMenu;
+use qt_widgets::q_tool_button::ToolButtonPopupMode;
 use qt_widgets::QTabWidget;
 use qt_widgets::QToolButton;
 use qt_widgets::QTreeView;
 
+use qt_gui::QIcon;
+use qt_gui::QKeySequence;
 use qt_gui::QListOfQStandardItem;
 use qt_gui::QStandardItem;
 use qt_gui::QStandardItemModel;
@@ -35,20 +39,20 @@ use cpp_core::CppDeletable;
 use anyhow::Result;
 use getset::*;
 
+use std::cell::RefCell;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 
-use rpfm_lib::files::pack::Pack;
 use rpfm_lib::games::GameInfo;
 use rpfm_lib::integrations::log::error;
 
-use rpfm_ui_common::locale::qtr;
+use rpfm_ui_common::locale::{qtr, tr, tre};
 use rpfm_ui_common::utils::*;
 
 use crate::ffi::*;
 use crate::mod_list_ui::VALUE_MOD_ID;
-use crate::mod_manager::{game_config::GameConfig, load_order::LoadOrder, secondary_mods_path};
+use crate::mod_manager::{conflicts::{ConflictReport, FileConflict}, game_config::GameConfig, load_order::LoadOrder, secondary_mods_path};
 
 use self::slots::PackListUISlots;
 
@@ -57,6 +61,9 @@ mod slots;
 const VIEW_DEBUG: &str = "ui_templates/pack_list_widget.ui";
 const VIEW_RELEASE: &str = "ui/pack_list_widget.ui";
 
+/// Max amount of conflicting files to show in the tooltip of each pack.
+const MAX_CONFLICTS_IN_TOOLTIP: usize = 30;
+
 //-------------------------------------------------------------------------------//
 //                              Enums & Structs
 //-------------------------------------------------------------------------------//
@@ -72,6 +79,15 @@ pub struct PackListUI {
     filter_timer: QBox<QTimer>,
 
     automatic_order_button: QPtr<QToolButton>,
+    data_conflicts_button: QPtr<QToolButton>,
+    translation_coverage_button: QPtr<QToolButton>,
+    undo_button: QPtr<QToolButton>,
+    redo_button: QPtr<QToolButton>,
+    snapshots_button: QPtr<QToolButton>,
+    snapshots_menu: QPtr<QMenu>,
+
+    // Last file conflicts report, reused while the load order packs don't change.
+    file_conflicts: RefCell<ConflictReport>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -92,6 +108,26 @@ impl PackListUI {
         let filter_case_sensitive_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "filter_case_sensitive_button")?;
         let automatic_order_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "automatic_order_button")?;
         automatic_order_button.set_tool_tip(&qtr("automatic_mode_tooltip"));
+        let data_conflicts_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "data_conflicts_button")?;
+        data_conflicts_button.set_tool_tip(&qtr("data_conflicts_tooltip"));
+        let translation_coverage_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "translation_coverage_button")?;
+        translation_coverage_button.set_tool_tip(&qtr("translation_coverage_tooltip"));
+
+        let undo_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "undo_button")?;
+        let redo_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "redo_button")?;
+        undo_button.set_tool_tip(&qtr("undo_mod_list_tooltip"));
+        redo_button.set_tool_tip(&qtr("redo_mod_list_tooltip"));
+        undo_button.set_shortcut(&QKeySequence::from_q_string(&QString::from_std_str("Ctrl+Z")));
+        redo_button.set_shortcut(&QKeySequence::from_q_string(&QString::from_std_str("Ctrl+Shift+Z")));
+        undo_button.set_enabled(false);
+        redo_button.set_enabled(false);
+
+        let snapshots_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "snapshots_button")?;
+        snapshots_button.set_tool_tip(&qtr("launch_snapshots_tooltip"));
+        let snapshots_menu = QMenu::from_q_widget(&snapshots_button);
+        snapshots_button.set_menu(snapshots_menu.into_raw_ptr());
+        snapshots_button.set_popup_mode(ToolButtonPopupMode::InstantPopup);
+        let snapshots_menu = snapshots_button.menu();
 
         // Replace the placeholder widget.
         let main_layout: QPtr<QGridLayout> = main_widget.layout().static_downcast();
@@ -118,6 +154,13 @@ impl PackListUI {
             filter_case_sensitive_button,
             filter_timer,
             automatic_order_button,
+            data_conflicts_button,
+            translation_coverage_button,
+            undo_button,
+            redo_button,
+            snapshots_button,
+            snapshots_menu,
+            file_conflicts: RefCell::new(ConflictReport::default()),
         });
 
         let slots = PackListUISlots::new(&list);
@@ -139,8 +182,21 @@ impl PackListUI {
 
         if !game_path.to_string_lossy().is_empty() {
             if let Ok(game_data_folder) = game_info.data_path(game_path) {
+                let missing_dependencies = load_order.missing_dependencies(game_config, &game_data_folder);
                 let game_data_folder = std::fs::canonicalize(game_data_folder.clone()).unwrap_or_else(|_| game_data_folder.clone());
 
+                // Only regenerate the conflicts if the packs changed, as it requires going through all the files of the vanilla packs.
+                if !self.file_conflicts().borrow().is_up_to_date(game_path, load_order) {
+                    let report = ConflictReport::generate(game_config, game_info, game_path, load_order).unwrap_or_else(|error| {
+                        error!("Error generating the file conflicts report: {}", error);
+                        ConflictReport::default()
+                    });
+
+                    self.file_conflicts().replace(report);
+                }
+
+                let conflicts = self.file_conflicts().borrow();
+
                 // Chain so movie packs are always last.
                 let mods = load_order.mods().iter().chain(load_order.movies().iter());
                 for (index, mod_id) in mods.enumerate() {
@@ -153,7 +209,13 @@ impl PackListUI {
                         //
                         // In practice if a bin pack loads here, there's a bug elsewhere.
                         if pack_name.ends_with(".pack") {
-                            let pack = Pack::read_and_merge(&[modd.paths()[0].to_path_buf()], true, false, false)?;
+                            let dependency_issues = load_order.dependency_issues_for_mod(mod_id)
+                                .iter()
+                                .map(|issue| issue.to_string())
+                                .chain(missing_dependencies.iter()
+                                    .filter(|dependency| dependency.mod_id() == mod_id)
+                                    .map(|dependency| dependency.to_string()))
+                                .collect::<Vec<_>>();
 
                             let item_name = Self::new_item();
                             let item_type = Self::new_item();
@@ -161,11 +223,19 @@ impl PackListUI {
                             let load_order = Self::new_item();
                             let location = Self::new_item();
                             let steam_id = Self::new_item();
+                            let file_conflicts = Self::new_item();
 
                             item_name.set_text(&QString::from_std_str(&pack_name));
                             item_name.set_data_2a(&QVariant::from_q_string(&QString::from_std_str(mod_id)), VALUE_MOD_ID);
-                            item_name.set_data_2a(&QVariant::from_q_string(&QString::from_std_str((pack.pfh_file_type() as u32).to_string() + &pack_name)), 20);
+                            item_name.set_data_2a(&QVariant::from_q_string(&QString::from_std_str((*modd.pack_type() as u32).to_string() + &pack_name)), 20);
                             item_type.set_text(&QString::from_std_str(&modd.pack_type().to_string()));
+
+                            // Mark the packs with missing dependencies, or that the automatic sorting couldn't place correctly.
+                            if !dependency_issues.is_empty() {
+                                item_name.set_icon(&QIcon::from_theme_1a(&QString::from_std_str("dialog-warning")));
+                                item_name.set_tool_tip(&QString::from_std_str(dependency_issues.join("\n")));
+                            }
+
                             item_path.set_text(&QString::from_std_str(&modd.paths()[0].to_string_lossy()));
                             load_order.set_data_2a(&QVariant::from_int(index as i32), 2);
 
@@ -189,12 +259,20 @@ impl PackListUI {
                                 steam_id.set_text(&QString::from_std_str(id));
                             }
 
+                            let conflicts_won = conflicts.conflicts_won_by(&pack_name);
+                            let conflicts_lost = conflicts.conflicts_lost_by(&pack_name);
+                            if !conflicts_won.is_empty() || !conflicts_lost.is_empty() {
+                                file_conflicts.set_text(&QString::from_std_str(tre("file_conflicts_summary", &[&conflicts_won.len().to_string(), &conflicts_lost.len().to_string()])));
+                                file_conflicts.set_tool_tip(&QString::from_std_str(Self::file_conflicts_tooltip(&conflicts_won, &conflicts_lost)));
+                            }
+
                             row.append_q_standard_item(&item_name.into_ptr().as_mut_raw_ptr());
                             row.append_q_standard_item(&item_type.into_ptr().as_mut_raw_ptr());
                             row.append_q_standard_item(&item_path.into_ptr().as_mut_raw_ptr());
                             row.append_q_standard_item(&load_order.into_ptr().as_mut_raw_ptr());
                             row.append_q_standard_item(&location.into_ptr().as_mut_raw_ptr());
                             row.append_q_standard_item(&steam_id.into_ptr().as_mut_raw_ptr());
+                            row.append_q_standard_item(&file_conflicts.into_ptr().as_mut_raw_ptr());
 
                             self.model().append_row_q_list_of_q_standard_item(row.into_ptr().as_ref().unwrap());
                         } else {
@@ -225,6 +303,7 @@ impl PackListUI {
         let load_order = QStandardItem::from_q_string(&qtr("load_order"));
         let location = QStandardItem::from_q_string(&qtr("location"));
         let steam_id = QStandardItem::from_q_string(&qtr("steam_id"));
+        let file_conflicts = QStandardItem::from_q_string(&qtr("file_conflicts"));
 
         self.model.set_horizontal_header_item(0, pack_name.into_ptr());
         self.model.set_horizontal_header_item(1, pack_type.into_ptr());
@@ -232,6 +311,7 @@ impl PackListUI {
         self.model.set_horizontal_header_item(3, load_order.into_ptr());
         self.model.set_horizontal_header_item(4, location.into_ptr());
         self.model.set_horizontal_header_item(5, steam_id.into_ptr());
+        self.model.set_horizontal_header_item(6, file_conflicts.into_ptr());
     }
 
     /// This returns the selection REVERSED, FROM BOTTOM TO TOP.
@@ -268,6 +348,37 @@ impl PackListUI {
         self.filter_timer.start_0a();
     }
 
+    /// This function builds the tooltip listing the files a pack overwrites, and the ones overwritten by other packs.
+    fn file_conflicts_tooltip(conflicts_won: &[&FileConflict], conflicts_lost: &[&FileConflict]) -> String {
+        let mut tooltip = vec![];
+
+        if !conflicts_won.is_empty() {
+            tooltip.push(tr("file_conflicts_won"));
+            tooltip.extend(conflicts_won.iter()
+                .take(MAX_CONFLICTS_IN_TOOLTIP)
+                .map(|file| format!("- {} ({})", file.path(), file.losers().join(", ")))
+            );
+
+            if conflicts_won.len() > MAX_CONFLICTS_IN_TOOLTIP {
+                tooltip.push(tre("file_conflicts_more", &[&(conflicts_won.len() - MAX_CONFLICTS_IN_TOOLTIP).to_string()]));
+            }
+        }
+
+        if !conflicts_lost.is_empty() {
+            tooltip.push(tr("file_conflicts_lost"));
+            tooltip.extend(conflicts_lost.iter()
+                .take(MAX_CONFLICTS_IN_TOOLTIP)
+                .map(|file| format!("- {} ({})", file.path(), file.winner().unwrap_or_default()))
+            );
+
+            if conflicts_lost.len() > MAX_CONFLICTS_IN_TOOLTIP {
+                tooltip.push(tre("file_conflicts_more", &[&(conflicts_lost.len() - MAX_CONFLICTS_IN_TOOLTIP).to_string()]));
+            }
+        }
+
+        tooltip.join("\n")
+    }
+
     unsafe fn new_item() -> CppBox<QStandardItem> {
         let item = QStandardItem::new();
         item.set_editable(false);
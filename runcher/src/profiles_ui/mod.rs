@@ -43,10 +43,11 @@ use std::path::{PathBuf, Path};
 use std::rc::Rc;
 
 use rpfm_ui_common::clone;
-use rpfm_ui_common::locale::qtr;
+use rpfm_ui_common::locale::{qtr, tre};
 use rpfm_ui_common::utils::*;
 
 use crate::AppUI;
+use crate::mod_manager::integrations::publish_collection;
 use crate::profiles_ui::slots::ProfilesUISlots;
 
 const VIEW_DEBUG: &str = "ui_templates/profile_manager_dialog.ui";
@@ -74,7 +75,7 @@ pub struct ProfilesUI {
     rename_profile_button: QPtr<QToolButton>,
     delete_profile_button: QPtr<QToolButton>,
     shortcut_button: QPtr<QToolButton>,
-
+    publish_collection_button: QPtr<QToolButton>,
 }
 
 //---------------------------------------------------------------------------//
@@ -95,6 +96,7 @@ impl ProfilesUI {
         let rename_profile_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "rename_button")?;
         let delete_profile_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "delete_button")?;
         let shortcut_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "shortcut_button")?;
+        let publish_collection_button: QPtr<QToolButton> = find_widget(&main_widget.static_upcast(), "publish_collection_button")?;
         let profiles_list_view: QPtr<QListView> = find_widget(&main_widget.static_upcast(), "profiles_list_view")?;
         let profiles_list_model = QStandardItemModel::new_1a(&profiles_list_view);
         profiles_list_view.set_model(&profiles_list_model);
@@ -104,11 +106,13 @@ impl ProfilesUI {
         rename_profile_button.set_tool_tip(&qtr("profile_rename"));
         delete_profile_button.set_tool_tip(&qtr("profile_delete"));
         shortcut_button.set_tool_tip(&qtr("profile_shortcut_new"));
+        publish_collection_button.set_tool_tip(&qtr("profile_publish_collection"));
 
         // Disable the buttons.
         delete_profile_button.set_enabled(false);
         rename_profile_button.set_enabled(false);
         shortcut_button.set_enabled(false);
+        publish_collection_button.set_enabled(false);
 
         let ui = Rc::new(Self {
             main_widget,
@@ -118,6 +122,7 @@ impl ProfilesUI {
             rename_profile_button,
             delete_profile_button,
             shortcut_button,
+            publish_collection_button,
         });
 
         let slots = ProfilesUISlots::new(&ui, app_ui);
@@ -137,6 +142,7 @@ impl ProfilesUI {
         self.rename_profile_button().released().connect(slots.profile_rename());
         self.delete_profile_button().released().connect(slots.profile_delete());
         self.shortcut_button().released().connect(slots.profile_shorcut());
+        self.publish_collection_button().released().connect(slots.profile_publish_collection());
     }
 
     pub unsafe fn load_data(&self, app_ui: &Rc<AppUI>) {
@@ -164,6 +170,21 @@ impl ProfilesUI {
             details.push_str(&format!("<li>Profile ID/Name: {}</li>", profile.id()));
             details.push_str(&format!("<li>Game: {}</li>", profile.game()));
 
+            let launch_options = profile.launch_options();
+            details.push_str("<li>Launch options:</li><ul>");
+            details.push_str(&format!("<li>Script logging: {}</li>", launch_options.enable_logging()));
+            details.push_str(&format!("<li>Skip intros: {}</li>", launch_options.enable_skip_intros()));
+            details.push_str(&format!("<li>Remove trait limit: {}</li>", launch_options.remove_trait_limit()));
+            details.push_str(&format!("<li>Translation: {}</li>", launch_options.translations_language().as_deref().unwrap_or("--")));
+            details.push_str(&format!("<li>Unit multiplier: {:.2}</li>", launch_options.unit_multiplier()));
+            details.push_str(&format!("<li>Universal rebalancer: {}</li>", launch_options.universal_rebalancer_base_mod().as_deref().unwrap_or("--")));
+            details.push_str("</ul>");
+            details.push_str(&format!("<li>Save: {}</li>", profile.save().as_deref().unwrap_or("--")));
+
+            if let Some(collection_id) = profile.workshop_collection() {
+                details.push_str(&format!("<li>Workshop collection: <a href=\"https://steamcommunity.com/sharedfiles/filedetails/?id={}\">{}</a></li>", collection_id, collection_id));
+            }
+
             if profile.load_order().mods().is_empty() {
                 details.push_str("<li>Profile contains an empty load order.</li>");
             } else if let Some(ref game_config) = *app_ui.game_config().read().unwrap() {
@@ -319,6 +340,64 @@ impl ProfilesUI {
         Ok(())
     }
 
+    /// This function publishes the mods of the selected profile as a Workshop collection, or updates the collection if it was already published.
+    pub unsafe fn publish_collection(&self, app_ui: &Rc<AppUI>) -> Result<()> {
+        let selection = self.list_selection();
+        let index = &selection[0];
+        let name = index.data_1a(2).to_string().to_std_string();
+
+        let mut profile = app_ui.game_profiles().read().unwrap().get(&name).cloned().ok_or_else(|| anyhow!("Profile {} not found.", name))?;
+        let (steam_ids, local_only) = match *app_ui.game_config().read().unwrap() {
+            Some(ref game_config) => profile.workshop_items(game_config),
+            None => return Err(anyhow!("The mod list of the game selected is not loaded.")),
+        };
+
+        if steam_ids.is_empty() {
+            return Err(anyhow!("This profile has no mods from the Workshop, so there's nothing to publish."));
+        }
+
+        let game = app_ui.game_selected().read().unwrap().clone();
+        app_ui.toggle_main_window(false);
+        let result = publish_collection(&game, profile.workshop_collection(), &steam_ids, &name, "");
+        app_ui.toggle_main_window(true);
+        let collection = result?;
+
+        // Remember the collection, so publishing the profile again updates it instead of creating a new one.
+        let collection_id = collection.published_file_id.to_string();
+        profile.set_workshop_collection(Some(collection_id.to_owned()));
+        profile.save(&game, &name)?;
+        app_ui.game_profiles().write().unwrap().insert(name.to_owned(), profile);
+
+        let mut message = tre("profile_publish_collection_success", &[&collection_id, &name]);
+
+        if !local_only.is_empty() {
+            message.push_str(&format!("<p>The following mods are not in the Workshop, so they have not been included:<p> <ul>{}</ul>",
+                local_only.iter().map(|mod_id| format!("<li>{}</li>", mod_id)).collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        if !collection.failed_items.is_empty() {
+            message.push_str(&format!("<p>The following mods could not be added to the collection. They may have been removed or hidden from the Workshop:<p> <ul>{}</ul>",
+                collection.failed_items.iter().map(|steam_id| format!("<li><a href=\"https://steamcommunity.com/sharedfiles/filedetails/?id={}\">{}</a></li>", steam_id, steam_id)).collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        if !collection.failed_removals.is_empty() {
+            message.push_str(&format!("<p>The following mods could not be removed from the collection, so it still contains them:<p> <ul>{}</ul>",
+                collection.failed_removals.iter().map(|steam_id| format!("<li><a href=\"https://steamcommunity.com/sharedfiles/filedetails/?id={}\">{}</a></li>", steam_id, steam_id)).collect::<Vec<_>>().join("\n")
+            ));
+        }
+
+        show_dialog(self.main_widget(), message, local_only.is_empty() && collection.failed_items.is_empty() && collection.failed_removals.is_empty());
+
+        // Reload the detailed view to show the collection.
+        let selection = self.profiles_list_view().selection_model().selection();
+        self.profiles_list_view().selection_model().select_q_item_selection_q_flags_selection_flag(&selection, SelectionFlag::Toggle.into());
+        self.profiles_list_view().selection_model().select_q_item_selection_q_flags_selection_flag(&selection, SelectionFlag::Toggle.into());
+
+        Ok(())
+    }
+
     pub unsafe fn create_shortcut(&self, app_ui: &Rc<AppUI>) -> Result<()> {
         let selection = self.list_selection();
         let index = &selection[0];
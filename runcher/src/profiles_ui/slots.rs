@@ -35,6 +35,7 @@ pub struct ProfilesUISlots {
     profile_rename: QBox<SlotNoArgs>,
     profile_delete: QBox<SlotNoArgs>,
     profile_shorcut: QBox<SlotNoArgs>,
+    profile_publish_collection: QBox<SlotNoArgs>,
 }
 
 //-------------------------------------------------------------------------------//
@@ -65,6 +66,7 @@ impl ProfilesUISlots {
                     ui.delete_profile_button().set_enabled(true);
                     ui.rename_profile_button().set_enabled(true);
                     ui.shortcut_button().set_enabled(true);
+                    ui.publish_collection_button().set_enabled(true);
                 }
 
                 // If nothing is loaded, means we're selecting multiple things, or none.
@@ -76,6 +78,7 @@ impl ProfilesUISlots {
                     ui.delete_profile_button().set_enabled(false);
                     ui.rename_profile_button().set_enabled(false);
                     ui.shortcut_button().set_enabled(false);
+                    ui.publish_collection_button().set_enabled(false);
                 }
             }
         ));
@@ -107,12 +110,22 @@ impl ProfilesUISlots {
             }
         ));
 
+        let profile_publish_collection = SlotNoArgs::new(ui.main_widget(), clone!(
+            app_ui,
+            ui => move || {
+                if let Err(error) = ui.publish_collection(&app_ui) {
+                    show_dialog(ui.main_widget(), error, false);
+                }
+            }
+        ));
+
         Self {
             update_details,
 
             profile_rename,
             profile_delete,
             profile_shorcut,
+            profile_publish_collection,
         }
     }
 }
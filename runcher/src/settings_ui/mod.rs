@@ -73,6 +73,9 @@ pub const SLASH_DMY_DATE_FORMAT_STR: &str = "[day]/[month]/[year]";
 pub const SLASH_MDY_DATE_FORMAT_STR: &str = "[month]/[day]/[year]";
 pub const SLASH_YMD_DATE_FORMAT_STR: &str = "[year]/[month]/[day]";
 
+/// Default command used to launch games on Linux. `{app_id}` is replaced with the game's Steam AppId.
+pub const DEFAULT_LINUX_LAUNCH_COMMAND: &str = "steam -applaunch {app_id}";
+
 const TRANSLATIONS_LOCAL_FOLDER: &str = "translations_local";
 const TRANSLATIONS_REMOTE_FOLDER: &str = "translations_remote";
 
@@ -111,6 +114,7 @@ pub struct SettingsUI {
     dark_mode_checkbox: QPtr<QCheckBox>,
     open_workshop_link_in_steam_checkbox: QPtr<QCheckBox>,
     check_logs_checkbox: QPtr<QCheckBox>,
+    linux_launch_command_line_edit: QPtr<QLineEdit>,
 
     font_button: QBox<QPushButton>,
     restore_default_button: QPtr<QPushButton>,
@@ -172,6 +176,7 @@ impl SettingsUI {
         let dark_mode_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "dark_mode_label")?;
         let open_workshop_link_in_steam_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "open_workshop_link_in_steam_label")?;
         let check_logs_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "check_logs_label")?;
+        let linux_launch_command_label: QPtr<QLabel> = find_widget(&main_widget.static_upcast(), "linux_launch_command_label")?;
         let language_combobox: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "language_combobox")?;
         let default_game_combobox: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "default_game_combobox")?;
         let update_chanel_combobox: QPtr<QComboBox> = find_widget(&main_widget.static_upcast(), "update_chanel_combobox")?;
@@ -182,6 +187,7 @@ impl SettingsUI {
         let dark_mode_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "dark_mode_checkbox")?;
         let open_workshop_link_in_steam_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "open_workshop_link_in_steam_checkbox")?;
         let check_logs_checkbox: QPtr<QCheckBox> = find_widget(&main_widget.static_upcast(), "check_logs_checkbox")?;
+        let linux_launch_command_line_edit: QPtr<QLineEdit> = find_widget(&main_widget.static_upcast(), "linux_launch_command_line_edit")?;
         let paths_layout: QPtr<QGridLayout> = paths_groupbox.layout().static_downcast();
         update_chanel_combobox.add_item_q_string(&QString::from_std_str(STABLE));
         update_chanel_combobox.add_item_q_string(&QString::from_std_str(BETA));
@@ -200,6 +206,15 @@ impl SettingsUI {
         dark_mode_label.set_text(&qtr("dark_mode"));
         open_workshop_link_in_steam_label.set_text(&qtr("open_workshop_link_in_steam"));
         check_logs_label.set_text(&qtr("check_logs"));
+        linux_launch_command_label.set_text(&qtr("linux_launch_command"));
+        linux_launch_command_line_edit.set_placeholder_text(&qtr("linux_launch_command_ph"));
+        linux_launch_command_line_edit.set_tool_tip(&qtr("linux_launch_command_tt"));
+
+        // The launcher command is only used on Linux, so don't show it on other systems.
+        if !cfg!(target_os = "linux") {
+            linux_launch_command_label.set_visible(false);
+            linux_launch_command_line_edit.set_visible(false);
+        }
 
         // Add one path at the beginning for the secondary mods folder.
         let secondary_mods_folder_label = QLabel::from_q_string_q_widget(&qtr("settings_secondary_mods_folder"), &paths_groupbox);
@@ -289,6 +304,7 @@ impl SettingsUI {
             dark_mode_checkbox,
             open_workshop_link_in_steam_checkbox,
             check_logs_checkbox,
+            linux_launch_command_line_edit,
 
             font_button,
             restore_default_button,
@@ -403,6 +419,7 @@ impl SettingsUI {
         self.check_updates_on_start_checkbox().set_checked(setting_bool_from_q_setting(&q_settings, "check_updates_on_start"));
         self.check_schema_updates_on_start_checkbox().set_checked(setting_bool_from_q_setting(&q_settings, "check_schema_updates_on_start"));
         self.check_logs_checkbox().set_checked(setting_bool_from_q_setting(&q_settings, "check_logs"));
+        self.linux_launch_command_line_edit().set_text(&QString::from_std_str(setting_string_from_q_setting(&q_settings, "linux_launch_command")));
 
         Ok(())
     }
@@ -459,6 +476,7 @@ impl SettingsUI {
         set_setting_bool_to_q_setting(&q_settings, "check_updates_on_start", self.check_updates_on_start_checkbox().is_checked());
         set_setting_bool_to_q_setting(&q_settings, "check_schema_updates_on_start", self.check_schema_updates_on_start_checkbox().is_checked());
         set_setting_bool_to_q_setting(&q_settings, "check_logs", self.check_logs_checkbox().is_checked());
+        set_setting_string_to_q_setting(&q_settings, "linux_launch_command", &self.linux_launch_command_line_edit().text().to_std_string());
 
         // Save the settings.
         q_settings.sync();
@@ -610,6 +628,7 @@ pub unsafe fn init_settings(main_window: &QPtr<QMainWindow>) {
     set_setting_if_new_bool(&q_settings, "check_schema_updates_on_start", true);
     set_setting_if_new_bool(&q_settings, "dark_mode", false);
     set_setting_if_new_bool(&q_settings, "check_logs", true);
+    set_setting_if_new_string(&q_settings, "linux_launch_command", DEFAULT_LINUX_LAUNCH_COMMAND);
 
     for game in &SUPPORTED_GAMES.games_sorted() {
         if game.key() != KEY_ARENA {
@@ -681,6 +700,10 @@ pub fn profiles_path() -> Result<PathBuf> {
     Ok(config_path()?.join("profiles"))
 }
 
+pub fn upload_history_path() -> Result<PathBuf> {
+    Ok(config_path()?.join("upload_history"))
+}
+
 pub fn rpfm_config_path() -> Result<PathBuf> {
     if cfg!(debug_assertions) { std::env::current_dir().map_err(From::from) } else {
         unsafe {
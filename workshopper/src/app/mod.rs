@@ -22,6 +22,10 @@ use std::path::PathBuf;
 #[command(author, version, about, long_about = None)]
 pub(crate) struct Cli {
 
+    /// Name of the IPC channel progress and results will be sent through.
+    #[arg(short, long, global = true, required = false, value_name = "IPC_CHANNEL")]
+    pub ipc_channel: Option<String>,
+
     #[clap(subcommand)]
     pub command: Commands,
 }
@@ -38,6 +42,43 @@ pub enum Commands {
         /// List of published file ids, separated by comma. If empty, all subscribed items are downloaded.
         #[arg(short, long, required = false, value_name = "PUBLISHED_FILE_IDS")]
         published_file_ids: Option<String>,
+
+        /// Subscribe to the provided items the user is not subscribed to, so Steam keeps them updated. Used when importing collections.
+        #[arg(long, required = false)]
+        subscribe: bool,
+    },
+
+    Subscribe {
+
+        /// SteamId/AppId of the game.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
+
+        /// List of published file ids, separated by comma.
+        #[arg(short, long, required = true, value_name = "PUBLISHED_FILE_IDS")]
+        published_file_ids: String,
+
+        /// Download the items after subscribing to them.
+        #[arg(short, long, required = false)]
+        download: bool,
+    },
+
+    Unsubscribe {
+
+        /// SteamId/AppId of the game.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
+
+        /// List of published file ids, separated by comma.
+        #[arg(short, long, required = true, value_name = "PUBLISHED_FILE_IDS")]
+        published_file_ids: String,
+    },
+
+    GetSubscribedItems {
+
+        /// SteamId/AppId of the game.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
     },
 
     GetPublishedFileDetails {
@@ -49,10 +90,48 @@ pub enum Commands {
         /// List of published file ids, separated by comma.
         #[arg(short, long, required = true, value_name = "PUBLISHED_FILE_IDS")]
         published_file_ids: String,
+    },
+
+    GetCollectionDetails {
+
+        /// SteamId/AppId of the game the collection is for.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
+
+        /// Published file id of the collection.
+        #[arg(short, long, required = true, value_name = "PUBLISHED_FILE_ID")]
+        published_file_id: u64,
+    },
+
+    PublishCollection {
+
+        /// If we're going to pass title and description as base64 strings. Use this when any of those includes special characters.
+        #[arg(short, long, required = false)]
+        base64: bool,
+
+        /// SteamId/AppId of the game the collection is for.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
+
+        /// PublishedFileId of the collection to update. If not provided, a new collection is created.
+        #[arg(long, required = false, value_name = "PUBLISHED_FILE_ID")]
+        published_file_id: Option<u64>,
+
+        /// List of published file ids the collection will contain, in order, separated by comma.
+        #[arg(short, long, required = true, value_name = "PUBLISHED_FILE_IDS")]
+        published_file_ids: String,
 
-        /// Name of the IPC channel the response will be sent through.
-        #[arg(short, long, value_name = "IPC_CHANNEL")]
-        ipc_channel: String,
+        /// Title of the collection. Only used when creating it.
+        #[arg(short, long, required = true, value_name = "TITLE")]
+        title: String,
+
+        /// Description of the collection. Only used when creating it.
+        #[arg(short, long, required = false, value_name = "DESCRIPTION")]
+        description: Option<String>,
+
+        /// Visibility of the collection. Only used when creating it.
+        #[arg(short, long, required = false, value_name = "VISIBILITY")]
+        visibility: Option<u32>,
     },
 
     Launch {
@@ -93,9 +172,13 @@ pub enum Commands {
         description: Option<String>,
 
         /// Tags the mod will receive.
-        #[arg(long, required = true, value_name = "COMMA-SEPARATED TAGS")]
+        #[arg(long, required = true, value_name = "COMMA-SEPARATED TAGS", value_delimiter = ',')]
         tags: Vec<String>,
 
+        /// Path of the preview image. If not provided, the png next to the file with its same name is used.
+        #[arg(long, required = false, value_name = "PATH")]
+        preview_path: Option<PathBuf>,
+
         /// Changelog for the initial release.
         #[arg(short, long, required = false, value_name = "CHANGELOG")]
         changelog: Option<String>,
@@ -132,9 +215,13 @@ pub enum Commands {
         description: Option<String>,
 
         /// Tags the mod will receive.
-        #[arg(long, required = true, value_name = "COMMA-SEPARATED TAGS")]
+        #[arg(long, required = true, value_name = "COMMA-SEPARATED TAGS", value_delimiter = ',')]
         tags: Vec<String>,
 
+        /// Path of the preview image. If not provided, the png next to the file with its same name is used.
+        #[arg(long, required = false, value_name = "PATH")]
+        preview_path: Option<PathBuf>,
+
         /// Changelog for this specific release.
         #[arg(short, long, required = false, value_name = "CHANGELOG")]
         changelog: Option<String>,
@@ -144,14 +231,30 @@ pub enum Commands {
         visibility: Option<u32>,
     },
 
+    /// Upload or update all the items of a manifest in one go.
+    Batch {
+
+        /// SteamId/AppId of the game we're going to upload the mods for.
+        #[arg(short, long, value_name = "STEAM_ID")]
+        steam_id: u32,
+
+        /// Path of the manifest with the items to upload. Files with the toml extension are read as TOML. The rest, as JSON.
+        #[arg(short, long, required = true, value_name = "PATH")]
+        manifest_path: PathBuf,
+
+        /// Path of the JSON file to write the result of each item to. If not provided, it's written next to the manifest.
+        #[arg(short, long, required = false, value_name = "PATH")]
+        result_path: Option<PathBuf>,
+
+        /// Keep uploading the rest of the items when one fails. By default, the rest are skipped.
+        #[arg(short, long, required = false)]
+        continue_on_error: bool,
+    },
+
     UserId {
 
         /// SteamId/AppId of a game to initialize the api with.
         #[arg(short, long, value_name = "STEAM_ID")]
         steam_id: u32,
-
-        /// Name of the IPC channel the response will be sent through.
-        #[arg(short, long, value_name = "IPC_CHANNEL")]
-        ipc_channel: String,
     },
 }
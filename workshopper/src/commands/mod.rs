@@ -11,15 +11,15 @@
 use anyhow::Result;
 use base64::{Engine, prelude::BASE64_STANDARD};
 use execute_command::ExecuteCommand;
-use interprocess::local_socket::{GenericNamespaced, prelude::*};
 use steamworks::Client;
 
-use std::io::Write;
 #[cfg(target_os = "windows")] use std::os::windows::process::CommandExt;
 use std::process::Command;
 
 use rpfm_lib::integrations::log::info;
 
+use crate::ipc::{IpcChannel, Stage};
+
 pub mod ugc;
 
 #[cfg(target_os = "windows")] const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -31,7 +31,9 @@ pub mod ugc;
 /// This function is used to launch games with the Steam API enabled.
 ///
 /// command is expected to be the full command to launch the game as a Rust std::process::Command.
-pub fn launch_game(base64: bool, steam_id: u32, command: &str) -> Result<()> {
+///
+/// Once the game is running we report it as started. The command finishes when the game is closed.
+pub fn launch_game(ipc: &mut IpcChannel, base64: bool, steam_id: u32, command: &str) -> Result<()> {
 
     // If we're in base64 mode, decode the args.
     let command = if base64 {
@@ -51,20 +53,20 @@ pub fn launch_game(base64: bool, steam_id: u32, command: &str) -> Result<()> {
     // This disables the terminal when executing the command.
     #[cfg(target_os = "windows")]game_command.creation_flags(CREATE_NO_WINDOW);
     let mut handle = game_command.spawn()?;
+    ipc.progress(Stage::Started, 0, 0);
+
     let _ = handle.wait()?;
 
     Ok(())
 }
 
-pub fn user_id(steam_id: u32, ipc_channel: &str) -> Result<()> {
+pub fn user_id(ipc: &mut IpcChannel, steam_id: u32) -> Result<()> {
     let (client, _) = Client::init_app(steam_id)?;
     let steam_user_id = client.user().steam_id();
 
     info!("User Steam ID: {}", steam_user_id.raw());
 
-    if let Ok(mut stream) = LocalSocketStream::connect(ipc_channel.to_ns_name::<GenericNamespaced>()?) {
-        let _ = stream.write(&steam_user_id.raw().to_le_bytes());
-    }
+    ipc.respond(&steam_user_id.raw());
 
     Ok(())
 }
@@ -12,11 +12,11 @@ use anyhow::{anyhow, Result};
 use base64::prelude::*;
 use crossbeam::channel::{Sender, Receiver, TryRecvError, unbounded};
 use indicatif::{ProgressBar, ProgressState, ProgressStyle};
-use interprocess::local_socket::{GenericNamespaced, prelude::*};
 use serde::{Deserialize, Serialize};
 use serde_json::to_string_pretty;
 use steamworks::{AppId, Client, ClientManager, DownloadItemResult, FileType, PublishedFileId, PublishedFileVisibility, QueryResult, SingleClient, SteamId, UpdateStatus, UpdateWatchHandle, UGC};
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::Write as FmtWrite;
 use std::fs::{DirBuilder, File};
 use std::io::{BufWriter, Write};
@@ -26,6 +26,10 @@ use std::thread::JoinHandle;
 use rpfm_lib::{games::GameInfo, integrations::log::{error, info, warn}};
 use rpfm_lib::utils::path_to_absolute_path;
 
+use crate::ipc::{IpcChannel, Stage};
+
+#[cfg(test)] mod test;
+
 const TOTAL_WAR_BASE_TAG: &str = "mod";
 
 //-------------------------------------------------------------------------------//
@@ -86,15 +90,96 @@ pub enum FileTypeDerive {
     GameManagedItem,
 }
 
+/// Result of publishing a collection, sent back to the caller.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PublishedCollection {
+    pub published_file_id: u64,
+
+    /// Items that could not be added to the collection, like deleted or hidden items.
+    pub failed_items: Vec<u64>,
+
+    /// Items that could not be removed from the collection, so they're still in it.
+    pub failed_removals: Vec<u64>,
+}
+
 #[derive(Debug)]
 pub enum SteamWorksThreadMessage {
     QueryResults(Vec<QueryResult>),
     PublishedFileId(PublishedFileId),
+    Collections(HashMap<PublishedFileId, Vec<PublishedFileId>>),
     Ok,
     Error(anyhow::Error),
     Exit
 }
 
+/// Access to the collections of the Workshop. This allows resolving collections without Steam running, using a local stand-in.
+pub trait CollectionSource {
+
+    /// This function returns the children of the provided items that are collections. Items that are not collections are not returned.
+    fn collections(&self, published_file_ids: &[PublishedFileId]) -> Result<HashMap<PublishedFileId, Vec<PublishedFileId>>>;
+}
+
+/// Collection source backed by the Steam API.
+pub struct SteamCollectionSource<'a> {
+    ugc: &'a UGC<ClientManager>,
+}
+
+/// Collection source backed by a list of collections in memory, for when we cannot use Steam, like in tests.
+#[cfg(test)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LocalCollectionSource {
+    pub collections: HashMap<u64, Vec<u64>>,
+}
+
+/// List of items to upload or update in one go. It can be written in TOML or JSON.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BatchManifest {
+    pub items: Vec<BatchItem>,
+}
+
+/// Item of a batch. Relative paths are relative to the folder of the manifest.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct BatchItem {
+    pub pack_path: PathBuf,
+
+    /// Id of the item in the Workshop. If not provided, a new item is created.
+    pub published_file_id: Option<u64>,
+    pub title: String,
+
+    /// Path of a text file with the description of the item. If not provided, the description is not changed.
+    pub description_file: Option<PathBuf>,
+
+    /// Path of the preview image. If not provided, the png next to the pack with its same name is used.
+    pub preview_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub visibility: Option<u32>,
+    pub changelog: Option<String>,
+}
+
+/// Result of an item of a batch, written to the result file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchItemResult {
+    pub pack_path: PathBuf,
+
+    /// Id of the item in the Workshop. For new items, this is the id they got, even if the upload failed afterwards.
+    pub published_file_id: Option<u64>,
+    pub status: BatchItemStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BatchItemStatus {
+
+    /// The item is being uploaded. If the batch finished with items in this state, it was interrupted.
+    Pending,
+    Uploaded,
+    Failed(String),
+
+    /// The item was not processed because a previous one failed.
+    Skipped,
+}
+
 //---------------------------------------------------------------------------//
 //                           From Implementations
 //---------------------------------------------------------------------------//
@@ -162,11 +247,196 @@ impl From<FileType> for FileTypeDerive {
     }
 }
 
+//---------------------------------------------------------------------------//
+//                          BatchManifest Implementations
+//---------------------------------------------------------------------------//
+
+impl BatchManifest {
+
+    /// This function loads a manifest from disk. Files with the toml extension are read as TOML. The rest, as JSON.
+    pub fn load(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)?;
+        let is_toml = path.extension().map(|extension| extension.to_string_lossy().to_lowercase() == "toml").unwrap_or(false);
+        let base_path = path_to_absolute_path(path, true);
+        let base_path = base_path.parent().ok_or_else(|| anyhow!("Invalid manifest path: {}.", path.to_string_lossy()))?;
+
+        Self::parse(&data, is_toml, base_path)
+    }
+
+    /// This function parses a manifest, making its relative paths relative to the provided folder.
+    pub fn parse(data: &str, is_toml: bool, base_path: &Path) -> Result<Self> {
+        let mut manifest: Self = if is_toml {
+            toml::from_str(data)?
+        } else {
+            serde_json::from_str(data)?
+        };
+
+        for item in &mut manifest.items {
+            item.pack_path = base_path.join(&item.pack_path);
+
+            if let Some(ref mut description_file) = item.description_file {
+                *description_file = base_path.join(description_file.as_path());
+            }
+
+            if let Some(ref mut preview_path) = item.preview_path {
+                *preview_path = base_path.join(preview_path.as_path());
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+//---------------------------------------------------------------------------//
+//                        CollectionSource Implementations
+//---------------------------------------------------------------------------//
+
+impl CollectionSource for SteamCollectionSource<'_> {
+    fn collections(&self, published_file_ids: &[PublishedFileId]) -> Result<HashMap<PublishedFileId, Vec<PublishedFileId>>> {
+        let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+        get_collections(self.ugc, tx_query, published_file_ids.to_vec());
+
+        let response = rx_query.recv()?;
+        match response {
+            SteamWorksThreadMessage::Collections(collections) => Ok(collections),
+            SteamWorksThreadMessage::Error(error) => Err(error),
+            _ => panic!("{response:?}")
+        }
+    }
+}
+
+#[cfg(test)]
+impl CollectionSource for LocalCollectionSource {
+    fn collections(&self, published_file_ids: &[PublishedFileId]) -> Result<HashMap<PublishedFileId, Vec<PublishedFileId>>> {
+        Ok(published_file_ids.iter()
+            .filter_map(|id| self.collections.get(&id.0).map(|children| (*id, children.iter().map(|child| PublishedFileId(*child)).collect())))
+            .collect())
+    }
+}
+
 //---------------------------------------------------------------------------//
 //                      UGC (Workshop) public functions
 //---------------------------------------------------------------------------//
 
-pub fn published_file_details(steam_id: u32, published_file_ids: &str, ipc_channel: &str) -> Result<()> {
+/// This function sends through the provided IPC channel the details of the items of a collection, in the order of the collection.
+pub fn collection_details(ipc: &mut IpcChannel, steam_id: u32, published_file_id: u64) -> Result<()> {
+
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let source = SteamCollectionSource { ugc: &ugc };
+    let published_file_ids = match resolve_collection(&source, PublishedFileId(published_file_id)) {
+        Ok(ids) => ids,
+        Err(error) => {
+            finish(tx, callback_thread)?;
+            return Err(error)
+        }
+    };
+
+    info!("Collection with ID {} contains {} items.", published_file_id, published_file_ids.len());
+
+    // Empty collections are valid, but the query fails without items.
+    if published_file_ids.is_empty() {
+        ipc.respond(&Vec::<QueryResultDerive>::new());
+        return finish(tx, callback_thread)
+    }
+
+    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+    get_published_file_details(&ugc, tx_query, published_file_ids.to_vec());
+
+    let response = rx_query.recv()?;
+    match response {
+        SteamWorksThreadMessage::QueryResults(results) => {
+            let mut results = results.iter().map(QueryResultDerive::from).collect::<Vec<_>>();
+            results.sort_by_key(|result| published_file_ids.iter().position(|id| *id == result.published_file_id));
+
+            ipc.respond(&results);
+            finish(tx, callback_thread)
+        },
+        SteamWorksThreadMessage::Error(error) => {
+            finish(tx, callback_thread)?;
+            Err(error)
+        },
+        _ => panic!("{response:?}")
+    }
+}
+
+/// This function returns the items of a collection in order, expanding any collection inside it.
+///
+/// Items found more than once are only returned the first time.
+pub fn resolve_collection(source: &impl CollectionSource, published_file_id: PublishedFileId) -> Result<Vec<PublishedFileId>> {
+    let mut collections = source.collections(&[published_file_id])?;
+    let children = collections.remove(&published_file_id).ok_or_else(|| anyhow!("Workshop item with ID {} is not a collection.", published_file_id.0))?;
+
+    let mut items = vec![];
+    let mut visited = HashSet::from([published_file_id]);
+    resolve_collection_children(source, &children, &mut items, &mut visited)?;
+
+    Ok(items)
+}
+
+/// This function creates a collection with the provided items or, if a collection is provided, makes it contain exactly the provided items, in order.
+///
+/// The id of the collection and the items that could not be added to it are sent through the provided IPC channel.
+pub fn publish_collection(
+    ipc: &mut IpcChannel,
+    base64: bool,
+    steam_id: u32,
+    published_file_id: Option<u64>,
+    published_file_ids: &str,
+    title: &str,
+    description: &Option<String>,
+    visibility: &Option<u32>,
+) -> Result<()> {
+    let mut items = vec![];
+    for id in published_file_ids.split(",").filter(|id| !id.is_empty()) {
+        match id.parse::<u64>() {
+            Ok(id) => if !items.contains(&PublishedFileId(id)) {
+                items.push(PublishedFileId(id));
+            },
+            Err(error) => warn!("Invalid Steam ID received: {}. Ignoring with error: {}.", id, error),
+        }
+    }
+
+    // If we're in base64 mode, decode the problematic fields.
+    let title = if base64 {
+        String::from_utf8(BASE64_STANDARD.decode(title)?)?
+    } else {
+        title.to_owned()
+    };
+
+    let mut description = description.clone();
+    if base64 {
+        if let Some(ref mut description) = description {
+            *description = String::from_utf8(BASE64_STANDARD.decode(description.clone())?)?;
+        }
+    }
+
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let result = sync_collection(&ugc, steam_id, published_file_id, &items, &title, &description, visibility);
+    finish(tx, callback_thread)?;
+
+    ipc.respond(&result?);
+    Ok(())
+}
+
+/// This function returns the items to remove from a collection, and the ones to add after that, so it contains the desired items in order.
+///
+/// Items are always added at the end, so we keep the items already in order, and remove and re-add everything after the first difference.
+pub fn collection_changes(current: &[PublishedFileId], desired: &[PublishedFileId]) -> (Vec<PublishedFileId>, Vec<PublishedFileId>) {
+    let common = current.iter()
+        .zip(desired.iter())
+        .take_while(|(current, desired)| current == desired)
+        .count();
+
+    (current[common..].to_vec(), desired[common..].to_vec())
+}
+
+pub fn published_file_details(ipc: &mut IpcChannel, steam_id: u32, published_file_ids: &str) -> Result<()> {
     let mut published_file_ids_enums = vec![];
     let published_file_ids_split = published_file_ids.split(",").collect::<Vec<_>>();
     for id in &published_file_ids_split {
@@ -179,7 +449,7 @@ pub fn published_file_details(steam_id: u32, published_file_ids: &str, ipc_chann
     }
 
     // Initialize the API.
-    let (client, tx, callback_thread) = init(steam_id, Some(ipc_channel))?;
+    let (client, tx, callback_thread) = init(steam_id)?;
     let ugc = client.ugc();
 
     // Create the query and request the results.
@@ -190,33 +460,19 @@ pub fn published_file_details(steam_id: u32, published_file_ids: &str, ipc_chann
     match response {
         SteamWorksThreadMessage::QueryResults(results) => {
             let results = results.iter().map(|result| QueryResultDerive::from(result)).collect::<Vec<_>>();
-            if let Ok(message) = to_string_pretty(&results) {
-
-                if let Ok(mut stream) = LocalSocketStream::connect(ipc_channel.to_ns_name::<GenericNamespaced>()?) {
-                    let _ = stream.write(message.as_bytes());
-                }
-
-                // In debug mode, dump the response to a file so we can see errors on it.
-                if cfg!(debug_assertions) {
-                    let path = PathBuf::from("get_published_file_details.json");
-                    let mut file = BufWriter::new(File::create(path)?);
-                    file.write_all(to_string_pretty(&results)?.as_bytes())?;
-                    file.flush()?;
-                }
-            } else {
-                if let Ok(mut stream) = LocalSocketStream::connect(ipc_channel.to_ns_name::<GenericNamespaced>()?) {
-                    let _ = stream.write(b"{}");
-                }
+            ipc.respond(&results);
+
+            // In debug mode, dump the response to a file so we can see errors on it.
+            if cfg!(debug_assertions) {
+                let path = PathBuf::from("get_published_file_details.json");
+                let mut file = BufWriter::new(File::create(path)?);
+                file.write_all(to_string_pretty(&results)?.as_bytes())?;
+                file.flush()?;
             }
 
             return finish(tx, callback_thread)
         },
         SteamWorksThreadMessage::Error(error) => {
-
-            if let Ok(mut stream) = LocalSocketStream::connect(ipc_channel.to_ns_name::<GenericNamespaced>()?) {
-                let _ = stream.write(b"{}");
-            }
-
             finish(tx, callback_thread)?;
             return Err(error)
         },
@@ -226,62 +482,114 @@ pub fn published_file_details(steam_id: u32, published_file_ids: &str, ipc_chann
 
 /// This function is used to upload a new mod to the Workshop. For updating mods, do not use this. Use update instead.
 pub fn upload(
+    ipc: &mut IpcChannel,
     base64: bool,
     steam_id: u32,
     pack_path: &Path,
+    preview_path: &Option<PathBuf>,
     title: &str,
     description: &Option<String>,
     tags: &[String],
     changelog: &Option<String>,
     visibility: &Option<u32>,
 ) -> Result<()> {
+    check_visibility(visibility)?;
 
     // Initialize the API.
-    let (client, tx, callback_thread) = init(steam_id, None)?;
+    let (client, tx, callback_thread) = init(steam_id)?;
     let ugc = client.ugc();
 
-    // Create the item.
-    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
-    create_item(&ugc, tx_query, steam_id);
-
-    let response = rx_query.recv()?;
-    let published_file_id = match response {
-        SteamWorksThreadMessage::PublishedFileId(id) => id,
-        SteamWorksThreadMessage::Error(error) => {
+    let published_file_id = match new_item(&ugc, steam_id) {
+        Ok(published_file_id) => published_file_id,
+        Err(error) => {
             finish(tx, callback_thread)?;
             return Err(error)
-        },
-        _ => panic!("{response:?}")
+        }
     };
 
-    // We need to subscribe ourself to the item. Otherwise we'll not get it's data in a data request.
-    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
-    subscribe_item(&ugc, tx_query, published_file_id);
+    // Finally update it with the local file.
+    update(ipc, Some(Ok((client, tx, callback_thread))), Some(ugc), base64, published_file_id, steam_id, pack_path, preview_path, title, description, tags, changelog, visibility)
+}
 
-    let response = rx_query.recv()?;
-    match response {
-        SteamWorksThreadMessage::Ok => {},
-        SteamWorksThreadMessage::Error(error) => {
-            finish(tx, callback_thread)?;
-            return Err(error)
-        },
-        _ => panic!("{response:?}")
-    };
+/// This function uploads or updates all the items of a manifest, in order, and writes the result of each one to a JSON file.
+///
+/// Items without published file id are created. If an item fails, the rest are skipped unless we're told to continue on errors.
+/// The result file is rewritten after each step, so the ids of the created items are not lost if the batch is interrupted.
+/// If all items are uploaded, the results are also sent through the provided IPC channel. If not, an error is sent instead.
+pub fn batch(ipc: &mut IpcChannel, steam_id: u32, manifest_path: &Path, result_path: &Option<PathBuf>, continue_on_error: bool) -> Result<()> {
+    let manifest = BatchManifest::load(manifest_path)?;
+    let result_path = result_path.clone().unwrap_or_else(|| manifest_path.with_extension("result.json"));
+
+    // Read the descriptions before touching the Workshop, so typos in the manifest don't leave the batch half-done.
+    let descriptions = manifest.items.iter()
+        .map(|item| item.description_file.as_ref().map(std::fs::read_to_string).transpose())
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    for item in &manifest.items {
+        check_visibility(&item.visibility).map_err(|error| anyhow!("Error in the manifest item for {}: {}", item.pack_path.to_string_lossy(), error))?;
+    }
 
-    // Finally update it with the local file.
-    update(Some(Ok((client, tx, callback_thread))), Some(ugc), base64, published_file_id, steam_id, pack_path, title, description, tags, changelog, visibility)
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let total = manifest.items.len();
+    let mut failed = 0;
+    let mut results = Vec::with_capacity(total);
+    for (index, (item, description)) in manifest.items.iter().zip(descriptions).enumerate() {
+        results.push(BatchItemResult {
+            pack_path: item.pack_path.to_path_buf(),
+            published_file_id: item.published_file_id,
+            status: BatchItemStatus::Pending,
+        });
+
+        let status = if failed > 0 && !continue_on_error {
+            BatchItemStatus::Skipped
+        } else {
+            info!("Uploading item {} of {}: {}.", index + 1, total, item.pack_path.to_string_lossy());
+
+            match upload_batch_item(ipc, &ugc, steam_id, item, &description, &result_path, &mut results) {
+                Ok(_) => BatchItemStatus::Uploaded,
+                Err(error) => {
+                    error!("Error uploading {}: {}", item.pack_path.to_string_lossy(), error);
+                    failed += 1;
+                    BatchItemStatus::Failed(error.to_string())
+                }
+            }
+        };
+
+        if let Some(result) = results.last_mut() {
+            result.status = status;
+        }
+
+        write_batch_results(&result_path, &results);
+    }
+
+    finish(tx, callback_thread)?;
+    info!("Results written to {}.", result_path.to_string_lossy());
+
+    if failed > 0 {
+        Err(anyhow!("{} of {} items failed to upload. Check {} for more info.", failed, total, result_path.to_string_lossy()))
+    } else {
+        ipc.respond(&results);
+        Ok(())
+    }
 }
 
 /// This function is used to update an existing mod on the Workshop. For new mods, do not use this. Use upload instead.
 ///
-/// The first two arguments are for internal re-use of this function. Pass them as none if you're just calling this function to update a mod.
+/// The api and ugc arguments are for internal re-use of this function. Pass them as none if you're just calling this function to update a mod.
+///
+/// The id of the item is sent through the provided IPC channel once the upload is done.
 pub fn update(
+    ipc: &mut IpcChannel,
     api: Option<Result<(Client, Sender<SteamWorksThreadMessage>, JoinHandle<()>)>>,
     ugc: Option<UGC<ClientManager>>,
     base64: bool,
     published_file_id: PublishedFileId,
     steam_id: u32,
     pack_path: &Path,
+    preview_path: &Option<PathBuf>,
     title: &str,
     description: &Option<String>,
     tags: &[String],
@@ -290,15 +598,43 @@ pub fn update(
 ) -> Result<()> {
 
     // Initialize the API.
-    let (client, tx, callback_thread) = api.unwrap_or_else(|| init(steam_id, None))?;
+    let (client, tx, callback_thread) = api.unwrap_or_else(|| init(steam_id))?;
     let ugc = ugc.unwrap_or_else(|| client.ugc());
 
+    let result = upload_item_files(ipc, &ugc, base64, published_file_id, steam_id, pack_path, preview_path, title, description, tags, changelog, visibility);
+    finish(tx, callback_thread)?;
+    result?;
+
+    ipc.respond(&published_file_id.0);
+    Ok(())
+}
+
+/// This function uploads the pack and preview of an existing item, along with its details, and waits until the upload finishes.
+///
+/// This function does NOT finish the background thread.
+fn upload_item_files(
+    ipc: &mut IpcChannel,
+    ugc: &UGC<ClientManager>,
+    base64: bool,
+    published_file_id: PublishedFileId,
+    steam_id: u32,
+    pack_path: &Path,
+    preview_path: &Option<PathBuf>,
+    title: &str,
+    description: &Option<String>,
+    tags: &[String],
+    changelog: &Option<String>,
+    visibility: &Option<u32>,
+) -> Result<()> {
+
     // Sanitize the pack_path.
     let pack_path = path_to_absolute_path(pack_path, true);
 
-    // Prepare the preview path. We replicate the same behavior as the vanilla launcher.
-    let mut preview_path = pack_path.to_path_buf();
-    preview_path.set_extension("png");
+    // Prepare the preview path. If we don't get one, we replicate the same behavior as the vanilla launcher.
+    let preview_path = match preview_path {
+        Some(preview_path) => path_to_absolute_path(preview_path, true),
+        None => pack_path.with_extension("png"),
+    };
 
     let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
 
@@ -347,20 +683,23 @@ pub fn update(
     let mut pack_path_dest = upload_path.to_path_buf();
     pack_path_dest.push(pack_path.file_name().unwrap());
 
+    // The preview always gets the name of the pack, so the launcher finds it next to the downloaded pack.
     let mut preview_path_dest = upload_path.to_path_buf();
-    preview_path_dest.push(preview_path.file_name().unwrap());
+    preview_path_dest.push(pack_path.with_extension("png").file_name().unwrap());
 
     std::fs::copy(&pack_path, pack_path_dest)?;
     std::fs::copy(&preview_path, &preview_path_dest)?;
 
     info!("Copying done, preparing upload.");
+    ipc.progress(Stage::Started, 0, 0);
 
-    let update_handle = upload_item_content(&ugc, tx_query, steam_id, published_file_id, &upload_path, &preview_path, &title, &description, tags, &changelog, visibility);
+    let update_handle = upload_item_content(ugc, tx_query, steam_id, published_file_id, &upload_path, &preview_path, &title, &description, tags, &changelog, visibility)?;
 
     // Initialize the progress bar. The upload is a 5-step process, and the bar should come at 3 and 4.
     let mut bar: Option<ProgressBar> = None;
     let mut prev_status = UpdateStatus::Invalid;
     let mut prev_total = 0;
+    let mut prev_loaded = 0;
 
     // We loop keeping painting the progress to the terminal until we're done.
     loop {
@@ -382,13 +721,9 @@ pub fn update(
                     }
 
                     info!("Temp files deleted.");
-
-                    return finish(tx, callback_thread)
-                },
-                SteamWorksThreadMessage::Error(error) => {
-                    finish(tx, callback_thread)?;
-                    return Err(error)
+                    return Ok(())
                 },
+                SteamWorksThreadMessage::Error(error) => return Err(error),
                 _ => panic!("{response:?}")
             }
 
@@ -400,12 +735,14 @@ pub fn update(
                         if prev_status != UpdateStatus::PreparingConfig {
                             prev_status = UpdateStatus::PreparingConfig;
                             info!("Preparing config...");
+                            ipc.progress(Stage::PreparingConfig, 0, 0);
                         }
                     },
                     UpdateStatus::PreparingContent => {
                         if prev_status != UpdateStatus::PreparingContent {
                             prev_status = UpdateStatus::PreparingContent;
                             info!("Preparing content...");
+                            ipc.progress(Stage::PreparingContent, 0, 0);
                         }
                     },
                     UpdateStatus::UploadingContent => {
@@ -423,6 +760,11 @@ pub fn update(
                         if let Some(ref bar) = bar {
                             bar.set_position(loaded);
                         }
+
+                        if total > 0 && loaded != prev_loaded {
+                            prev_loaded = loaded;
+                            ipc.progress(Stage::UploadingContent, loaded, total);
+                        }
                     },
                     UpdateStatus::UploadingPreviewFile => {
                         if prev_status != UpdateStatus::UploadingPreviewFile {
@@ -434,6 +776,7 @@ pub fn update(
                             }
 
                             prev_total = 0;
+                            prev_loaded = 0;
                         }
 
                         // Total takes some time to update after changing status.
@@ -446,6 +789,11 @@ pub fn update(
                         if let Some(ref bar) = bar {
                             bar.set_position(loaded);
                         }
+
+                        if total > 0 && loaded != prev_loaded {
+                            prev_loaded = loaded;
+                            ipc.progress(Stage::UploadingPreview, loaded, total);
+                        }
                     },
                     UpdateStatus::CommittingChanges => {
                         if prev_status != UpdateStatus::CommittingChanges {
@@ -459,6 +807,7 @@ pub fn update(
                             bar = None;
 
                             info!("Committing changes...");
+                            ipc.progress(Stage::CommittingChanges, 0, 0);
                         }
                     },
 
@@ -478,59 +827,119 @@ pub fn update(
 }
 
 /// This function tries to download all mods a user has subscribed to from a game.
-pub fn download_subscribed_mods(steam_id: u32, published_file_ids: Option<String>) -> Result<()> {
+///
+/// Items requested by id the user is not subscribed to are only subscribed to if `subscribe` is true, like when importing a collection.
+/// Failing items don't stop the rest, but they're reported as an error at the end.
+pub fn download_subscribed_mods(ipc: &mut IpcChannel, steam_id: u32, published_file_ids: Option<String>, subscribe: bool) -> Result<()> {
 
     // Initialize the API.
-    let (client, tx, callback_thread) = init(steam_id, None)?;
+    let (client, tx, callback_thread) = init(steam_id)?;
     let ugc = client.ugc();
 
     // Get the published_file_ids.
-    let published_file_ids = match published_file_ids {
+    let subscribed_items = ugc.subscribed_items();
+    let published_file_ids: Vec<PublishedFileId> = match published_file_ids {
         Some(ids) => ids.split(",").filter_map(|x| x.parse::<u64>().ok()).map(|x| PublishedFileId(x)).collect(),
-        None => ugc.subscribed_items(),
+        None => subscribed_items.to_vec(),
     };
 
+    let total = published_file_ids.len() as u64;
+    let mut failed = 0;
+    for (index, published_file_id) in published_file_ids.into_iter().enumerate() {
+        ipc.progress(Stage::Downloading, index as u64, total);
+
+        // Items from a collection may not be subscribed yet. Subscribe to them, so Steam keeps them updated.
+        let result = if subscribe && !subscribed_items.contains(&published_file_id) {
+            subscribe_and_download_item(&client, &ugc, &subscribed_items, published_file_id, true)
+        } else {
+            download_item(&client, &ugc, published_file_id)
+        };
+
+        if let Err(error) = result {
+            error!("Failed to download Workshop item with ID {}: {}", published_file_id.0, error);
+            failed += 1;
+        }
+    }
+
+    finish(tx, callback_thread)?;
+
+    if failed > 0 {
+        Err(anyhow!("Failed to download {} items. Check the log for more info.", failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// This function subscribes to the provided items, and optionally downloads them.
+///
+/// Broken legacy items are unsubscribed first, as that's the only way to get Steam to download them again.
+/// Failing items don't stop the rest, but they're reported as an error at the end.
+pub fn subscribe(steam_id: u32, published_file_ids: &str, download: bool) -> Result<()> {
+    let published_file_ids = parse_published_file_ids(published_file_ids);
+
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let subscribed_items = ugc.subscribed_items();
+    let mut failed = 0;
     for published_file_id in published_file_ids {
+        if let Err(error) = subscribe_and_download_item(&client, &ugc, &subscribed_items, published_file_id, download) {
+            error!("Failed to subscribe to Workshop item with ID {}: {}", published_file_id.0, error);
+            failed += 1;
+        }
+    }
 
-        if ugc.download_item(published_file_id, true) {
-            info!("Downloading workshop item with ID: {}.", published_file_id.0);
+    finish(tx, callback_thread)?;
 
-            let (tx_callback, rx_callback): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
-            let _cb = client.register_callback(move |d: DownloadItemResult| {
-                match d.error {
-                    Some(error) => {
-                        error!("Error downloading workshop item with ID {}: {}", published_file_id.0, error);
-                        let _ = tx_callback.send(SteamWorksThreadMessage::Error(error.into()));
-                    }
-                    None => {
-                        info!("Workshop item with ID {} downloaded.", published_file_id.0);
-                        let _ = tx_callback.send(SteamWorksThreadMessage::Ok);
-                    }
-                }
-            });
+    if failed > 0 {
+        Err(anyhow!("Failed to subscribe to {} items. Check the log for more info.", failed))
+    } else {
+        Ok(())
+    }
+}
 
-            let response = rx_callback.recv()?;
-            match response {
-                SteamWorksThreadMessage::Ok => {
-                    if let Some(install_info) = ugc.item_install_info(published_file_id) {
+/// This function unsubscribes from the provided items. Steam removes their files once the game is closed.
+pub fn unsubscribe(steam_id: u32, published_file_ids: &str) -> Result<()> {
+    let published_file_ids = parse_published_file_ids(published_file_ids);
 
-                        // So, fun bug: if the item is a legacy item and somehow it got deleted from the content folder, steam will consistently fail to re-download it.
-                        // Solution? Unsubscribe, then resubscribe, then download again. Fuck legacy mods.
-                        if install_info.folder.ends_with(".bin") && !PathBuf::from(&install_info.folder).is_file() {
-                            warn!("Steam lied about downloading Workshop item with ID {}. Posible legacy mod.", published_file_id.0);
-                            warn!("To re-download this one, go to https://steamcommunity.com/sharedfiles/filedetails/?id={}, then unsubscribe and re-subscribe.", published_file_id.0);
-                        }
-                    }
-                    continue
-                },
-                SteamWorksThreadMessage::Error(_) => continue,
-                _ => panic!("{response:?}")
-            };
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let mut failed = 0;
+    for published_file_id in published_file_ids {
+        let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+        unsubscribe_item(&ugc, tx_query, published_file_id);
+
+        let response = rx_query.recv()?;
+        match response {
+            SteamWorksThreadMessage::Ok => {},
+            SteamWorksThreadMessage::Error(_) => failed += 1,
+            _ => panic!("{response:?}")
         }
     }
 
     finish(tx, callback_thread)?;
-    Ok(())
+
+    if failed > 0 {
+        Err(anyhow!("Failed to unsubscribe from {} items. Check the log for more info.", failed))
+    } else {
+        Ok(())
+    }
+}
+
+/// This function sends through the provided IPC channel the ids of the items the user is subscribed to.
+pub fn subscribed_items(ipc: &mut IpcChannel, steam_id: u32) -> Result<()> {
+
+    // Initialize the API.
+    let (client, tx, callback_thread) = init(steam_id)?;
+    let ugc = client.ugc();
+
+    let subscribed_items = ugc.subscribed_items().iter().map(|id| id.0).collect::<Vec<_>>();
+    ipc.respond(&subscribed_items);
+
+    finish(tx, callback_thread)
 }
 
 //---------------------------------------------------------------------------//
@@ -538,19 +947,8 @@ pub fn download_subscribed_mods(steam_id: u32, published_file_ids: Option<String
 //---------------------------------------------------------------------------//
 
 /// This function initializes the client and callback thread. DO NOT CALL IT IF THERE'S ALREADY A CLIENT ALIVE.
-fn init(steam_id: u32, channel: Option<&str>) -> Result<(Client, Sender<SteamWorksThreadMessage>, JoinHandle<()>)> {
-    let (client, single) = match Client::init_app(steam_id) {
-        Ok(client) => client,
-        Err(error) => {
-            if let Some(channel) = channel {
-                if let Ok(mut stream) = LocalSocketStream::connect(channel.to_ns_name::<GenericNamespaced>()?) {
-                    let _ = stream.write("{}".as_bytes());
-                }
-            }
-
-            return Err(From::from(error));
-        }
-    };
+fn init(steam_id: u32) -> Result<(Client, Sender<SteamWorksThreadMessage>, JoinHandle<()>)> {
+    let (client, single) = Client::init_app(steam_id)?;
     let (tx, rx) = unbounded();
 
     let thread = std::thread::spawn(move || { callback_loop(single, rx); });
@@ -578,6 +976,70 @@ fn callback_loop(single: SingleClient<ClientManager>, rx: Receiver<SteamWorksThr
     }
 }
 
+/// This function uploads an item of a batch, creating it first if it's new. The id of new items is stored as soon as they're created.
+///
+/// This function does NOT finish the background thread.
+fn upload_batch_item(ipc: &mut IpcChannel, ugc: &UGC<ClientManager>, steam_id: u32, item: &BatchItem, description: &Option<String>, result_path: &Path, results: &mut [BatchItemResult]) -> Result<()> {
+    let id = match item.published_file_id {
+        Some(id) => PublishedFileId(id),
+        None => {
+            let id = new_item(ugc, steam_id)?;
+
+            // Save the id right away. If the upload doesn't finish, the next batch needs it to update the item instead of creating another one.
+            if let Some(result) = results.last_mut() {
+                result.published_file_id = Some(id.0);
+            }
+
+            write_batch_results(result_path, results);
+            id
+        }
+    };
+
+    upload_item_files(ipc, ugc, false, id, steam_id, &item.pack_path, &item.preview_path, &item.title, description, &item.tags, &item.changelog, &item.visibility)
+}
+
+/// This function writes the results of a batch to the provided path.
+///
+/// Errors are only logged, so a problem with the result file doesn't stop the uploads.
+fn write_batch_results(result_path: &Path, results: &[BatchItemResult]) {
+    let write = || -> Result<()> {
+        let mut file = BufWriter::new(File::create(result_path)?);
+        file.write_all(to_string_pretty(&results)?.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    };
+
+    if let Err(error) = write() {
+        error!("Error writing the batch results to {}: {}", result_path.to_string_lossy(), error);
+    }
+}
+
+/// This function creates a new item in the Workshop and subscribes us to it, so we get its data in data requests.
+///
+/// This function does NOT finish the background thread.
+fn new_item(ugc: &UGC<ClientManager>, steam_id: u32) -> Result<PublishedFileId> {
+    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+    create_item(ugc, tx_query, steam_id, FileType::Community);
+
+    let response = rx_query.recv()?;
+    let published_file_id = match response {
+        SteamWorksThreadMessage::PublishedFileId(id) => id,
+        SteamWorksThreadMessage::Error(error) => return Err(error),
+        _ => panic!("{response:?}")
+    };
+
+    // We need to subscribe ourself to the item. Otherwise we'll not get it's data in a data request.
+    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+    subscribe_item(ugc, tx_query, published_file_id);
+
+    let response = rx_query.recv()?;
+    match response {
+        SteamWorksThreadMessage::Ok => Ok(published_file_id),
+        SteamWorksThreadMessage::Error(error) => Err(error),
+        _ => panic!("{response:?}")
+    }
+}
+
 /// Use this to close the callback thread.
 fn finish(tx: Sender<SteamWorksThreadMessage>, callback_thread: JoinHandle<()>) -> Result<()> {
     tx.send(SteamWorksThreadMessage::Exit)?;
@@ -585,6 +1047,111 @@ fn finish(tx: Sender<SteamWorksThreadMessage>, callback_thread: JoinHandle<()>)
     Ok(())
 }
 
+/// This function parses a comma-separated list of published file ids, ignoring the invalid ones.
+fn parse_published_file_ids(published_file_ids: &str) -> Vec<PublishedFileId> {
+    let mut ids = vec![];
+    for id in published_file_ids.split(",").filter(|id| !id.is_empty()) {
+        match id.parse::<u64>() {
+            Ok(id) => ids.push(PublishedFileId(id)),
+            Err(error) => warn!("Invalid Steam ID received: {}. Ignoring with error: {}.", id, error),
+        }
+    }
+
+    ids
+}
+
+/// This function downloads an item, waiting until the download finishes.
+///
+/// Download errors are logged and returned, so the caller can decide if they stop the download of other items.
+fn download_item(client: &Client, ugc: &UGC<ClientManager>, published_file_id: PublishedFileId) -> Result<()> {
+    if !ugc.download_item(published_file_id, true) {
+        error!("Steam refused to download workshop item with ID {}.", published_file_id.0);
+        return Err(anyhow!("Steam refused to download workshop item with ID {}. Make sure the item exists and you're subscribed to it.", published_file_id.0));
+    }
+
+    info!("Downloading workshop item with ID: {}.", published_file_id.0);
+
+    let (tx_callback, rx_callback): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+    let _cb = client.register_callback(move |d: DownloadItemResult| {
+        match d.error {
+            Some(error) => {
+                error!("Error downloading workshop item with ID {}: {}", published_file_id.0, error);
+                let _ = tx_callback.send(SteamWorksThreadMessage::Error(error.into()));
+            }
+            None => {
+                info!("Workshop item with ID {} downloaded.", published_file_id.0);
+                let _ = tx_callback.send(SteamWorksThreadMessage::Ok);
+            }
+        }
+    });
+
+    let response = rx_callback.recv()?;
+    match response {
+        SteamWorksThreadMessage::Ok => {
+
+            // So, fun bug: if the item is a legacy item and somehow it got deleted from the content folder, steam will consistently fail to re-download it.
+            // Solution? Unsubscribe, then resubscribe, then download again. Fuck legacy mods.
+            if is_broken_legacy_item(ugc, published_file_id) {
+                warn!("Steam lied about downloading Workshop item with ID {}. Posible legacy mod.", published_file_id.0);
+                warn!("To re-download this one, re-subscribe to it from Runcher's mod list, or go to https://steamcommunity.com/sharedfiles/filedetails/?id={}, then unsubscribe and re-subscribe.", published_file_id.0);
+            }
+
+            Ok(())
+        },
+        SteamWorksThreadMessage::Error(error) => Err(error),
+        _ => panic!("{response:?}")
+    }
+}
+
+/// This function subscribes to an item, unless we're already subscribed to it, and optionally downloads it.
+fn subscribe_and_download_item(client: &Client, ugc: &UGC<ClientManager>, subscribed_items: &[PublishedFileId], published_file_id: PublishedFileId, download: bool) -> Result<()> {
+    let subscribed = subscribed_items.contains(&published_file_id);
+    if subscribed && is_broken_legacy_item(ugc, published_file_id) {
+        info!("Workshop item with ID {} is a broken legacy item. Re-subscribing to it.", published_file_id.0);
+
+        let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+        unsubscribe_item(ugc, tx_query, published_file_id);
+
+        let response = rx_query.recv()?;
+        match response {
+            SteamWorksThreadMessage::Ok => {},
+            SteamWorksThreadMessage::Error(error) => return Err(error),
+            _ => panic!("{response:?}")
+        }
+    } else if subscribed {
+        info!("Already subscribed to Workshop item with ID {}.", published_file_id.0);
+        if download {
+            download_item(client, ugc, published_file_id)?;
+        }
+
+        return Ok(());
+    }
+
+    let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+    subscribe_item(ugc, tx_query, published_file_id);
+
+    let response = rx_query.recv()?;
+    match response {
+        SteamWorksThreadMessage::Ok => {},
+        SteamWorksThreadMessage::Error(error) => return Err(error),
+        _ => panic!("{response:?}")
+    }
+
+    if download {
+        download_item(client, ugc, published_file_id)?;
+    }
+
+    Ok(())
+}
+
+/// This function checks if an item is a legacy item Steam thinks is downloaded, but whose file is missing.
+fn is_broken_legacy_item(ugc: &UGC<ClientManager>, published_file_id: PublishedFileId) -> bool {
+    match ugc.item_install_info(published_file_id) {
+        Some(install_info) => install_info.folder.ends_with(".bin") && !PathBuf::from(&install_info.folder).is_file(),
+        None => false,
+    }
+}
+
 /// Function to subscribe to an specific item in the workshop.
 ///
 /// This function does NOT finish the background thread.
@@ -610,7 +1177,6 @@ fn subscribe_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessa
 /// Function to unsubscribe from an specific item in the workshop.
 ///
 /// This function does NOT finish the background thread.
-#[allow(dead_code)]
 fn unsubscribe_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, published_file_id: PublishedFileId) {
     ugc.unsubscribe_item(
         published_file_id,
@@ -662,13 +1228,199 @@ fn get_published_file_details(ugc: &UGC<ClientManager>, sender: Sender<SteamWork
     }
 }
 
+/// Function to retrieve the children of the collections in a list of PublishedFileIds.
+fn get_collections(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, published_file_ids: Vec<PublishedFileId>) {
+    match ugc.query_items(published_file_ids) {
+        Ok(handle) => {
+            handle.include_children(true)
+                .fetch(move |results| {
+                    match results {
+                        Ok(results) => {
+                            let mut collections = HashMap::new();
+                            for (index, result) in results.iter().enumerate() {
+                                if let Some(result) = result {
+                                    if matches!(result.file_type, FileType::Collection) {
+                                        collections.insert(result.published_file_id, results.get_children(index as u32).unwrap_or_default());
+                                    }
+                                }
+                            }
+
+                            let _ = sender.send(SteamWorksThreadMessage::Collections(collections));
+                        }
+
+                        Err(error) => {
+                            error!("Collection query failed: {}", error);
+                            let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error)));
+                        },
+                    }
+                },);
+            }
+        Err(error) => { let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error))); },
+    }
+}
+
+/// This function adds the provided items of a collection to the list of items, expanding the ones that are collections.
+fn resolve_collection_children(source: &impl CollectionSource, children: &[PublishedFileId], items: &mut Vec<PublishedFileId>, visited: &mut HashSet<PublishedFileId>) -> Result<()> {
+    let children = children.iter()
+        .filter(|child| !visited.contains(*child))
+        .copied()
+        .collect::<Vec<_>>();
+
+    if children.is_empty() {
+        return Ok(());
+    }
+
+    let collections = source.collections(&children)?;
+    for child in children {
+        if !visited.insert(child) {
+            continue;
+        }
+
+        match collections.get(&child) {
+            Some(grandchildren) => resolve_collection_children(source, grandchildren, items, visited)?,
+            None => items.push(child),
+        }
+    }
+
+    Ok(())
+}
+
+/// This function creates the collection if needed and updates its items.
+///
+/// This function does NOT finish the background thread.
+fn sync_collection(
+    ugc: &UGC<ClientManager>,
+    app_id: u32,
+    published_file_id: Option<u64>,
+    items: &[PublishedFileId],
+    title: &str,
+    description: &Option<String>,
+    visibility: &Option<u32>,
+) -> Result<PublishedCollection> {
+
+    check_visibility(visibility)?;
+
+    // New collections need to be created, and get their title and description set. Existing ones keep theirs, in case they were edited on Steam.
+    let (published_file_id, current) = match published_file_id {
+        Some(published_file_id) => {
+            let published_file_id = PublishedFileId(published_file_id);
+            let source = SteamCollectionSource { ugc };
+            let mut collections = source.collections(&[published_file_id])?;
+            let current = collections.remove(&published_file_id).ok_or_else(|| anyhow!("Workshop item with ID {} is not a collection.", published_file_id.0))?;
+
+            (published_file_id, current)
+        }
+
+        None => {
+            let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+            create_item(ugc, tx_query, app_id, FileType::Collection);
+
+            let published_file_id = match rx_query.recv()? {
+                SteamWorksThreadMessage::PublishedFileId(id) => id,
+                SteamWorksThreadMessage::Error(error) => return Err(error),
+                response => panic!("{response:?}")
+            };
+
+            let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+            update_item_details(ugc, tx_query, app_id, published_file_id, title, description, visibility)?;
+
+            match rx_query.recv()? {
+                SteamWorksThreadMessage::Ok => {},
+                SteamWorksThreadMessage::Error(error) => return Err(error),
+                response => panic!("{response:?}")
+            }
+
+            (published_file_id, vec![])
+        }
+    };
+
+    let (to_remove, to_add) = collection_changes(&current, items);
+    info!("Updating collection with ID {}: {} items to remove, {} items to add.", published_file_id.0, to_remove.len(), to_add.len());
+
+    let mut failed_removals = vec![];
+    for child in to_remove {
+        let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+        remove_collection_item(ugc, tx_query, published_file_id, child);
+
+        match rx_query.recv()? {
+            SteamWorksThreadMessage::Ok => {},
+            SteamWorksThreadMessage::Error(_) => failed_removals.push(child.0),
+            response => panic!("{response:?}")
+        }
+    }
+
+    let mut failed_items = vec![];
+    for child in to_add {
+        let (tx_query, rx_query): (Sender<SteamWorksThreadMessage>, Receiver<SteamWorksThreadMessage>) = unbounded();
+        add_collection_item(ugc, tx_query, published_file_id, child);
+
+        match rx_query.recv()? {
+            SteamWorksThreadMessage::Ok => {},
+            SteamWorksThreadMessage::Error(_) => failed_items.push(child.0),
+            response => panic!("{response:?}")
+        }
+    }
+
+    Ok(PublishedCollection {
+        published_file_id: published_file_id.0,
+        failed_items,
+        failed_removals,
+    })
+}
+
+/// Function to add an item to a collection in the workshop.
+///
+/// This function does NOT finish the background thread.
+fn add_collection_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, collection_id: PublishedFileId, published_file_id: PublishedFileId) {
+    ugc.add_dependency(
+        collection_id,
+        published_file_id,
+        move |result| {
+            match result {
+                Ok(_) => {
+                    info!("Added Workshop item with ID {} to the collection.", published_file_id.0);
+                    let _ = sender.send(SteamWorksThreadMessage::Ok);
+                }
+
+                Err(error) => {
+                    error!("Failed to add Workshop item with ID {} to the collection: {}.", published_file_id.0, error.to_string());
+                    let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error)));
+                },
+            }
+        },
+    );
+}
+
+/// Function to remove an item from a collection in the workshop.
+///
+/// This function does NOT finish the background thread.
+fn remove_collection_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, collection_id: PublishedFileId, published_file_id: PublishedFileId) {
+    ugc.remove_dependency(
+        collection_id,
+        published_file_id,
+        move |result| {
+            match result {
+                Ok(_) => {
+                    info!("Removed Workshop item with ID {} from the collection.", published_file_id.0);
+                    let _ = sender.send(SteamWorksThreadMessage::Ok);
+                }
+
+                Err(error) => {
+                    error!("Failed to remove Workshop item with ID {} from the collection: {}.", published_file_id.0, error.to_string());
+                    let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error)));
+                },
+            }
+        },
+    );
+}
+
 /// Function to create an item in a specific workshop.
 ///
-/// This only creates the item. You need to upload a pack after this.
-fn create_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, app_id: u32) {
+/// This only creates the item. You need to upload a pack or add items to it after this.
+fn create_item(ugc: &UGC<ClientManager>, sender: Sender<SteamWorksThreadMessage>, app_id: u32, file_type: FileType) {
     ugc.create_item(
         AppId(app_id),
-        FileType::Community,
+        file_type,
         move |create_result| {
 
             match create_result {
@@ -701,7 +1453,7 @@ fn upload_item_content(
     tags: &[String],
     changelog: &Option<String>,
     visibility: &Option<u32>,
-) -> UpdateWatchHandle<ClientManager> {
+) -> Result<UpdateWatchHandle<ClientManager>> {
 
     // uploading the content of the workshop item
     // this process uses a builder pattern to set properties of the item
@@ -725,13 +1477,7 @@ fn upload_item_content(
         .title(title);
 
     if let Some(visibility) = visibility {
-        handle = handle.visibility(match visibility {
-            0 => PublishedFileVisibility::Public,
-            1 => PublishedFileVisibility::FriendsOnly,
-            2 => PublishedFileVisibility::Private,
-            3 => PublishedFileVisibility::Unlisted,
-            _ => panic!("Invalid Visibility"),
-        });
+        handle = handle.visibility(published_file_visibility(*visibility)?);
     }
 
     let mut tags = tags.to_vec();
@@ -740,26 +1486,21 @@ fn upload_item_content(
     if let Ok(game) = GameInfo::game_by_steam_id(app_id as u64) {
         if let Ok(valid_tags) = game.steam_workshop_tags() {
 
-            // NOTE: Tags are tricky. All mods uploaded to the workshop contain two tags: "mod" and one from a list of available tags.
-            // And CA don't want people adding custom tags to the workshop. So we need to limit it to one tag from the list of existing tags.
+            // NOTE: Tags are tricky. All mods uploaded to the workshop contain the "mod" tag, plus tags from a list of available tags.
+            // And CA don't want people adding custom tags to the workshop. So we need to limit them to the ones in the list of existing tags.
             tags.retain(|tag| valid_tags.contains(tag));
 
             // Remove duplicated tags.
             tags.sort();
             tags.dedup();
 
-            // "mod" has to be the first tag. The second one is user-chosen.
+            // "mod" has to be the first tag. The rest are user-chosen.
             if let Some(pos) = tags.iter().position(|x| x == TOTAL_WAR_BASE_TAG) {
                 tags.remove(pos);
             }
 
             tags.insert(0, TOTAL_WAR_BASE_TAG.to_owned());
 
-            // Reduce if we have more than two tags, trim it.
-            if tags.len() > 2 {
-                let _ = tags.split_off(2);
-            }
-
             // If all tags got deleted and we only have mod, add the first one from the list of valid tags.
             if tags.len() == 1 {
                 tags.push(valid_tags.first().unwrap().to_owned());
@@ -774,7 +1515,7 @@ fn upload_item_content(
         handle = handle.description(description);
     }
 
-    handle.submit(changelog.as_deref(),
+    Ok(handle.submit(changelog.as_deref(),
         move |upload_result| {
             match upload_result {
                 Ok((published_id, needs_to_agree_to_terms)) => {
@@ -795,7 +1536,61 @@ fn upload_item_content(
                 Err(error) => { let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error))); },
             }
         }
-    )
+    ))
+}
+
+/// Function to update the title, description and visibility of an item, without touching its content.
+///
+/// This function does NOT finish the background thread.
+fn update_item_details(
+    ugc: &UGC<ClientManager>,
+    sender: Sender<SteamWorksThreadMessage>,
+    app_id: u32,
+    published_id: PublishedFileId,
+    title: &str,
+    description: &Option<String>,
+    visibility: &Option<u32>,
+) -> Result<()> {
+    let mut handle = ugc.start_item_update(AppId(app_id), published_id)
+        .title(title);
+
+    if let Some(ref description) = description {
+        handle = handle.description(description);
+    }
+
+    if let Some(visibility) = visibility {
+        handle = handle.visibility(published_file_visibility(*visibility)?);
+    }
+
+    handle.submit(None,
+        move |update_result| {
+            match update_result {
+                Ok(_) => {
+                    info!("Updated details of item with id {:?}", published_id);
+                    let _ = sender.send(SteamWorksThreadMessage::Ok);
+                }
+                Err(error) => { let _ = sender.send(SteamWorksThreadMessage::Error(From::from(error))); },
+            }
+        }
+    );
+
+    Ok(())
+}
+
+/// This function turns the visibility we receive from the cli into the one Steam expects.
+fn published_file_visibility(visibility: u32) -> Result<PublishedFileVisibility> {
+    match visibility {
+        0 => Ok(PublishedFileVisibility::Public),
+        1 => Ok(PublishedFileVisibility::FriendsOnly),
+        2 => Ok(PublishedFileVisibility::Private),
+        3 => Ok(PublishedFileVisibility::Unlisted),
+        _ => Err(anyhow!("Invalid visibility: {}. Valid values are 0 (public), 1 (friends only), 2 (private) and 3 (unlisted).", visibility)),
+    }
+}
+
+/// This function checks the visibility we receive from the cli is valid, so we can fail before creating anything on the Workshop.
+fn check_visibility(visibility: &Option<u32>) -> Result<()> {
+    visibility.map(published_file_visibility).transpose().map(|_| ())
 }
 
 /// This just initializes a nice progress bar for the uploads.
@@ -807,3 +1602,4 @@ fn progress_bar(total: u64) -> ProgressBar {
         .progress_chars("#>-"));
     bar
 }
+
@@ -0,0 +1,135 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the Workshop functions, using a local stand-in instead of Steam.
+
+use std::collections::HashMap;
+
+use super::*;
+
+fn source() -> LocalCollectionSource {
+    LocalCollectionSource {
+        collections: HashMap::from([
+            (1, vec![10, 2, 11, 10]),
+            (2, vec![20, 11, 3]),
+            (3, vec![30, 2]),
+            (4, vec![]),
+        ]),
+    }
+}
+
+#[test]
+fn test_resolve_collection_nested() {
+    let items = resolve_collection(&source(), PublishedFileId(1)).unwrap();
+    assert_eq!(items, vec![PublishedFileId(10), PublishedFileId(20), PublishedFileId(11), PublishedFileId(30)]);
+}
+
+#[test]
+fn test_resolve_collection_empty() {
+    assert!(resolve_collection(&source(), PublishedFileId(4)).unwrap().is_empty());
+}
+
+#[test]
+fn test_resolve_collection_not_a_collection() {
+    assert!(resolve_collection(&source(), PublishedFileId(10)).is_err());
+}
+
+#[test]
+fn test_collection_changes() {
+    let ids = |ids: &[u64]| ids.iter().map(|id| PublishedFileId(*id)).collect::<Vec<_>>();
+
+    // Same items, same order: nothing to do.
+    assert_eq!(collection_changes(&ids(&[1, 2, 3]), &ids(&[1, 2, 3])), (vec![], vec![]));
+
+    // New items go at the end.
+    assert_eq!(collection_changes(&ids(&[1, 2]), &ids(&[1, 2, 3])), (vec![], ids(&[3])));
+
+    // Everything after the first difference is re-added, to keep the order.
+    assert_eq!(collection_changes(&ids(&[1, 2, 3, 4]), &ids(&[1, 3, 2])), (ids(&[2, 3, 4]), ids(&[3, 2])));
+    assert_eq!(collection_changes(&ids(&[]), &ids(&[1])), (vec![], ids(&[1])));
+}
+
+#[test]
+fn test_batch_manifest_parse() {
+    let base_path = Path::new("/mods");
+    let toml = r#"
+        [[items]]
+        pack_path = "my_mod.pack"
+        published_file_id = 123
+        title = "My Mod"
+        description_file = "descriptions/my_mod.txt"
+        tags = ["units"]
+        visibility = 0
+
+        [[items]]
+        pack_path = "/other/my_other_mod.pack"
+        title = "My Other Mod"
+        changelog = "Initial release."
+    "#;
+
+    let manifest = BatchManifest::parse(toml, true, base_path).unwrap();
+    assert_eq!(manifest.items.len(), 2);
+    assert_eq!(manifest.items[0].pack_path, base_path.join("my_mod.pack"));
+    assert_eq!(manifest.items[0].published_file_id, Some(123));
+    assert_eq!(manifest.items[0].description_file, Some(base_path.join("descriptions/my_mod.txt")));
+    assert_eq!(manifest.items[0].tags, vec!["units".to_owned()]);
+    assert_eq!(manifest.items[1].pack_path, PathBuf::from("/other/my_other_mod.pack"));
+    assert_eq!(manifest.items[1].published_file_id, None);
+    assert!(manifest.items[1].tags.is_empty());
+
+    let json = r#"{ "items": [{ "pack_path": "my_mod.pack", "title": "My Mod", "preview_path": "my_mod.png" }] }"#;
+    let manifest = BatchManifest::parse(json, false, base_path).unwrap();
+    assert_eq!(manifest.items[0].preview_path, Some(base_path.join("my_mod.png")));
+
+    // Title is required, so it's not uploaded without one by mistake.
+    assert!(BatchManifest::parse(r#"{ "items": [{ "pack_path": "my_mod.pack" }] }"#, false, base_path).is_err());
+}
+
+#[test]
+fn test_batch_results_write() {
+    let result_path = std::env::temp_dir().join(format!("workshopper_batch_{}.result.json", std::process::id()));
+    let mut results = vec![
+        BatchItemResult {
+            pack_path: PathBuf::from("/mods/my_mod.pack"),
+            published_file_id: Some(123),
+            status: BatchItemStatus::Uploaded,
+        },
+        BatchItemResult {
+            pack_path: PathBuf::from("/mods/my_new_mod.pack"),
+            published_file_id: Some(456),
+            status: BatchItemStatus::Pending,
+        },
+    ];
+
+    // Interrupted batches keep the ids of the created items.
+    write_batch_results(&result_path, &results);
+    let written: Vec<BatchItemResult> = serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+    assert_eq!(written.len(), 2);
+    assert_eq!(written[1].published_file_id, Some(456));
+    assert_eq!(written[1].status, BatchItemStatus::Pending);
+
+    // Each write replaces the previous one.
+    results[1].status = BatchItemStatus::Failed("Steam is down.".to_owned());
+    write_batch_results(&result_path, &results);
+    let written: Vec<BatchItemResult> = serde_json::from_str(&std::fs::read_to_string(&result_path).unwrap()).unwrap();
+    assert_eq!(written[1].status, BatchItemStatus::Failed("Steam is down.".to_owned()));
+
+    std::fs::remove_file(&result_path).unwrap();
+}
+
+#[test]
+fn test_check_visibility() {
+    assert!(check_visibility(&None).is_ok());
+    assert!(matches!(published_file_visibility(3), Ok(PublishedFileVisibility::Unlisted)));
+
+    // Invalid values must fail before anything is created on the Workshop, not panic in the middle of a batch.
+    let error = check_visibility(&Some(4)).unwrap_err().to_string();
+    assert_eq!(error, "Invalid visibility: 4. Valid values are 0 (public), 1 (friends only), 2 (private) and 3 (unlisted).");
+}
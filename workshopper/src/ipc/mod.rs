@@ -0,0 +1,203 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! This module contains the protocol used to report back to the program that called us.
+//!
+//! The caller opens a local socket and passes its name with `-i`. We send through it one JSON envelope per line:
+//! any amount of progress messages, followed by exactly one response or error. Runcher has a copy of these types,
+//! so any incompatible change here needs a bump of [PROTOCOL_VERSION] on both sides, and an update of `test_files/ipc_messages.jsonl`,
+//! which both sides use in their tests.
+
+use anyhow::{Error, Result};
+use interprocess::local_socket::{GenericNamespaced, prelude::*};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use steamworks::{SteamAPIInitError, SteamError};
+
+use std::io::Write;
+use std::num::ParseIntError;
+use std::string::FromUtf8Error;
+
+use rpfm_lib::integrations::log::warn;
+
+#[cfg(test)] mod test;
+
+/// Version of the protocol. Messages from a different version are rejected by the caller.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+//-------------------------------------------------------------------------------//
+//                              Enums & Structs
+//-------------------------------------------------------------------------------//
+
+/// Wrapper of every message we send, so the caller can check it understands it before parsing it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub version: u32,
+    pub message: Message,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Message {
+    Progress(Progress),
+
+    /// Result of the command. Its format depends on the command, and it's null for commands that return nothing.
+    Response(Value),
+    Error(ErrorMessage),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Progress {
+    pub stage: Stage,
+
+    /// Progress within the stage. Both are 0 for stages we cannot measure.
+    pub current: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stage {
+
+    /// The command is under way and the rest of it cannot fail in ways the caller can fix. Callers not interested in the result can stop listening here.
+    Started,
+    PreparingConfig,
+    PreparingContent,
+    UploadingContent,
+    UploadingPreview,
+    CommittingChanges,
+    Downloading,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorKind {
+
+    /// The Steam API failed to initialize. Usually Steam is not running, or the game is not owned.
+    SteamInit,
+
+    /// Steam returned an error for the request.
+    Workshop,
+
+    /// The arguments we received are not valid.
+    InvalidInput,
+    Io,
+    Other,
+}
+
+/// Connection with the caller. Without channel messages are discarded, so commands behave the same when called by hand.
+#[derive(Default)]
+pub struct IpcChannel {
+    stream: Option<LocalSocketStream>,
+    answered: bool,
+}
+
+//-------------------------------------------------------------------------------//
+//                             Implementations
+//-------------------------------------------------------------------------------//
+
+impl From<&Error> for ErrorKind {
+    fn from(value: &Error) -> Self {
+        if value.is::<SteamAPIInitError>() {
+            Self::SteamInit
+        } else if value.is::<SteamError>() {
+            Self::Workshop
+        } else if value.is::<base64::DecodeError>() || value.is::<FromUtf8Error>() || value.is::<ParseIntError>() || value.is::<toml::de::Error>() || value.is::<serde_json::Error>() {
+            Self::InvalidInput
+        } else if value.is::<std::io::Error>() {
+            Self::Io
+        } else {
+            Self::Other
+        }
+    }
+}
+
+impl IpcChannel {
+
+    /// This function connects to the channel with the provided name, if any.
+    pub fn connect(ipc_channel: &Option<String>) -> Result<Self> {
+        let stream = match ipc_channel {
+            Some(ipc_channel) => Some(LocalSocketStream::connect(ipc_channel.to_ns_name::<GenericNamespaced>()?)?),
+            None => None,
+        };
+
+        Ok(Self {
+            stream,
+            answered: false,
+        })
+    }
+
+    pub fn progress(&mut self, stage: Stage, current: u64, total: u64) {
+        self.send(Message::Progress(Progress {
+            stage,
+            current,
+            total,
+        }));
+    }
+
+    /// This function sends the result of the command. Only the first answer is sent.
+    pub fn respond<T: Serialize>(&mut self, data: &T) {
+        match serde_json::to_value(data) {
+            Ok(data) => self.answer(Message::Response(data)),
+            Err(error) => self.error(&From::from(error)),
+        }
+    }
+
+    /// This function sends an error as the result of the command. Only the first answer is sent.
+    pub fn error(&mut self, error: &Error) {
+        self.answer(Message::Error(ErrorMessage {
+            kind: ErrorKind::from(error),
+            message: error.to_string(),
+        }));
+    }
+
+    /// This function sends the final result of a command, unless the command already answered.
+    pub fn finish(&mut self, result: &Result<()>) {
+        match result {
+            Ok(_) => self.respond(&()),
+            Err(error) => self.error(error),
+        }
+    }
+
+    fn answer(&mut self, message: Message) {
+        if !self.answered {
+            self.answered = true;
+            self.send(message);
+        }
+    }
+
+    /// This function writes a message to the channel.
+    ///
+    /// If the caller stopped listening we just stop sending, as the caller may not care about the rest of the command.
+    fn send(&mut self, message: Message) {
+        if let Some(ref mut stream) = self.stream {
+            let envelope = Envelope {
+                version: PROTOCOL_VERSION,
+                message,
+            };
+
+            let result = serde_json::to_string(&envelope)
+                .map_err(Error::from)
+                .and_then(|mut line| {
+                    line.push('\n');
+                    stream.write_all(line.as_bytes())?;
+                    stream.flush().map_err(From::from)
+                });
+
+            if let Err(error) = result {
+                warn!("The IPC channel got closed, no more messages will be sent through it: {}", error);
+                self.stream = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//---------------------------------------------------------------------------//
+// Copyright (c) 2017-2024 Ismael Gutiérrez González. All rights reserved.
+//
+// This file is part of the Rusted Launcher (Runcher) project,
+// which can be found here: https://github.com/Frodo45127/rpfm.
+//
+// This file is licensed under the MIT license, which can be found here:
+// https://github.com/Frodo45127/rpfm/blob/master/LICENSE.
+//---------------------------------------------------------------------------//
+
+//! Tests for the IPC protocol. These check the wire format, as Runcher parses it with its own copy of the types.
+
+use anyhow::anyhow;
+use serde_json::json;
+
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use super::*;
+
+#[test]
+fn test_envelope_format() {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION,
+        message: Message::Progress(Progress {
+            stage: Stage::UploadingContent,
+            current: 5,
+            total: 10,
+        }),
+    };
+
+    let line = serde_json::to_string(&envelope).unwrap();
+    assert!(!line.contains('\n'));
+    assert_eq!(serde_json::from_str::<Value>(&line).unwrap(), json!({
+        "version": PROTOCOL_VERSION,
+        "message": { "Progress": { "stage": "UploadingContent", "current": 5, "total": 10 } }
+    }));
+
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION,
+        message: Message::Response(serde_json::to_value(()).unwrap()),
+    };
+
+    assert_eq!(serde_json::to_value(&envelope).unwrap(), json!({ "version": PROTOCOL_VERSION, "message": { "Response": null } }));
+}
+
+#[test]
+fn test_error_kind() {
+    assert_eq!(ErrorKind::from(&Error::from("abc".parse::<u64>().unwrap_err())), ErrorKind::InvalidInput);
+    assert_eq!(ErrorKind::from(&Error::from(std::io::Error::new(std::io::ErrorKind::Other, "test"))), ErrorKind::Io);
+    assert_eq!(ErrorKind::from(&anyhow!("test")), ErrorKind::Other);
+}
+
+#[test]
+fn test_messages_fixture() {
+    let progress = |stage, current, total| Message::Progress(Progress { stage, current, total });
+    let error = |kind, message: &str| Message::Error(ErrorMessage { kind, message: message.to_owned() });
+
+    let messages = vec![
+        progress(Stage::Started, 0, 0),
+        progress(Stage::PreparingConfig, 0, 0),
+        progress(Stage::PreparingContent, 0, 0),
+        progress(Stage::UploadingContent, 5, 10),
+        progress(Stage::UploadingPreview, 1, 2),
+        progress(Stage::CommittingChanges, 0, 0),
+        progress(Stage::Downloading, 3, 4),
+        Message::Response(Value::Null),
+        Message::Response(json!({ "failed_items": [4], "failed_removals": [5], "published_file_id": 123 })),
+        error(ErrorKind::SteamInit, "SteamAPI_Init failed"),
+        error(ErrorKind::Workshop, "The item does not exist."),
+        error(ErrorKind::InvalidInput, "Invalid digit found in string."),
+        error(ErrorKind::Io, "File not found."),
+        error(ErrorKind::Other, "Something else failed."),
+    ];
+
+    // Runcher checks it can decode this file. If this fails, update the file and make sure Runcher still understands it.
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_files/ipc_messages.jsonl");
+    let fixture = read_to_string(path).unwrap();
+    let lines = messages.into_iter()
+        .map(|message| serde_json::to_string(&Envelope { version: PROTOCOL_VERSION, message }).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(fixture.lines().collect::<Vec<_>>(), lines);
+}
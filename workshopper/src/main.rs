@@ -28,9 +28,11 @@ use std::process::exit;
 use rpfm_lib::integrations::log::*;
 
 use crate::app::{Cli, Commands};
+use crate::ipc::IpcChannel;
 
 mod app;
 mod commands;
+mod ipc;
 
 /// Guess you know what this function does....
 fn main() {
@@ -44,16 +46,34 @@ fn main() {
     let cli = Cli::parse();
     info!("{:?}", cli.command);
 
+    // Connect to whoever called us, so we can report back to them.
+    let mut ipc = match IpcChannel::connect(&cli.ipc_channel) {
+        Ok(ipc) => ipc,
+        Err(error) => {
+            error!("Failed to connect to the IPC channel: {}", error);
+            exit(1);
+        }
+    };
+
     // Execute the commands.
     let (result, wait): (Result<()>, bool) = match cli.command {
-        Commands::DownloadSubscribedItems { steam_id, published_file_ids } => (crate::commands::ugc::download_subscribed_mods(steam_id, published_file_ids), true),
-        Commands::GetPublishedFileDetails { steam_id, published_file_ids, ipc_channel } => (crate::commands::ugc::published_file_details(steam_id, &published_file_ids, &ipc_channel), false),
-        Commands::Launch { base64, steam_id, command } => (crate::commands::launch_game(base64, steam_id, &command), false),
-        Commands::Upload { base64, steam_id, file_path, title, description, tags, changelog, visibility } => (crate::commands::ugc::upload(base64, steam_id, &file_path, &title, &description, &tags, &changelog, &visibility), true),
-        Commands::Update { base64, steam_id, published_file_id, file_path, title, description, tags, changelog, visibility } => (crate::commands::ugc::update(None, None, base64, PublishedFileId(published_file_id), steam_id, &file_path, &title, &description, &tags, &changelog, &visibility), true),
-        Commands::UserId { steam_id, ipc_channel } => (crate::commands::user_id(steam_id, &ipc_channel), false)
+        Commands::DownloadSubscribedItems { steam_id, published_file_ids, subscribe } => (crate::commands::ugc::download_subscribed_mods(&mut ipc, steam_id, published_file_ids, subscribe), true),
+        Commands::Subscribe { steam_id, published_file_ids, download } => (crate::commands::ugc::subscribe(steam_id, &published_file_ids, download), false),
+        Commands::Unsubscribe { steam_id, published_file_ids } => (crate::commands::ugc::unsubscribe(steam_id, &published_file_ids), false),
+        Commands::GetSubscribedItems { steam_id } => (crate::commands::ugc::subscribed_items(&mut ipc, steam_id), false),
+        Commands::GetPublishedFileDetails { steam_id, published_file_ids } => (crate::commands::ugc::published_file_details(&mut ipc, steam_id, &published_file_ids), false),
+        Commands::GetCollectionDetails { steam_id, published_file_id } => (crate::commands::ugc::collection_details(&mut ipc, steam_id, published_file_id), false),
+        Commands::PublishCollection { base64, steam_id, published_file_id, published_file_ids, title, description, visibility } => (crate::commands::ugc::publish_collection(&mut ipc, base64, steam_id, published_file_id, &published_file_ids, &title, &description, &visibility), false),
+        Commands::Launch { base64, steam_id, command } => (crate::commands::launch_game(&mut ipc, base64, steam_id, &command), false),
+        Commands::Upload { base64, steam_id, file_path, title, description, tags, preview_path, changelog, visibility } => (crate::commands::ugc::upload(&mut ipc, base64, steam_id, &file_path, &preview_path, &title, &description, &tags, &changelog, &visibility), true),
+        Commands::Update { base64, steam_id, published_file_id, file_path, title, description, tags, preview_path, changelog, visibility } => (crate::commands::ugc::update(&mut ipc, None, None, base64, PublishedFileId(published_file_id), steam_id, &file_path, &preview_path, &title, &description, &tags, &changelog, &visibility), true),
+        Commands::Batch { steam_id, manifest_path, result_path, continue_on_error } => (crate::commands::ugc::batch(&mut ipc, steam_id, &manifest_path, &result_path, continue_on_error), true),
+        Commands::UserId { steam_id } => (crate::commands::user_id(&mut ipc, steam_id), false)
     };
 
+    // Commands that return data answer by themselves. For the rest, this sends an empty response or the error.
+    ipc.finish(&result);
+
     // Output the result of the commands, then give people 60 seconds to read them before exiting.
     match result {
         Ok(_) => {